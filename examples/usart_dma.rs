@@ -49,6 +49,7 @@ fn main() -> ! {
             let res = serial
                 .rx
                 .read_all(rx_buf, rx_channel)
+                .unwrap()
                 .start()
                 .wait()
                 .unwrap();
@@ -63,6 +64,7 @@ fn main() -> ! {
             let res = serial
                 .tx
                 .write_all(tx_buf, tx_channel)
+                .unwrap()
                 .start()
                 .wait()
                 .expect("USART write shouldn't fail");
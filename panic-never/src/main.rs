@@ -0,0 +1,84 @@
+//! Link test for panic-free DMA transfers
+//!
+//! Uses `panic-never`, which makes linking fail, if any panicking code path is
+//! left in the binary after optimization. This makes sure that invalid DMA
+//! transfers are reported as errors, instead of panicking.
+//!
+//! Build with `cargo build --release --features=845` from this directory.
+
+#![no_main]
+#![no_std]
+
+use panic_never as _;
+
+use lpc8xx_hal::{cortex_m_rt::entry, usart, Peripherals};
+
+#[entry]
+fn main() -> ! {
+    let p = match Peripherals::take() {
+        Some(p) => p,
+        None => loop {},
+    };
+
+    let swm = p.SWM.split();
+    let mut syscon = p.SYSCON.split();
+
+    let dma = p.DMA.enable(&mut syscon.handle);
+    let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+
+    let clock_config = match usart::Clock::try_new_with_baudrate(115200) {
+        Ok(clock_config) => clock_config,
+        Err(_) => loop {},
+    };
+
+    let (u0_rxd, _) = swm
+        .movable_functions
+        .u0_rxd
+        .assign(p.pins.pio0_24.into_swm_pin(), &mut swm_handle);
+    let (u0_txd, _) = swm
+        .movable_functions
+        .u0_txd
+        .assign(p.pins.pio0_25.into_swm_pin(), &mut swm_handle);
+
+    let mut serial = p.USART0.enable_async(
+        &clock_config,
+        &mut syscon.handle,
+        u0_rxd,
+        u0_txd,
+        usart::Settings::default(),
+    );
+
+    let mut rx_channel = dma.channels.channel0;
+    let mut tx_channel = dma.channels.channel1;
+
+    static mut BUF: [u8; 4] = [0; 4];
+
+    loop {
+        // Sound, as the mutable reference is dropped before the next use of
+        // `BUF`.
+        let rx_buf = unsafe { &mut BUF };
+
+        let payload = match serial.rx.read_all(rx_buf, rx_channel) {
+            Ok(transfer) => match transfer.start().wait() {
+                Ok(payload) => payload,
+                Err((_, payload)) => payload,
+            },
+            Err((_, payload)) => payload,
+        };
+        rx_channel = payload.channel;
+        serial.rx = payload.source;
+
+        // Sound, as the mutable reference above has been dropped.
+        let tx_buf = unsafe { &BUF };
+
+        let payload = match serial.tx.write_all(tx_buf, tx_channel) {
+            Ok(transfer) => match transfer.start().wait() {
+                Ok(payload) => payload,
+                Err((_, payload)) => payload,
+            },
+            Err((_, payload)) => payload,
+        };
+        tx_channel = payload.channel;
+        serial.tx = payload.dest;
+    }
+}
@@ -35,10 +35,18 @@
 //!
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
-use embedded_hal::adc::{Channel, OneShot};
+use core::cmp;
+
+use embedded_hal::{
+    adc::{Channel, OneShot},
+    timer::CountDown,
+};
+use void::Void;
 
 use crate::{
-    init_state, pac, swm,
+    init_state,
+    mrt::{self, Ticks},
+    pac, swm,
     syscon::{self, clock_source::AdcClock},
 };
 
@@ -159,10 +167,16 @@ where
 
     /// Request that the ADC begin a conversion on the specified pin
     fn read(&mut self, _: &mut PIN) -> nb::Result<u16, Self::Error> {
+        Ok(self.convert(PIN::channel()))
+    }
+}
+
+impl ADC<init_state::Enabled> {
+    fn convert(&mut self, channel: u8) -> u16 {
         // Start the measurement of the given channel
         // Follows the description in the um
         self.adc.seq_ctrla.write(|w| {
-            unsafe { w.channels().bits(1 << PIN::channel()) };
+            unsafe { w.channels().bits(1 << channel) };
             w.start().set_bit();
             w.trigpol().set_bit();
             w.seq_ena().enabled();
@@ -177,8 +191,282 @@ where
         }
 
         // Returns the result as a 16 bit value
-        Ok(read.result().bits() << 4)
+        read.result().bits() << 4
+    }
+}
+
+/// The number of ADC channels
+const NUM_CHANNELS: usize = 12;
+
+/// Samples a set of ADC channels periodically
+///
+/// The scanner uses an MRT channel to pace the sampling. Each time the
+/// configured period has elapsed, [`Scanner::poll`] converts all enabled
+/// channels, and keeps track of the latest, minimum, and maximum value of each
+/// of them. Channels can have thresholds, which are checked on every
+/// conversion.
+///
+/// [`Scanner::poll`] needs to be called at least once per period, either from
+/// the main loop, or from an interrupt handler.
+///
+/// All values are scaled to 16 bits, like the values returned by
+/// [`ADC`]'s `OneShot` implementation.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     adc::Scanner,
+///     mrt::Ticks,
+///     syscon::clock_source::AdcClock,
+///     Peripherals,
+/// };
+/// use core::convert::TryFrom;
+///
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+/// let mut swm    = p.SWM.split();
+///
+/// #[cfg(feature = "82x")]
+/// let mut swm_handle = swm.handle;
+/// #[cfg(feature = "845")]
+/// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+///
+/// let adc_clock = AdcClock::new_default();
+/// let adc = p.ADC.enable(&adc_clock, &mut syscon.handle);
+/// let mrt = p.MRT0.split(&mut syscon.handle);
+///
+/// let (adc_0, _) = swm
+///     .fixed_functions
+///     .adc_0
+///     .assign(p.pins.pio0_7.into_swm_pin(), &mut swm_handle);
+///
+/// // Sample at 1 kHz, assuming the default system clock of 12 MHz.
+/// let period = Ticks::try_from(12_000).unwrap();
+/// let mut scanner = Scanner::new(adc, mrt.mrt0, period);
+///
+/// scanner.enable_channel(&adc_0);
+/// scanner.set_thresholds(&adc_0, 0x1000, 0xf000);
+///
+/// loop {
+///     let _ = scanner.poll(|violation| {
+///         // handle threshold violation
+///     });
+/// }
+/// ```
+///
+/// [`Scanner::poll`]: #method.poll
+/// [`ADC`]: struct.ADC.html
+pub struct Scanner<T: mrt::Trait> {
+    adc: ADC,
+    timer: mrt::Channel<T>,
+    channels: [Option<ChannelState>; NUM_CHANNELS],
+}
+
+impl<T> Scanner<T>
+where
+    T: mrt::Trait,
+{
+    /// Create a new `Scanner` and start its timer
+    ///
+    /// `period` is the time between two scans. No channels are enabled
+    /// initially.
+    pub fn new(adc: ADC, mut timer: mrt::Channel<T>, period: Ticks) -> Self {
+        timer.start(period);
+
+        Self {
+            adc,
+            timer,
+            channels: [None; NUM_CHANNELS],
+        }
+    }
+
+    /// Include a channel in the scan
+    ///
+    /// Has no effect, if the channel is already enabled.
+    pub fn enable_channel<PIN>(&mut self, _: &PIN)
+    where
+        PIN: Channel<ADC, ID = u8>,
+    {
+        let channel = &mut self.channels[usize::from(PIN::channel())];
+        if channel.is_none() {
+            *channel = Some(ChannelState::new());
+        }
+    }
+
+    /// Remove a channel from the scan
+    ///
+    /// This also discards its statistics and thresholds.
+    pub fn disable_channel<PIN>(&mut self, _: &PIN)
+    where
+        PIN: Channel<ADC, ID = u8>,
+    {
+        self.channels[usize::from(PIN::channel())] = None;
+    }
+
+    /// Set thresholds for a channel
+    ///
+    /// If a converted value is lower than `low` or higher than `high`, the
+    /// closure passed to [`poll`] is called. Has no effect, if the channel is
+    /// not enabled.
+    ///
+    /// [`poll`]: #method.poll
+    pub fn set_thresholds<PIN>(&mut self, _: &PIN, low: u16, high: u16)
+    where
+        PIN: Channel<ADC, ID = u8>,
+    {
+        if let Some(channel) = &mut self.channels[usize::from(PIN::channel())] {
+            channel.thresholds = Some((low, high));
+        }
     }
+
+    /// Remove the thresholds of a channel
+    pub fn clear_thresholds<PIN>(&mut self, _: &PIN)
+    where
+        PIN: Channel<ADC, ID = u8>,
+    {
+        if let Some(channel) = &mut self.channels[usize::from(PIN::channel())] {
+            channel.thresholds = None;
+        }
+    }
+
+    /// Returns the statistics of a channel
+    ///
+    /// Returns `None`, if the channel is not enabled or hasn't been sampled
+    /// since it was enabled, or since the statistics were reset.
+    pub fn stats<PIN>(&self, _: &PIN) -> Option<Stats>
+    where
+        PIN: Channel<ADC, ID = u8>,
+    {
+        self.channels[usize::from(PIN::channel())]
+            .as_ref()
+            .and_then(|channel| channel.stats)
+    }
+
+    /// Reset the statistics of all channels
+    pub fn reset_stats(&mut self) {
+        for channel in self.channels.iter_mut().flatten() {
+            channel.stats = None;
+        }
+    }
+
+    /// Scan all enabled channels, if the period has elapsed
+    ///
+    /// Returns `WouldBlock`, if the period hasn't elapsed yet. Otherwise, all
+    /// enabled channels are converted, and `on_violation` is called for every
+    /// value that violates the thresholds of its channel.
+    pub fn poll<F>(&mut self, mut on_violation: F) -> nb::Result<(), Void>
+    where
+        F: FnMut(Violation),
+    {
+        CountDown::wait(&mut self.timer)?;
+
+        for (i, channel) in self.channels.iter_mut().enumerate() {
+            if let Some(channel) = channel {
+                // Can't truncate, as there are only 12 channels.
+                let id = i as u8;
+                let value = self.adc.convert(id);
+
+                channel.update(value);
+
+                if let Some((low, high)) = channel.thresholds {
+                    let kind = if value < low {
+                        Some(ViolationKind::BelowLow)
+                    } else if value > high {
+                        Some(ViolationKind::AboveHigh)
+                    } else {
+                        None
+                    };
+
+                    if let Some(kind) = kind {
+                        on_violation(Violation {
+                            channel: id,
+                            value,
+                            kind,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the ADC and timer channel
+    pub fn free(self) -> (ADC, mrt::Channel<T>) {
+        (self.adc, self.timer)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ChannelState {
+    stats: Option<Stats>,
+    thresholds: Option<(u16, u16)>,
+}
+
+impl ChannelState {
+    fn new() -> Self {
+        Self {
+            stats: None,
+            thresholds: None,
+        }
+    }
+
+    fn update(&mut self, value: u16) {
+        self.stats = Some(match self.stats {
+            Some(stats) => Stats {
+                latest: value,
+                min: cmp::min(stats.min, value),
+                max: cmp::max(stats.max, value),
+            },
+            None => Stats {
+                latest: value,
+                min: value,
+                max: value,
+            },
+        });
+    }
+}
+
+/// Statistics of a channel, as tracked by [`Scanner`]
+///
+/// [`Scanner`]: struct.Scanner.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    /// The most recently converted value
+    pub latest: u16,
+
+    /// The lowest value since the statistics were last reset
+    pub min: u16,
+
+    /// The highest value since the statistics were last reset
+    pub max: u16,
+}
+
+/// A threshold violation, as reported by [`Scanner::poll`]
+///
+/// [`Scanner::poll`]: struct.Scanner.html#method.poll
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Violation {
+    /// The channel on which the violation occurred
+    pub channel: u8,
+
+    /// The value that violated the threshold
+    pub value: u16,
+
+    /// Which threshold was violated
+    pub kind: ViolationKind,
+}
+
+/// Indicates which threshold was violated
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ViolationKind {
+    /// The value was lower than the low threshold
+    BelowLow,
+
+    /// The value was higher than the high threshold
+    AboveHigh,
 }
 
 macro_rules! adc_channel {
@@ -195,14 +483,21 @@ macro_rules! adc_channel {
     };
 }
 
+#[cfg(not(feature = "20"))]
 adc_channel!(ADC_0, 0);
+#[cfg(not(feature = "20"))]
 adc_channel!(ADC_1, 1);
 adc_channel!(ADC_2, 2);
 adc_channel!(ADC_3, 3);
+#[cfg(not(feature = "20"))]
 adc_channel!(ADC_4, 4);
+#[cfg(not(feature = "20"))]
 adc_channel!(ADC_5, 5);
+#[cfg(not(feature = "20"))]
 adc_channel!(ADC_6, 6);
+#[cfg(not(feature = "20"))]
 adc_channel!(ADC_7, 7);
+#[cfg(not(feature = "20"))]
 adc_channel!(ADC_8, 8);
 adc_channel!(ADC_9, 9);
 adc_channel!(ADC_10, 10);
@@ -0,0 +1,413 @@
+//! Software I2C master
+//!
+//! Implements an I2C master on two arbitrary GPIO pins, for boards on which
+//! the pins of the I2C peripherals are not available.
+//!
+//! The GPIO peripheral doesn't support open-drain outputs on all pins, so open
+//! drain is emulated by switching the pins between output (to pull the line
+//! LOW) and input (to release it). Both lines need external pull-up resistors.
+//!
+//! Clock stretching by the slave is supported. If the slave holds SCL LOW for
+//! longer than the configured timeout, the transaction is aborted.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     prelude::*,
+//!     Peripherals,
+//!     bitbang,
+//!     gpio,
+//!     mrt::Ticks,
+//!     pins,
+//! };
+//! use core::convert::TryFrom;
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let gpio = p.GPIO;
+//! #[cfg(feature = "845")]
+//! let gpio = p.GPIO.enable(&mut syscon.handle);
+//!
+//! let mrt = p.MRT0.split(&mut syscon.handle);
+//!
+//! let scl = p.pins.pio0_12.into_dynamic_pin(
+//!     gpio.tokens.pio0_12,
+//!     gpio::Level::Low,
+//!     pins::DynamicPinDirection::Input,
+//! );
+//! let sda = p.pins.pio0_13.into_dynamic_pin(
+//!     gpio.tokens.pio0_13,
+//!     gpio::Level::Low,
+//!     pins::DynamicPinDirection::Input,
+//! );
+//!
+//! // 100 kHz at the default system clock of 12 MHz
+//! let half_period = Ticks::try_from(12_000_000 / 100_000 / 2).unwrap();
+//!
+//! let mut i2c = bitbang::i2c::Master::new(scl, sda, mrt.mrt0, half_period);
+//! i2c.write(0x48, &[0x01, 0x02]);
+//! ```
+
+use embedded_hal::{blocking::i2c, timer::CountDown};
+
+use crate::{
+    gpio::{direction, GpioPin, Level},
+    mrt::{self, Ticks},
+    pins,
+};
+
+/// Software I2C master
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::blocking::i2c::Read`] for blocking reads
+/// - [`embedded_hal::blocking::i2c::Write`] for blocking writes
+/// - [`embedded_hal::blocking::i2c::WriteRead`] for a write, followed by a
+///   read, with a repeated start in between
+///
+/// [`embedded_hal::blocking::i2c::Read`]: #impl-Read
+/// [`embedded_hal::blocking::i2c::Write`]: #impl-Write
+/// [`embedded_hal::blocking::i2c::WriteRead`]: #impl-WriteRead
+pub struct Master<Scl, Sda, T: mrt::Trait> {
+    scl: GpioPin<Scl, direction::Dynamic>,
+    sda: GpioPin<Sda, direction::Dynamic>,
+    timer: mrt::Channel<T>,
+    half_period: Ticks,
+    stretch_timeout: u32,
+}
+
+impl<Scl, Sda, T> Master<Scl, Sda, T>
+where
+    Scl: pins::Trait,
+    Sda: pins::Trait,
+    T: mrt::Trait,
+{
+    /// Create a new instance of `Master`
+    ///
+    /// `half_period` is half the period of SCL, as a number of MRT ticks. For
+    /// a given bus frequency, that is the system clock frequency divided by
+    /// twice the bus frequency.
+    ///
+    /// Both pins are released, meaning they are switched to input. The clock
+    /// stretching timeout is set to 1000 half periods, which can be changed
+    /// using [`set_stretch_timeout`].
+    ///
+    /// [`set_stretch_timeout`]: #method.set_stretch_timeout
+    pub fn new(
+        mut scl: GpioPin<Scl, direction::Dynamic>,
+        mut sda: GpioPin<Sda, direction::Dynamic>,
+        timer: mrt::Channel<T>,
+        half_period: Ticks,
+    ) -> Self {
+        scl.switch_to_input();
+        sda.switch_to_input();
+
+        Self {
+            scl,
+            sda,
+            timer,
+            half_period,
+            stretch_timeout: 1000,
+        }
+    }
+
+    /// Set the clock stretching timeout
+    ///
+    /// `half_periods` is the maximum time, in SCL half periods, that a slave
+    /// is allowed to hold SCL LOW, before [`Error::SclTimeout`] is returned.
+    ///
+    /// [`Error::SclTimeout`]: enum.Error.html#variant.SclTimeout
+    pub fn set_stretch_timeout(&mut self, half_periods: u32) {
+        self.stretch_timeout = half_periods;
+    }
+
+    /// Return the pins and timer channel
+    pub fn free(
+        self,
+    ) -> (
+        GpioPin<Scl, direction::Dynamic>,
+        GpioPin<Sda, direction::Dynamic>,
+        mrt::Channel<T>,
+    ) {
+        (self.scl, self.sda, self.timer)
+    }
+
+    fn wait(&mut self) {
+        // Can't fail. The error type is `Void`.
+        let _ = nb::block!(CountDown::wait(&mut self.timer));
+    }
+
+    fn release_scl(&mut self) -> Result<(), Error> {
+        self.scl.switch_to_input();
+
+        // The slave might be stretching the clock.
+        let mut waited = 0;
+        while self.scl.get_level() == Level::Low {
+            if waited >= self.stretch_timeout {
+                return Err(Error::SclTimeout);
+            }
+            self.wait();
+            waited += 1;
+        }
+
+        Ok(())
+    }
+
+    fn start(&mut self) -> Result<(), Error> {
+        // Starts the timer, which keeps running periodically for the rest of
+        // the transaction.
+        self.timer.start(self.half_period);
+
+        self.sda.switch_to_input();
+        self.release_scl()?;
+        if self.sda.get_level() == Level::Low {
+            // Someone else is using the bus.
+            return Err(Error::ArbitrationLoss);
+        }
+
+        self.sda.switch_to_output(Level::Low);
+        self.wait();
+        self.scl.switch_to_output(Level::Low);
+
+        Ok(())
+    }
+
+    fn repeated_start(&mut self) -> Result<(), Error> {
+        self.sda.switch_to_input();
+        self.wait();
+        self.release_scl()?;
+        self.wait();
+        self.sda.switch_to_output(Level::Low);
+        self.wait();
+        self.scl.switch_to_output(Level::Low);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Error> {
+        self.sda.switch_to_output(Level::Low);
+        self.wait();
+        self.release_scl()?;
+        self.wait();
+        self.sda.switch_to_input();
+        self.wait();
+
+        Ok(())
+    }
+
+    fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+        if bit {
+            self.sda.switch_to_input();
+        } else {
+            self.sda.switch_to_output(Level::Low);
+        }
+        self.wait();
+        self.release_scl()?;
+        self.wait();
+
+        if bit && self.sda.get_level() == Level::Low {
+            // Another master is pulling SDA low.
+            self.sda.switch_to_input();
+            return Err(Error::ArbitrationLoss);
+        }
+
+        self.scl.switch_to_output(Level::Low);
+
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> Result<bool, Error> {
+        self.sda.switch_to_input();
+        self.wait();
+        self.release_scl()?;
+        self.wait();
+        let bit = self.sda.get_level() == Level::High;
+        self.scl.switch_to_output(Level::Low);
+
+        Ok(bit)
+    }
+
+    /// Writes a byte and returns whether it was acknowledged
+    fn write_byte(&mut self, byte: u8) -> Result<bool, Error> {
+        for i in (0..8).rev() {
+            self.write_bit(byte & (0x01 << i) != 0)?;
+        }
+
+        let nack = self.read_bit()?;
+        Ok(!nack)
+    }
+
+    fn read_byte(&mut self, ack: bool) -> Result<u8, Error> {
+        let mut byte = 0;
+        for _ in 0..8 {
+            byte <<= 1;
+            if self.read_bit()? {
+                byte |= 0x01;
+            }
+        }
+
+        self.write_bit(!ack)?;
+        Ok(byte)
+    }
+
+    fn write_part(&mut self, address: u8, bytes: &[u8]) -> Result<(), Error> {
+        if !self.write_byte(address << 1)? {
+            return Err(Error::AddressNack);
+        }
+        for &byte in bytes {
+            if !self.write_byte(byte)? {
+                return Err(Error::DataNack);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_part(
+        &mut self,
+        address: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        if !self.write_byte(address << 1 | 0x01)? {
+            return Err(Error::AddressNack);
+        }
+
+        let len = buffer.len();
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            // The last byte is not acknowledged, to signal the end of the read
+            // to the slave.
+            *byte = self.read_byte(i + 1 < len)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends a stop condition after `result`, unless arbitration was lost
+    fn finish(&mut self, result: Result<(), Error>) -> Result<(), Error> {
+        match result {
+            Err(Error::ArbitrationLoss) => {
+                // The bus belongs to another master now. Don't interfere.
+                self.scl.switch_to_input();
+                self.sda.switch_to_input();
+                result
+            }
+            result => {
+                let stop = self.stop();
+                result.and(stop)
+            }
+        }
+    }
+}
+
+impl<Scl, Sda, T> i2c::Write for Master<Scl, Sda, T>
+where
+    Scl: pins::Trait,
+    Sda: pins::Trait,
+    T: mrt::Trait,
+{
+    type Error = Error;
+
+    /// Write to the I2C bus
+    ///
+    /// Please refer to the [embedded-hal documentation] for details.
+    ///
+    /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.1/embedded_hal/blocking/i2c/trait.Write.html#tymethod.write
+    fn write(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
+        Error::check_address(address)?;
+
+        self.start()?;
+        let result = self.write_part(address, data);
+        self.finish(result)
+    }
+}
+
+impl<Scl, Sda, T> i2c::Read for Master<Scl, Sda, T>
+where
+    Scl: pins::Trait,
+    Sda: pins::Trait,
+    T: mrt::Trait,
+{
+    type Error = Error;
+
+    /// Read from the I2C bus
+    ///
+    /// Please refer to the [embedded-hal documentation] for details.
+    ///
+    /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.1/embedded_hal/blocking/i2c/trait.Read.html#tymethod.read
+    fn read(
+        &mut self,
+        address: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        Error::check_address(address)?;
+
+        self.start()?;
+        let result = self.read_part(address, buffer);
+        self.finish(result)
+    }
+}
+
+impl<Scl, Sda, T> i2c::WriteRead for Master<Scl, Sda, T>
+where
+    Scl: pins::Trait,
+    Sda: pins::Trait,
+    T: mrt::Trait,
+{
+    type Error = Error;
+
+    /// Write to, then read from the I2C bus
+    ///
+    /// Please refer to the [embedded-hal documentation] for details.
+    ///
+    /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.1/embedded_hal/blocking/i2c/trait.WriteRead.html#tymethod.write_read
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        Error::check_address(address)?;
+
+        self.start()?;
+        let result = self
+            .write_part(address, bytes)
+            .and_then(|()| self.repeated_start())
+            .and_then(|()| self.read_part(address, buffer));
+        self.finish(result)
+    }
+}
+
+/// Software I2C error
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The slave did not acknowledge its address
+    AddressNack,
+
+    /// The slave did not acknowledge a data byte
+    DataNack,
+
+    /// Another master is using the bus
+    ArbitrationLoss,
+
+    /// A slave held SCL LOW for longer than the clock stretching timeout
+    SclTimeout,
+
+    /// An unencodable address was specified.
+    ///
+    /// Currently, only seven-bit addressing is implemented.
+    AddressOutOfRange,
+}
+
+impl Error {
+    fn check_address(address: u8) -> Result<(), Self> {
+        if address > 0b111_1111 {
+            return Err(Self::AddressOutOfRange);
+        }
+
+        Ok(())
+    }
+}
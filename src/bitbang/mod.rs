@@ -0,0 +1,17 @@
+//! Software implementations of serial protocols
+//!
+//! The drivers in this module implement serial protocols in software, using
+//! GPIO pins and MRT channels. They are intended as a fallback, for situations
+//! in which the hardware peripherals are already in use, or the pins that they
+//! can be assigned to are not available.
+//!
+//! All of these drivers busy-wait on an MRT channel for timing. Interrupts that
+//! fire during a transfer will distort the timing, so depending on your
+//! application, you might have to run transfers within a critical section
+//! (see [`cortex_m::interrupt::free`]).
+//!
+//! [`cortex_m::interrupt::free`]: https://docs.rs/cortex-m/0.7/cortex_m/interrupt/fn.free.html
+
+pub mod i2c;
+pub mod onewire;
+pub mod usart;
@@ -0,0 +1,342 @@
+//! 1-Wire master
+//!
+//! Implements a 1-Wire master (as used by DS18B20 temperature sensors, for
+//! example) on an arbitrary GPIO pin, including ROM search.
+//!
+//! Open drain is emulated by switching the pin between output (to pull the
+//! line LOW) and input (to release it). The line needs an external pull-up
+//! resistor.
+//!
+//! The timing is generated by an MRT channel, which is assumed to run at the
+//! default system clock frequency of 12 MHz. Standard speed is supported;
+//! overdrive speed is not.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     Peripherals,
+//!     bitbang::onewire::{self, OneWire},
+//!     gpio,
+//!     pins,
+//! };
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let gpio = p.GPIO;
+//! #[cfg(feature = "845")]
+//! let gpio = p.GPIO.enable(&mut syscon.handle);
+//!
+//! let mrt = p.MRT0.split(&mut syscon.handle);
+//!
+//! let pin = p.pins.pio0_12.into_dynamic_pin(
+//!     gpio.tokens.pio0_12,
+//!     gpio::Level::Low,
+//!     pins::DynamicPinDirection::Input,
+//! );
+//!
+//! let mut bus = OneWire::new(pin, mrt.mrt0);
+//!
+//! let mut search = onewire::Search::new();
+//! while let Some(rom) = bus.search_next(&mut search).unwrap() {
+//!     // `rom` is the ROM code of a device on the bus
+//! }
+//! ```
+
+use embedded_hal::timer::CountDown;
+
+use crate::{
+    gpio::{direction, GpioPin, Level},
+    mrt::{self, Ticks},
+    pins,
+};
+
+/// Number of MRT ticks per microsecond, at a system clock of 12 MHz
+const TICKS_PER_US: u32 = 12;
+
+/// ROM command: Address all devices on the bus
+pub const SKIP_ROM: u8 = 0xcc;
+
+/// ROM command: Address a single device, identified by its ROM code
+pub const MATCH_ROM: u8 = 0x55;
+
+/// ROM command: Start a ROM search
+pub const SEARCH_ROM: u8 = 0xf0;
+
+/// A 1-Wire ROM code
+///
+/// The first byte is the family code, the last byte is the CRC.
+pub type Rom = [u8; 8];
+
+/// 1-Wire master
+pub struct OneWire<P, T: mrt::Trait> {
+    pin: GpioPin<P, direction::Dynamic>,
+    timer: mrt::Channel<T>,
+}
+
+impl<P, T> OneWire<P, T>
+where
+    P: pins::Trait,
+    T: mrt::Trait,
+{
+    /// Create a new instance of `OneWire`
+    ///
+    /// The pin is released, meaning it is switched to input.
+    pub fn new(
+        mut pin: GpioPin<P, direction::Dynamic>,
+        timer: mrt::Channel<T>,
+    ) -> Self {
+        pin.switch_to_input();
+        Self { pin, timer }
+    }
+
+    /// Return the pin and timer channel
+    pub fn free(self) -> (GpioPin<P, direction::Dynamic>, mrt::Channel<T>) {
+        (self.pin, self.timer)
+    }
+
+    /// Send a reset pulse
+    ///
+    /// Returns an error, if no device responds with a presence pulse.
+    pub fn reset(&mut self) -> Result<(), Error> {
+        self.pin.switch_to_input();
+        if self.pin.get_level() == Level::Low {
+            return Err(Error::BusShorted);
+        }
+
+        self.pin.switch_to_output(Level::Low);
+        self.delay_us(480);
+        self.pin.switch_to_input();
+        self.delay_us(70);
+        let presence = self.pin.get_level() == Level::Low;
+        self.delay_us(410);
+
+        if !presence {
+            return Err(Error::NoPresence);
+        }
+
+        Ok(())
+    }
+
+    /// Write a single bit
+    pub fn write_bit(&mut self, bit: bool) {
+        self.pin.switch_to_output(Level::Low);
+        if bit {
+            self.delay_us(6);
+            self.pin.switch_to_input();
+            self.delay_us(64);
+        } else {
+            self.delay_us(60);
+            self.pin.switch_to_input();
+            self.delay_us(10);
+        }
+    }
+
+    /// Read a single bit
+    pub fn read_bit(&mut self) -> bool {
+        self.pin.switch_to_output(Level::Low);
+        self.delay_us(6);
+        self.pin.switch_to_input();
+        self.delay_us(9);
+        let bit = self.pin.get_level() == Level::High;
+        self.delay_us(55);
+
+        bit
+    }
+
+    /// Write a byte, least significant bit first
+    pub fn write_byte(&mut self, byte: u8) {
+        for i in 0..8 {
+            self.write_bit(byte & (0x01 << i) != 0);
+        }
+    }
+
+    /// Read a byte, least significant bit first
+    pub fn read_byte(&mut self) -> u8 {
+        let mut byte = 0;
+        for i in 0..8 {
+            if self.read_bit() {
+                byte |= 0x01 << i;
+            }
+        }
+
+        byte
+    }
+
+    /// Write multiple bytes
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_byte(byte);
+        }
+    }
+
+    /// Read multiple bytes
+    pub fn read_bytes(&mut self, buffer: &mut [u8]) {
+        for byte in buffer {
+            *byte = self.read_byte();
+        }
+    }
+
+    /// Reset the bus and address all devices
+    ///
+    /// The next command is received by all devices on the bus.
+    pub fn skip_rom(&mut self) -> Result<(), Error> {
+        self.reset()?;
+        self.write_byte(SKIP_ROM);
+        Ok(())
+    }
+
+    /// Reset the bus and address the device with the given ROM code
+    ///
+    /// The next command is only received by that device.
+    pub fn match_rom(&mut self, rom: &Rom) -> Result<(), Error> {
+        self.reset()?;
+        self.write_byte(MATCH_ROM);
+        self.write_bytes(rom);
+        Ok(())
+    }
+
+    /// Find the next device on the bus
+    ///
+    /// Returns `Ok(None)`, once all devices have been found. Use the same
+    /// instance of [`Search`] for all calls that belong to the same search.
+    ///
+    /// [`Search`]: struct.Search.html
+    pub fn search_next(
+        &mut self,
+        search: &mut Search,
+    ) -> Result<Option<Rom>, Error> {
+        if search.finished {
+            return Ok(None);
+        }
+
+        self.reset()?;
+        self.write_byte(SEARCH_ROM);
+
+        let mut last_zero = 0;
+
+        for bit_number in 1..=64 {
+            let index = usize::from((bit_number - 1) / 8);
+            let mask = 0x01 << ((bit_number - 1) % 8);
+
+            let bit = self.read_bit();
+            let complement = self.read_bit();
+
+            let direction = match (bit, complement) {
+                // No device responded.
+                (true, true) => return Err(Error::NoPresence),
+
+                // All remaining devices have the same bit here.
+                (false, true) => false,
+                (true, false) => true,
+
+                // Discrepancy: Devices with both values are present.
+                (false, false) => {
+                    let direction = if bit_number < search.last_discrepancy {
+                        search.rom[index] & mask != 0
+                    } else {
+                        bit_number == search.last_discrepancy
+                    };
+
+                    if !direction {
+                        last_zero = bit_number;
+                    }
+
+                    direction
+                }
+            };
+
+            if direction {
+                search.rom[index] |= mask;
+            } else {
+                search.rom[index] &= !mask;
+            }
+            self.write_bit(direction);
+        }
+
+        search.last_discrepancy = last_zero;
+        search.finished = last_zero == 0;
+
+        if crc8(&search.rom) != 0 {
+            return Err(Error::CrcMismatch);
+        }
+
+        Ok(Some(search.rom))
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        // Sound, as all delays used in this module are small constants.
+        let ticks = unsafe { Ticks::from_u32(us * TICKS_PER_US) };
+
+        self.timer.start(ticks);
+        // Can't fail. The error type is `Void`.
+        let _ = nb::block!(CountDown::wait(&mut self.timer));
+    }
+}
+
+/// State of a ROM search
+///
+/// Pass this to [`OneWire::search_next`] repeatedly, to find all devices on the
+/// bus.
+///
+/// [`OneWire::search_next`]: struct.OneWire.html#method.search_next
+pub struct Search {
+    rom: Rom,
+    last_discrepancy: u8,
+    finished: bool,
+}
+
+impl Search {
+    /// Start a new ROM search
+    pub fn new() -> Self {
+        Self {
+            rom: [0; 8],
+            last_discrepancy: 0,
+            finished: false,
+        }
+    }
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compute the 1-Wire CRC-8 of `data`
+///
+/// Computing the CRC over data that includes its CRC byte results in zero, if
+/// the data is valid.
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0;
+    for &byte in data {
+        let mut byte = byte;
+        for _ in 0..8 {
+            let mix = (crc ^ byte) & 0x01;
+            crc >>= 1;
+            if mix != 0 {
+                crc ^= 0x8c;
+            }
+            byte >>= 1;
+        }
+    }
+
+    crc
+}
+
+/// 1-Wire error
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// No device responded
+    NoPresence,
+
+    /// The line was LOW before a reset pulse was sent
+    BusShorted,
+
+    /// A ROM code with an invalid CRC was received during a search
+    CrcMismatch,
+}
@@ -0,0 +1,262 @@
+//! Software UART
+//!
+//! Implements an 8N1 UART transmitter ([`Tx`]) and receiver ([`Rx`]) on
+//! arbitrary GPIO pins. Each of them needs its own MRT channel for timing.
+//!
+//! Both implement the same `embedded-hal` traits as the hardware USART
+//! driver, so they can be used as a drop-in replacement.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     prelude::*,
+//!     Peripherals,
+//!     bitbang,
+//!     gpio,
+//!     mrt::Ticks,
+//! };
+//! use core::convert::TryFrom;
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let gpio = p.GPIO;
+//! #[cfg(feature = "845")]
+//! let gpio = p.GPIO.enable(&mut syscon.handle);
+//!
+//! let mrt = p.MRT0.split(&mut syscon.handle);
+//!
+//! let tx_pin = p.pins.pio0_12.into_output_pin(
+//!     gpio.tokens.pio0_12,
+//!     gpio::Level::High,
+//! );
+//!
+//! // 9600 baud at the default system clock of 12 MHz
+//! let bit_period = Ticks::try_from(12_000_000 / 9600).unwrap();
+//!
+//! let mut tx = bitbang::usart::Tx::new(tx_pin, mrt.mrt0, bit_period);
+//! tx.bwrite_all(b"Hello, world!");
+//! ```
+//!
+//! [`Tx`]: struct.Tx.html
+//! [`Rx`]: struct.Rx.html
+
+use embedded_hal::{
+    blocking::serial::write::Default as BlockingWriteDefault,
+    serial::{Read, Write},
+    timer::CountDown,
+};
+use void::Void;
+
+use crate::{
+    gpio::{direction, GpioPin},
+    mrt::{self, Ticks},
+    pins,
+    usart::Error,
+};
+
+/// Software UART transmitter
+///
+/// Transmits 8N1 frames on a GPIO output pin. The pin should be set to
+/// [`Level::High`] (the idle level) before it is passed to [`Tx::new`].
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::serial::Write`] for writing single bytes
+/// - [`embedded_hal::blocking::serial::Write`] for writing multiple bytes
+///
+/// [`Level::High`]: ../../gpio/enum.Level.html#variant.High
+/// [`Tx::new`]: #method.new
+/// [`embedded_hal::serial::Write`]: #impl-Write%3Cu8%3E
+/// [`embedded_hal::blocking::serial::Write`]: #impl-Write%3CWord%3E
+pub struct Tx<P, T: mrt::Trait> {
+    pin: GpioPin<P, direction::Output>,
+    timer: mrt::Channel<T>,
+    bit_period: Ticks,
+}
+
+impl<P, T> Tx<P, T>
+where
+    P: pins::Trait,
+    T: mrt::Trait,
+{
+    /// Create a new instance of `Tx`
+    ///
+    /// `bit_period` is the duration of a single bit, as a number of MRT ticks.
+    /// For a given baud rate, that is the system clock frequency divided by
+    /// the baud rate.
+    pub fn new(
+        pin: GpioPin<P, direction::Output>,
+        timer: mrt::Channel<T>,
+        bit_period: Ticks,
+    ) -> Self {
+        Self {
+            pin,
+            timer,
+            bit_period,
+        }
+    }
+
+    /// Return the pin and timer channel
+    pub fn free(self) -> (GpioPin<P, direction::Output>, mrt::Channel<T>) {
+        (self.pin, self.timer)
+    }
+
+    fn wait(&mut self) {
+        // Can't fail. The error type is `Void`.
+        let _ = nb::block!(CountDown::wait(&mut self.timer));
+    }
+}
+
+impl<P, T> Write<u8> for Tx<P, T>
+where
+    P: pins::Trait,
+    T: mrt::Trait,
+{
+    type Error = Void;
+
+    /// Write a single byte
+    ///
+    /// The whole frame is transmitted before this method returns, so it never
+    /// returns `WouldBlock`.
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        // The MRT channel is periodic, so starting it once and waiting for it
+        // repeatedly keeps the bit edges aligned to the timer.
+        self.timer.start(self.bit_period);
+
+        // Start bit
+        self.pin.set_low();
+        self.wait();
+
+        for i in 0..8 {
+            if word & (0x01 << i) != 0 {
+                self.pin.set_high();
+            } else {
+                self.pin.set_low();
+            }
+            self.wait();
+        }
+
+        // Stop bit
+        self.pin.set_high();
+        self.wait();
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<P, T> BlockingWriteDefault<u8> for Tx<P, T>
+where
+    P: pins::Trait,
+    T: mrt::Trait,
+{
+}
+
+/// Software UART receiver
+///
+/// Receives 8N1 frames on a GPIO input pin.
+///
+/// There is no hardware that detects the start bit, so [`Rx`] needs to be
+/// polled frequently enough to catch it, ideally in a tight loop. The start
+/// bit is detected late by however long it takes between two polls, so this
+/// delay must be small compared to the bit period.
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::serial::Read`] for non-blocking reads
+///
+/// [`Rx`]: struct.Rx.html
+/// [`embedded_hal::serial::Read`]: #impl-Read%3Cu8%3E
+pub struct Rx<P, T: mrt::Trait> {
+    pin: GpioPin<P, direction::Input>,
+    timer: mrt::Channel<T>,
+    bit_period: Ticks,
+}
+
+impl<P, T> Rx<P, T>
+where
+    P: pins::Trait,
+    T: mrt::Trait,
+{
+    /// Create a new instance of `Rx`
+    ///
+    /// `bit_period` is the duration of a single bit, as a number of MRT ticks.
+    /// For a given baud rate, that is the system clock frequency divided by
+    /// the baud rate.
+    pub fn new(
+        pin: GpioPin<P, direction::Input>,
+        timer: mrt::Channel<T>,
+        bit_period: Ticks,
+    ) -> Self {
+        Self {
+            pin,
+            timer,
+            bit_period,
+        }
+    }
+
+    /// Return the pin and timer channel
+    pub fn free(self) -> (GpioPin<P, direction::Input>, mrt::Channel<T>) {
+        (self.pin, self.timer)
+    }
+
+    fn wait(&mut self) {
+        // Can't fail. The error type is `Void`.
+        let _ = nb::block!(CountDown::wait(&mut self.timer));
+    }
+}
+
+impl<P, T> Read<u8> for Rx<P, T>
+where
+    P: pins::Trait,
+    T: mrt::Trait,
+{
+    type Error = Error<u8>;
+
+    /// Read a single byte
+    ///
+    /// Returns `WouldBlock`, if the line is idle. Otherwise, the whole frame is
+    /// received before this method returns.
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        if self.pin.is_high() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        // Move to the middle of the start bit.
+        //
+        // Sound, as half of a valid tick value is still a valid tick value.
+        let half_period =
+            unsafe { Ticks::from_u32(self.bit_period.to_u32() / 2) };
+        self.timer.start(half_period);
+        self.wait();
+
+        if self.pin.is_high() {
+            // The line went back to idle. This was a glitch, not a start bit.
+            return Err(nb::Error::WouldBlock);
+        }
+
+        // From here on, sample in the middle of each bit.
+        self.timer.start(self.bit_period);
+
+        let mut word = 0;
+        for i in 0..8 {
+            self.wait();
+            if self.pin.is_high() {
+                word |= 0x01 << i;
+            }
+        }
+
+        // Stop bit
+        self.wait();
+        if self.pin.is_low() {
+            return Err(nb::Error::Other(Error::Framing(word)));
+        }
+
+        Ok(word)
+    }
+}
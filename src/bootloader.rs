@@ -0,0 +1,100 @@
+//! Utilities for bootloaders
+//!
+//! Provides what a bootloader built on LPC8xx HAL needs to hand off control to
+//! an application: Putting the core into a clean state ([`deinit`]) and
+//! jumping to the application ([`jump_to_application`]).
+//!
+//! The Cortex-M0+ cores in the LPC8xx parts implement the VTOR register, so
+//! the application's vector table can be used directly from flash. No RAM
+//! remapping or trampoline is required.
+//!
+//! Peripherals that were used by the bootloader should be disabled using
+//! their respective `disable` methods before jumping to the application, so
+//! the application finds them in their reset state. Please note that LPC8xx
+//! HAL assumes the default clock configuration. If the bootloader changed any
+//! clock settings, it must restore them before handing off control.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::bootloader;
+//!
+//! // Address of the application's vector table
+//! const APPLICATION: u32 = 0x0000_2000;
+//!
+//! unsafe {
+//!     bootloader::deinit();
+//!     bootloader::jump_to_application(APPLICATION);
+//! }
+//! ```
+//!
+//! [`deinit`]: fn.deinit.html
+//! [`jump_to_application`]: fn.jump_to_application.html
+
+use cortex_m::{
+    asm, interrupt,
+    peripheral::{NVIC, SCB, SYST},
+};
+
+/// Put the core into a clean state for an application
+///
+/// Disables interrupts, the SysTick timer, and all interrupts in the NVIC, and
+/// clears all pending interrupts. Interrupts stay disabled (PRIMASK is set)
+/// when this function returns.
+///
+/// # Safety
+///
+/// This function modifies core peripherals, ignoring any instances that might
+/// exist. The caller must make sure that nothing relies on the previous state
+/// of those peripherals, which is the case if this function is called right
+/// before [`jump_to_application`].
+///
+/// [`jump_to_application`]: fn.jump_to_application.html
+pub unsafe fn deinit() {
+    interrupt::disable();
+
+    let syst = &*SYST::PTR;
+    syst.csr.write(0);
+    syst.rvr.write(0);
+    syst.cvr.write(0);
+
+    // The Cortex-M0+ only supports up to 32 interrupts, so one register is
+    // enough.
+    let nvic = &*NVIC::PTR;
+    nvic.icer[0].write(0xffff_ffff);
+    nvic.icpr[0].write(0xffff_ffff);
+
+    // Clear pending PendSV and SysTick exceptions.
+    let scb = &*SCB::PTR;
+    scb.icsr.write(0x1 << 27 | 0x1 << 25);
+}
+
+/// Jump to the application whose vector table is at `address`
+///
+/// Points VTOR to the application's vector table, then loads the stack pointer
+/// from its first entry and jumps to the reset handler from its second one.
+///
+/// Interrupts are left as they are. If [`deinit`] was called before, they stay
+/// disabled (PRIMASK is set), and the application must enable them itself,
+/// after it has initialized its memory.
+///
+/// `address` must be aligned to 256 bytes, as required by VTOR on the
+/// Cortex-M0+.
+///
+/// # Safety
+///
+/// `address` must point to a valid vector table of an application. This
+/// function never returns, and since it replaces the stack pointer, no code
+/// that was running before must ever be resumed. Call [`deinit`] before this
+/// function, to make sure no interrupts or exceptions from the bootloader fire
+/// in the application.
+///
+/// [`deinit`]: fn.deinit.html
+pub unsafe fn jump_to_application(address: u32) -> ! {
+    let scb = &*SCB::PTR;
+    scb.vtor.write(address);
+
+    // Sets the stack pointer and jumps to the reset handler in assembly, so no
+    // Rust code runs on the application's stack.
+    asm::bootload(address as *const u32)
+}
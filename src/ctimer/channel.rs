@@ -1,9 +1,11 @@
 //! Contains types related to CTIMER PWM channels
 
-use core::{convert::Infallible, marker::PhantomData};
+use core::marker::PhantomData;
+
+#[cfg(feature = "embedded-hal-1")]
+use core::convert::{Infallible, TryFrom};
 
 use embedded_hal::PwmPin;
-use embedded_hal_alpha::pwm::PwmPin as PwmPinAlpha;
 
 use crate::{
     init_state::Enabled,
@@ -12,6 +14,7 @@ use crate::{
         CTIMER0,
     },
     reg_proxy::RegProxy,
+    units::Percent,
 };
 
 use self::state::Attached;
@@ -37,6 +40,24 @@ impl<T, PeripheralState, State> Channel<T, PeripheralState, State> {
     }
 }
 
+impl<T> Channel<T, Enabled, Attached>
+where
+    T: Trait,
+{
+    /// Sets a new duty cycle, as a percentage of the period
+    ///
+    /// Use this together with the [`U32Ext`] extension trait, to write duty
+    /// cycles like `50.percent()`.
+    ///
+    /// [`U32Ext`]: ../../units/trait.U32Ext.html
+    pub fn set_duty_percent(&mut self, duty: Percent) {
+        let duty = duty.of(self.mr[3].read().match_().bits());
+        unsafe {
+            self.msr[T::ID as usize].write(|w| w.match_shadow().bits(duty))
+        };
+    }
+}
+
 impl<T> PwmPin for Channel<T, Enabled, Attached>
 where
     T: Trait,
@@ -71,42 +92,46 @@ where
     }
 }
 
-impl<T> PwmPinAlpha for Channel<T, Enabled, Attached>
+#[cfg(feature = "embedded-hal-1")]
+impl<T> embedded_hal_1::pwm::ErrorType for Channel<T, Enabled, Attached>
 where
     T: Trait,
 {
     type Error = Infallible;
-    type Duty = u32;
-
-    /// The behaviour of `enable` is implementation defined and does nothing in
-    /// this implementation
-    fn try_enable(&mut self) -> Result<(), Self::Error> {
-        Ok(())
-    }
-
-    /// The behaviour of `disable` is implementation defined and does nothing in
-    /// this implementation
-    // Accessing pwmc would require some kind of lock, which is inconvenient
-    // and would involve a hidden `CriticalSection`
-    fn try_disable(&mut self) -> Result<(), Self::Error> {
-        Ok(())
-    }
-
-    /// Returns the current duty cycle
-    fn try_get_duty(&self) -> Result<Self::Duty, Self::Error> {
-        Ok(self.msr[T::ID as usize].read().match_shadow().bits())
-    }
+}
 
+#[cfg(feature = "embedded-hal-1")]
+impl<T> embedded_hal_1::pwm::SetDutyCycle for Channel<T, Enabled, Attached>
+where
+    T: Trait,
+{
     /// Returns the maximum duty cycle value
-    fn try_get_max_duty(&self) -> Result<Self::Duty, Self::Error> {
-        Ok(self.mr[3].read().match_().bits())
+    ///
+    /// This is the period of the timer, or `u16::MAX`, if the period doesn't
+    /// fit into a `u16`. In the latter case, duty cycles are scaled to the
+    /// period in [`set_duty_cycle`].
+    ///
+    /// [`set_duty_cycle`]: #method.set_duty_cycle
+    fn max_duty_cycle(&self) -> u16 {
+        let period = self.mr[3].read().match_().bits();
+        u16::try_from(period).unwrap_or(u16::MAX)
     }
 
     /// Sets a new duty cycle
-    fn try_set_duty(&mut self, duty: Self::Duty) -> Result<(), Self::Error> {
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        let period = u64::from(self.mr[3].read().match_().bits());
+        let max = u64::from(self.max_duty_cycle());
+
+        // Can't overflow, as the result is at most `period`.
+        let duty = (u64::from(duty).min(max) * period)
+            .checked_div(max)
+            .unwrap_or(0) as u32;
+
         unsafe {
-            Ok(self.msr[T::ID as usize].write(|w| w.match_shadow().bits(duty)))
-        }
+            self.msr[T::ID as usize].write(|w| w.match_shadow().bits(duty))
+        };
+
+        Ok(())
     }
 }
 
@@ -24,10 +24,6 @@ use cortex_m::peripheral::syst::SystClkSource;
 
 use crate::pac::SYST;
 use embedded_hal::blocking::delay::{DelayMs, DelayUs};
-use embedded_hal_alpha::blocking::delay::{
-    DelayMs as DelayMsAlpha, DelayUs as DelayUsAlpha,
-};
-use void::Void;
 
 const SYSTICK_RANGE: u32 = 0x0100_0000;
 const SYSTEM_CLOCK: u32 = 12_000_000;
@@ -37,9 +33,11 @@ const SYSTEM_CLOCK: u32 = 12_000_000;
 /// # `embedded-hal` traits
 /// - [`embedded_hal::blocking::delay::DelayUs`]
 /// - [`embedded_hal::blocking::delay::DelayMs`]
+/// - [`embedded_hal_1::delay::DelayNs`]
 ///
 /// [`embedded_hal::blocking::delay::DelayUs`]: #impl-DelayUs%3Cu32%3E
 /// [`embedded_hal::blocking::delay::DelayMs`]: #impl-DelayMs%3Cu32%3E
+/// [`embedded_hal_1::delay::DelayNs`]: #impl-DelayNs
 #[derive(Clone)]
 pub struct Delay {
     scale: u32,
@@ -76,15 +74,6 @@ impl DelayMs<u32> for Delay {
     }
 }
 
-impl DelayMsAlpha<u32> for Delay {
-    type Error = Void;
-
-    /// Pauses execution for `ms` milliseconds
-    fn try_delay_ms(&mut self, ms: u32) -> Result<(), Self::Error> {
-        Ok(self.delay_ms(ms))
-    }
-}
-
 impl DelayMs<u16> for Delay {
     /// Pauses execution for `ms` milliseconds
     fn delay_ms(&mut self, ms: u16) {
@@ -94,15 +83,6 @@ impl DelayMs<u16> for Delay {
     }
 }
 
-impl DelayMsAlpha<u16> for Delay {
-    type Error = Void;
-
-    /// Pauses execution for `ms` milliseconds
-    fn try_delay_ms(&mut self, ms: u16) -> Result<(), Self::Error> {
-        Ok(self.delay_ms(ms))
-    }
-}
-
 impl DelayMs<u8> for Delay {
     /// Pauses execution for `ms` milliseconds
     fn delay_ms(&mut self, ms: u8) {
@@ -110,15 +90,6 @@ impl DelayMs<u8> for Delay {
     }
 }
 
-impl DelayMsAlpha<u8> for Delay {
-    type Error = Void;
-
-    /// Pauses execution for `ms` milliseconds
-    fn try_delay_ms(&mut self, ms: u8) -> Result<(), Self::Error> {
-        Ok(self.delay_ms(ms))
-    }
-}
-
 // At 30MHz (the maximum frequency), this overflows at approx. 2^32 / 30 = 146 seconds
 impl DelayUs<u32> for Delay {
     /// Pauses execution for `us` microseconds
@@ -149,15 +120,6 @@ impl DelayUs<u32> for Delay {
     }
 }
 
-impl DelayUsAlpha<u32> for Delay {
-    type Error = Void;
-
-    /// Pauses execution for `us` microseconds
-    fn try_delay_us(&mut self, us: u32) -> Result<(), Self::Error> {
-        Ok(self.delay_us(us))
-    }
-}
-
 impl DelayUs<u16> for Delay {
     /// Pauses execution for `us` microseconds
     fn delay_us(&mut self, us: u16) {
@@ -165,15 +127,6 @@ impl DelayUs<u16> for Delay {
     }
 }
 
-impl DelayUsAlpha<u16> for Delay {
-    type Error = Void;
-
-    /// Pauses execution for `us` microseconds
-    fn try_delay_us(&mut self, us: u16) -> Result<(), Self::Error> {
-        Ok(self.delay_us(us))
-    }
-}
-
 impl DelayUs<u8> for Delay {
     /// Pauses execution for `us` microseconds
     fn delay_us(&mut self, us: u8) {
@@ -181,11 +134,23 @@ impl DelayUs<u8> for Delay {
     }
 }
 
-impl DelayUsAlpha<u8> for Delay {
-    type Error = Void;
+#[cfg(feature = "embedded-hal-1")]
+impl embedded_hal_1::delay::DelayNs for Delay {
+    /// Pauses execution for at least `ns` nanoseconds
+    ///
+    /// The delay is rounded up to full microseconds.
+    fn delay_ns(&mut self, ns: u32) {
+        let us = ns.div_ceil(1_000);
+        DelayUs::<u32>::delay_us(self, us);
+    }
 
     /// Pauses execution for `us` microseconds
-    fn try_delay_us(&mut self, us: u8) -> Result<(), Self::Error> {
-        Ok(self.delay_us(us))
+    fn delay_us(&mut self, us: u32) {
+        DelayUs::<u32>::delay_us(self, us);
+    }
+
+    /// Pauses execution for `ms` milliseconds
+    fn delay_ms(&mut self, ms: u32) {
+        DelayMs::<u32>::delay_ms(self, ms);
     }
 }
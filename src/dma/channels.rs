@@ -8,8 +8,8 @@ use crate::{
         self,
         dma0::{
             channel::{CFG, XFERCFG},
-            ACTIVE0, BUSY0, ENABLESET0, ERRINT0, INTA0, INTB0, INTENCLR0,
-            INTENSET0, SETTRIG0,
+            ACTIVE0, BUSY0, ENABLECLR0, ENABLESET0, ERRINT0, INTA0, INTB0,
+            INTENCLR0, INTENSET0, SETTRIG0,
         },
     },
     reg_proxy::{Reg, RegProxy},
@@ -115,6 +115,7 @@ pub trait Instance {
 pub(super) struct SharedRegisters<C> {
     active0: &'static ACTIVE0,
     busy0: &'static BUSY0,
+    enableclr0: &'static ENABLECLR0,
     enableset0: &'static ENABLESET0,
     errint0: &'static ERRINT0,
     inta0: &'static INTA0,
@@ -141,6 +142,7 @@ where
             Self {
                 active0: &(*registers).active0,
                 busy0: &(*registers).busy0,
+                enableclr0: &(*registers).enableclr0,
                 enableset0: &(*registers).enableset0,
                 errint0: &(*registers).errint0,
                 inta0: &(*registers).inta0,
@@ -175,6 +177,13 @@ where
         });
     }
 
+    pub(super) fn disable(&self) {
+        self.enableclr0.write(|w| {
+            // Sound, as all values assigned to `C::FLAG` are valid here.
+            unsafe { w.clr().bits(C::FLAG) }
+        });
+    }
+
     pub(super) fn trigger(&self) {
         self.settrig0.write(|w| {
             // Sound, as all values assigned to `C::FLAG` are valid here.
@@ -209,4 +218,14 @@ where
         self.inta0.write(|w| unsafe { w.bits(C::FLAG) });
         self.intb0.write(|w| unsafe { w.bits(C::FLAG) });
     }
+
+    pub(super) fn reset_a_flag(&self) {
+        // Sound, as all `FLAG` values are valid in this register.
+        self.inta0.write(|w| unsafe { w.bits(C::FLAG) });
+    }
+
+    pub(super) fn reset_b_flag(&self) {
+        // Sound, as all `FLAG` values are valid in this register.
+        self.intb0.write(|w| unsafe { w.bits(C::FLAG) });
+    }
 }
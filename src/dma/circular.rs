@@ -0,0 +1,510 @@
+use core::{
+    ptr,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use crate::{init_state::Enabled, pac::dma0::channel::xfercfg::DSTINC_A};
+
+use super::{
+    channels::{Instance, SharedRegisters},
+    descriptors::{ChannelDescriptor, RELOAD_DESCRIPTORS},
+    transfer::InvalidTransfer,
+    Channel, Payload, Source,
+};
+
+/// The maximum number of transfers in each half of the ring buffer
+///
+/// XFERCOUNT could hold up to 1024 transfers. One less is allowed, so
+/// `write_pos` can tell a completed half from a half that has just started.
+const MAX_HALF_LEN: usize = 1023;
+
+/// A circular DMA transfer from a peripheral into a ring buffer
+///
+/// Once started, a `CircularTransfer` never finishes on its own. It
+/// continuously writes the data received by the peripheral into a ring buffer,
+/// wrapping around at the end of the buffer. The received data can be read
+/// out using [`read`].
+///
+/// The ring buffer is split into two halves. Each half is a separate DMA
+/// transfer, and the hardware reloads the next one automatically. INTA is set
+/// whenever the first half has been filled, INTB whenever the second half has
+/// been filled. This serves as a high watermark that can be used to trigger an
+/// interrupt (see [`enable_interrupts`] and [`watermark_reached`]).
+///
+/// # Limitations
+///
+/// If the data isn't read out in time, the DMA overwrites it. [`read`]
+/// detects this and returns [`Overrun`]. Each call to [`read`] or
+/// [`watermark_reached`] can only detect a single wrap-around of the buffer
+/// since the previous call, so make sure to call one of them at least once
+/// per half of the buffer. Doing so from the DMA interrupt, whenever the
+/// watermark is reached, is one way to achieve this.
+///
+/// Instances of this struct can be acquired by calling a `read_circular`
+/// method of the peripheral that should be involved in the transfer.
+///
+/// [`read`]: #method.read
+/// [`enable_interrupts`]: #method.enable_interrupts
+/// [`watermark_reached`]: #method.watermark_reached
+/// [`Overrun`]: struct.Overrun.html
+pub struct CircularTransfer<C, S>
+where
+    C: Instance,
+{
+    payload: Payload<C, S, &'static mut [u8]>,
+    read_pos: usize,
+    last_write_pos: usize,
+    overrun: bool,
+}
+
+impl<C, S> CircularTransfer<C, S>
+where
+    C: Instance,
+    S: Source,
+{
+    /// Create and start a new circular DMA transfer
+    ///
+    /// Returns an error, if the length of `buffer` is odd, 0, or larger than
+    /// 2046, or if `source` is not a valid peripheral. The error is returned together with
+    /// all resources that were passed to this function.
+    ///
+    /// # Limitations
+    ///
+    /// The caller must make sure to call this method only for the correct
+    /// combination of channel and source.
+    pub(crate) fn start(
+        channel: Channel<C, Enabled>,
+        source: S,
+        buffer: &'static mut [u8],
+    ) -> Result<Self, (InvalidTransfer, Payload<C, S, &'static mut [u8]>)> {
+        if let Err(error) = check(&source, buffer) {
+            return Err((
+                error,
+                Payload {
+                    channel,
+                    source,
+                    dest: buffer,
+                },
+            ));
+        }
+
+        compiler_fence(Ordering::SeqCst);
+
+        let half = buffer.len() / 2;
+
+        // The reload descriptors need the raw XFERCFG values. We get them by
+        // writing each configuration to the register, and reading it back.
+        // This is fine, as the channel hasn't been triggered yet. The first
+        // half goes last, as that's the configuration the transfer starts
+        // with.
+        // See user manual, section 12.6.18.
+        let second_config =
+            configure_half(&channel, &source, half, Half::Second);
+        let first_config = configure_half(&channel, &source, half, Half::First);
+
+        let source_end = source.end_addr();
+
+        // Sound, as we stay within the bounds of the buffer.
+        let first_end = unsafe { buffer.as_mut_ptr().add(half - 1) };
+        let second_end = unsafe { buffer.as_mut_ptr().add(buffer.len() - 1) };
+
+        // Sound, as we own the channel, and only access the reload descriptors
+        // that belong to it.
+        let reload = unsafe { &mut RELOAD_DESCRIPTORS[C::INDEX] };
+
+        // The two reload descriptors point at each other, which makes the
+        // transfer wrap around endlessly.
+        // See user manual, section 12.5.3.
+        let first_desc: *const ChannelDescriptor = &reload[0];
+        let second_desc: *const ChannelDescriptor = &reload[1];
+        reload[0] = ChannelDescriptor {
+            config: first_config,
+            source_end,
+            dest_end: first_end,
+            next_desc: second_desc,
+        };
+        reload[1] = ChannelDescriptor {
+            config: second_config,
+            source_end,
+            dest_end: second_end,
+            next_desc: first_desc,
+        };
+
+        // Configure channel
+        // See user manual, section 12.6.16.
+        channel.cfg.write(|w| {
+            w.periphreqen().enabled();
+            w.hwtrigen().disabled();
+            unsafe { w.chpriority().bits(0) }
+        });
+
+        // Configure channel descriptor
+        // See user manual, sections 12.5.2 and 12.5.3.
+        channel.descriptor.source_end = source_end;
+        channel.descriptor.dest_end = first_end;
+        channel.descriptor.next_desc = second_desc;
+
+        compiler_fence(Ordering::SeqCst);
+
+        let registers = SharedRegisters::<C>::new();
+        registers.reset_flags();
+        registers.enable();
+        registers.trigger();
+
+        Ok(Self {
+            payload: Payload {
+                channel,
+                source,
+                dest: buffer,
+            },
+            read_pos: 0,
+            last_write_pos: 0,
+            overrun: false,
+        })
+    }
+
+    /// Returns the number of bytes that have been received, but not read yet
+    ///
+    /// Doesn't detect overruns. If the DMA has overwritten data that hasn't
+    /// been read yet, the returned value is wrong, and [`read`] will return an
+    /// error.
+    ///
+    /// [`read`]: #method.read
+    pub fn available(&self) -> usize {
+        let len = self.payload.dest.len();
+        (self.write_pos() + len - self.read_pos) % len
+    }
+
+    /// Reads received data into `buf`
+    ///
+    /// Copies as many bytes as are available, or as fit into `buf`, whichever
+    /// is less. Returns the number of bytes copied.
+    ///
+    /// Returns [`Overrun`], if the DMA has overwritten data that hadn't been
+    /// read yet, since the last call to this method. All data in the buffer
+    /// is discarded in that case, and the next call continues with newly
+    /// received data. Please refer to the [struct documentation] for the
+    /// limitations of this check.
+    ///
+    /// [`Overrun`]: struct.Overrun.html
+    /// [struct documentation]: #limitations
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize, Overrun> {
+        let registers = SharedRegisters::<C>::new();
+
+        let wrapped = registers.b_interrupt_fired();
+        if wrapped {
+            registers.reset_b_flag();
+        }
+        self.sync(wrapped);
+
+        if self.overrun {
+            self.overrun = false;
+            return Err(Overrun);
+        }
+
+        let write_pos = self.last_write_pos;
+        let len = self.payload.dest.len();
+
+        compiler_fence(Ordering::SeqCst);
+
+        let mut n = 0;
+        for b in buf {
+            if self.read_pos == write_pos {
+                break;
+            }
+
+            // Sound, as we stay within the bounds of the buffer. The read must
+            // be volatile, as the DMA writes to the buffer behind our back.
+            *b = unsafe {
+                ptr::read_volatile(
+                    self.payload.dest.as_ptr().add(self.read_pos),
+                )
+            };
+
+            self.read_pos = (self.read_pos + 1) % len;
+            n += 1;
+        }
+
+        Ok(n)
+    }
+
+    /// Enable interrupts for this transfer's channel
+    ///
+    /// The interrupt fires whenever the watermark is reached, meaning one half
+    /// of the buffer has been filled.
+    pub fn enable_interrupts(&mut self) {
+        self.payload.channel.enable_interrupts();
+    }
+
+    /// Disable interrupts for this transfer's channel
+    pub fn disable_interrupts(&mut self) {
+        self.payload.channel.disable_interrupts();
+    }
+
+    /// Indicates whether the watermark has been reached
+    ///
+    /// Returns `true`, if one half of the buffer has been filled since the
+    /// last call. Resets the INTA and INTB flags, which also clears a pending
+    /// interrupt.
+    ///
+    /// Also checks for overruns, which are then reported by the next call to
+    /// [`read`].
+    ///
+    /// [`read`]: #method.read
+    pub fn watermark_reached(&mut self) -> bool {
+        let registers = SharedRegisters::<C>::new();
+
+        // Only reset the flags we've seen, so a wrap-around that happens in
+        // between isn't lost.
+        let a = registers.a_interrupt_fired();
+        let b = registers.b_interrupt_fired();
+        if a {
+            registers.reset_a_flag();
+        }
+        if b {
+            registers.reset_b_flag();
+        }
+
+        self.sync(b);
+
+        a || b
+    }
+
+    /// Indicates whether the error interrupt fired
+    ///
+    /// Corresponds to the channel's flag in the ERRINT0 register.
+    pub fn error_interrupt_fired(&self) -> bool {
+        let registers = SharedRegisters::<C>::new();
+        registers.error_interrupt_fired()
+    }
+
+    /// Stop the transfer
+    ///
+    /// Consumes this instance of `CircularTransfer` and returns the transfer
+    /// payload, which contains all resources that were held by this transfer.
+    /// Any data that has not been read yet is discarded.
+    pub fn stop(self) -> Payload<C, S, &'static mut [u8]> {
+        let registers = SharedRegisters::<C>::new();
+
+        // See user manual, section 12.6.1.
+        registers.disable();
+        while registers.is_busy() {}
+        registers.reset_flags();
+
+        self.payload.channel.descriptor.next_desc = ptr::null();
+
+        compiler_fence(Ordering::SeqCst);
+
+        self.payload
+    }
+
+    /// Track the DMA's progress since the last call
+    ///
+    /// `wrapped` indicates that the INTB flag was set, meaning the DMA has
+    /// wrapped around to the start of the buffer. The caller must have reset
+    /// the flag before calling this method.
+    ///
+    /// Sets the overrun flag and discards all unread data, if the DMA has
+    /// overwritten data that hadn't been read yet.
+    fn sync(&mut self, wrapped: bool) {
+        let len = self.payload.dest.len();
+        let prev = self.last_write_pos;
+        let pos = self.write_pos();
+
+        if !wrapped && pos < prev {
+            // The DMA wrapped around after the flag was checked. Reset the
+            // flag, so this wrap-around isn't counted again next time.
+            SharedRegisters::<C>::new().reset_b_flag();
+        }
+
+        self.last_write_pos = pos;
+
+        if is_overrun(len, self.read_pos, prev, pos, wrapped) {
+            self.read_pos = pos;
+            self.overrun = true;
+        }
+    }
+
+    /// Returns the position in the buffer that the DMA writes to next
+    fn write_pos(&self) -> usize {
+        // Read the register once, so the half and the count are consistent.
+        let xfercfg = self.payload.channel.xfercfg.read();
+
+        write_pos(
+            self.payload.dest.len(),
+            xfercfg.setintb().bit_is_set(),
+            xfercfg.xfercount().bits(),
+        )
+    }
+}
+
+/// Indicates that data was lost in a circular DMA transfer
+///
+/// The DMA has overwritten data in the ring buffer, before it could be read.
+/// Returned by [`CircularTransfer::read`].
+///
+/// [`CircularTransfer::read`]: struct.CircularTransfer.html#method.read
+#[derive(Debug, Eq, PartialEq)]
+pub struct Overrun;
+
+/// The two halves of the ring buffer
+enum Half {
+    First,
+    Second,
+}
+
+/// Check that a circular transfer can be created
+fn check(source: &impl Source, buffer: &[u8]) -> Result<(), InvalidTransfer> {
+    if buffer.is_empty() {
+        return Err(InvalidTransfer::EmptyBuffer);
+    }
+    if !buffer.len().is_multiple_of(2) {
+        return Err(InvalidTransfer::OddLength);
+    }
+    if buffer.len() / 2 > MAX_HALF_LEN {
+        return Err(InvalidTransfer::BufferTooLarge);
+    }
+    if source.transfer_count().is_some() {
+        return Err(InvalidTransfer::Unsupported);
+    }
+    if !source.is_valid() {
+        return Err(InvalidTransfer::InvalidSource);
+    }
+
+    Ok(())
+}
+
+/// Computes the position in the buffer that the DMA writes to next
+///
+/// `second_half` is the SETINTB bit of XFERCFG, which is only set in the
+/// configuration of the second half. `xfercount` is the XFERCOUNT field.
+fn write_pos(len: usize, second_half: bool, xfercount: u16) -> usize {
+    let half = len / 2;
+    let base = if second_half { half } else { 0 };
+
+    // XFERCOUNT counts down during the transfer, starting at `half - 1`. After
+    // the last transfer of a half, it wraps to its maximum value, until the
+    // next descriptor has been loaded. As `check` limits each half to 1023
+    // transfers, that value is never reached otherwise.
+    let written = if usize::from(xfercount) >= MAX_HALF_LEN {
+        half
+    } else {
+        half - (usize::from(xfercount) + 1)
+    };
+
+    (base + written) % len
+}
+
+/// Indicates whether the DMA has overwritten data that wasn't read yet
+///
+/// The DMA has moved from `prev` to `pos` since the last check, `wrapped`
+/// indicating that it passed the end of the buffer. `read_pos` is the position
+/// of the first unread byte.
+fn is_overrun(
+    len: usize,
+    read_pos: usize,
+    prev: usize,
+    pos: usize,
+    wrapped: bool,
+) -> bool {
+    let advance = if wrapped || pos < prev {
+        pos + len - prev
+    } else {
+        pos - prev
+    };
+    let unread = (prev + len - read_pos) % len;
+
+    unread + advance >= len
+}
+
+/// Configure XFERCFG for one half of the ring buffer
+///
+/// Returns the resulting register value, for use in the reload descriptors.
+/// See user manual, section 12.6.18.
+fn configure_half<C, S>(
+    channel: &Channel<C, Enabled>,
+    source: &S,
+    half_len: usize,
+    half: Half,
+) -> u32
+where
+    C: Instance,
+    S: Source,
+{
+    // The cast is fine, as `check` limits each half to 1023 transfers.
+    let transfer_count = (half_len - 1) as u16;
+
+    let first = match half {
+        Half::First => true,
+        Half::Second => false,
+    };
+
+    channel.xfercfg.write(|w| {
+        w.cfgvalid().valid();
+        w.reload().enabled();
+        w.swtrig().not_set();
+        // Keep the channel triggered, when the next half is loaded.
+        w.clrtrig().clear_bit();
+        w.setinta().bit(first);
+        w.setintb().bit(!first);
+        w.width().bit_8();
+        w.srcinc().variant(source.increment());
+        w.dstinc().variant(DSTINC_A::WIDTH_X_1);
+
+        // Sound, as `check` limits each half to 1023 bytes.
+        unsafe { w.xfercount().bits(transfer_count) }
+    });
+
+    channel.xfercfg.read().bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_overrun, write_pos, MAX_HALF_LEN};
+
+    #[test]
+    fn write_pos_should_count_down_within_each_half() {
+        // First half, nothing written yet.
+        assert_eq!(write_pos(8, false, 3), 0);
+        // First half, three bytes written.
+        assert_eq!(write_pos(8, false, 0), 3);
+        // Second half, one byte written.
+        assert_eq!(write_pos(8, true, 2), 5);
+    }
+
+    #[test]
+    fn write_pos_should_treat_wrapped_count_as_complete_half() {
+        // The first half is complete, but the second hasn't been loaded yet.
+        assert_eq!(write_pos(8, false, 0x3ff), 4);
+        // The second half is complete, so the DMA continues at the start.
+        assert_eq!(write_pos(8, true, 0x3ff), 0);
+    }
+
+    #[test]
+    fn write_pos_should_handle_largest_halves() {
+        let len = MAX_HALF_LEN * 2;
+
+        // Start of the first half.
+        assert_eq!(write_pos(len, false, MAX_HALF_LEN as u16 - 1), 0);
+        // Last byte of the first half still missing.
+        assert_eq!(write_pos(len, false, 0), MAX_HALF_LEN - 1);
+        // First half complete.
+        assert_eq!(write_pos(len, false, 0x3ff), MAX_HALF_LEN);
+        // Second half complete.
+        assert_eq!(write_pos(len, true, 0x3ff), 0);
+    }
+
+    #[test]
+    fn is_overrun_should_allow_filling_up_to_one_less_than_len() {
+        assert!(!is_overrun(8, 0, 0, 7, false));
+        assert!(!is_overrun(8, 6, 6, 5, true));
+    }
+
+    #[test]
+    fn is_overrun_should_detect_overwritten_data() {
+        // The DMA caught up with the read position.
+        assert!(is_overrun(8, 2, 4, 2, true));
+        // The DMA went all the way around.
+        assert!(is_overrun(8, 3, 3, 3, true));
+    }
+}
@@ -2,6 +2,17 @@ use core::ptr;
 
 pub(super) static mut DESCRIPTORS: DescriptorTable = DescriptorTable::new();
 
+/// Reload descriptors for circular transfers
+///
+/// Contains two descriptors for each DMA channel, one for each half of the
+/// buffer. These are only linked into use by [`CircularTransfer`], so the
+/// linker can remove them from programs that don't use circular transfers.
+///
+/// [`CircularTransfer`]: ../struct.CircularTransfer.html
+pub(super) static mut RELOAD_DESCRIPTORS: [[ChannelDescriptor; 2];
+    target::NUM_CHANNELS] =
+    [[ChannelDescriptor::new(); 2]; target::NUM_CHANNELS];
+
 /// The channel descriptor table
 ///
 /// Contains a descriptor for each DMA channel.
@@ -20,10 +31,10 @@ impl DescriptorTable {
 #[derive(Clone, Copy)]
 #[repr(C, align(16))]
 pub(super) struct ChannelDescriptor {
-    config: u32,
+    pub(super) config: u32,
     pub(super) source_end: *const u8,
     pub(super) dest_end: *mut u8,
-    next_desc: *const ChannelDescriptor,
+    pub(super) next_desc: *const ChannelDescriptor,
 }
 
 impl ChannelDescriptor {
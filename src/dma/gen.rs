@@ -28,14 +28,16 @@ macro_rules! channels {
             pub(super) fn new(descriptors: &'static mut DescriptorTable)
                 -> Self
             {
-                let mut descriptors = (&mut descriptors.0).into_iter();
+                // Destructuring the array makes sure at compile-time that there
+                // is exactly one descriptor per channel.
+                let [$($field,)*] = &mut descriptors.0;
 
                 Channels {
                     $(
                         $field: Channel {
                             ty        : $name(()),
                             _state    : Disabled,
-                            descriptor: descriptors.next().unwrap(),
+                            descriptor: $field,
 
                             cfg    : RegProxy::new(),
                             xfercfg: RegProxy::new(),
@@ -9,6 +9,7 @@
 //! [`DMA`]: struct.DMA.html
 
 mod buffer;
+mod circular;
 mod descriptors;
 mod gen;
 mod peripheral;
@@ -18,6 +19,7 @@ pub mod transfer;
 
 pub use self::{
     channels::Channel,
+    circular::{CircularTransfer, Overrun},
     gen::*,
     peripheral::DMA,
     transfer::{Dest, Payload, Source, Transfer},
@@ -45,10 +45,10 @@ where
 {
     /// Create a new DMA transfer
     ///
-    /// # Panics
-    ///
-    /// Panics, if the length of any buffer passed to this function is 0 or
-    /// larger than 1024.
+    /// Returns an error, if the length of any buffer passed to this function is
+    /// 0 or larger than 1024, or if the combination of source and destination
+    /// is not supported. The error is returned together with all resources that
+    /// were passed to this function.
     ///
     /// # Limitations
     ///
@@ -58,26 +58,23 @@ where
         channel: Channel<C, Enabled>,
         source: S,
         mut dest: D,
-    ) -> Self {
-        assert!(!source.is_empty());
-        assert!(!dest.is_full());
-        assert!(source.is_valid());
-        assert!(dest.is_valid());
-
-        compiler_fence(Ordering::SeqCst);
-
-        // Currently we don't support memory-to-memory transfers, which means
-        // exactly one participant is providing the transfer count.
-        let source_count = source.transfer_count();
-        let dest_count = dest.transfer_count();
-        let transfer_count = match (source_count, dest_count) {
-            (Some(transfer_count), None) => transfer_count,
-            (None, Some(transfer_count)) => transfer_count,
-            _ => {
-                panic!("Unsupported transfer type");
+    ) -> Result<Self, (InvalidTransfer, Payload<C, S, D>)> {
+        let transfer_count = match check(&source, &dest) {
+            Ok(transfer_count) => transfer_count,
+            Err(error) => {
+                return Err((
+                    error,
+                    Payload {
+                        channel,
+                        source,
+                        dest,
+                    },
+                ))
             }
         };
 
+        compiler_fence(Ordering::SeqCst);
+
         // Configure channel
         // See user manual, section 12.6.16.
         channel.cfg.write(|w| {
@@ -106,14 +103,19 @@ where
         channel.descriptor.source_end = source.end_addr();
         channel.descriptor.dest_end = dest.end_addr();
 
-        Self {
+        Ok(Self {
             _state: state::Ready,
             payload: Payload {
                 channel,
                 source,
                 dest,
             },
-        }
+        })
+    }
+
+    /// Return the resources of a transfer that hasn't been started
+    pub(crate) fn free(self) -> Payload<C, S, D> {
+        self.payload
     }
 
     /// Set INTA flag when this transfer is complete
@@ -319,6 +321,62 @@ where
     }
 }
 
+/// Checks whether a transfer is valid, and returns its transfer count
+///
+/// Currently we don't support memory-to-memory transfers, which means exactly
+/// one participant is providing the transfer count.
+pub(crate) fn check<S, D>(source: &S, dest: &D) -> Result<u16, InvalidTransfer>
+where
+    S: Source,
+    D: Dest,
+{
+    if source.is_empty() || dest.is_full() {
+        return Err(InvalidTransfer::EmptyBuffer);
+    }
+    if !source.is_valid() || !dest.is_valid() {
+        return Err(InvalidTransfer::BufferTooLarge);
+    }
+
+    match (source.transfer_count(), dest.transfer_count()) {
+        (Some(transfer_count), None) => Ok(transfer_count),
+        (None, Some(transfer_count)) => Ok(transfer_count),
+        _ => Err(InvalidTransfer::Unsupported),
+    }
+}
+
+/// Indicates that a DMA transfer can't be created
+///
+/// Returned by the methods that create DMA transfers, together with the
+/// resources that were passed to them.
+#[derive(Debug, Eq, PartialEq)]
+pub enum InvalidTransfer {
+    /// A buffer has a length of 0
+    EmptyBuffer,
+
+    /// A buffer is larger than 1024 words
+    ///
+    /// For circular transfers, each half of the ring buffer is limited to 1023
+    /// words.
+    BufferTooLarge,
+
+    /// The ring buffer of a circular transfer has an odd length
+    ///
+    /// The ring buffer is split into two halves of equal length.
+    OddLength,
+
+    /// The transfer is not between memory and a peripheral
+    ///
+    /// Other kinds of transfers are not supported yet.
+    Unsupported,
+
+    /// The source of a circular transfer is not valid
+    ///
+    /// See [`Source::is_valid`].
+    ///
+    /// [`Source::is_valid`]: trait.Source.html#tymethod.is_valid
+    InvalidSource,
+}
+
 /// Error that can occur while waiting for the DMA transfer to finish
 #[derive(Debug)]
 pub enum Error<S, D> {
@@ -0,0 +1,232 @@
+//! Crate-wide error type
+//!
+//! Each peripheral API defines its own error types, which describe what went
+//! wrong in terms of that peripheral. All of them can be converted into
+//! [`Error`], which only retains the general category of the error. This is
+//! useful for application code that uses multiple peripherals and just wants
+//! to propagate errors using `?`, without writing a conversion for each of
+//! them.
+//!
+//! [`Error`]: enum.Error.html
+
+use crate::{
+    bitbang, dma, framing, gpio, i2c, mrt, pmu, safety, selftest, spi, usart,
+};
+
+/// The category of an error that occured in LPC8xx HAL
+///
+/// All error types of the peripheral APIs can be converted into this type.
+/// This conversion loses information, so please match on the original error,
+/// if you need to handle a specific error condition.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error was detected on a bus or transmission line
+    ///
+    /// This includes USART framing, parity, and noise errors, as well as I2C
+    /// arbitration loss and protocol errors.
+    Bus,
+
+    /// An operation didn't complete in time
+    Timeout,
+
+    /// Data was lost, as a buffer wasn't read or written in time
+    ///
+    /// This includes both overruns and underruns.
+    Overrun,
+
+    /// Automatic baud rate detection failed
+    ///
+    /// The baud rate of the received character was too low to be measured.
+    Autobaud,
+
+    /// An invalid configuration was requested, or an operation was attempted
+    /// that is not valid in the current configuration
+    Configuration,
+}
+
+impl From<i2c::Error> for Error {
+    fn from(error: i2c::Error) -> Self {
+        match error {
+            i2c::Error::EventTimeout => Self::Timeout,
+            i2c::Error::SclTimeout => Self::Timeout,
+            i2c::Error::MasterArbitrationLoss => Self::Bus,
+            i2c::Error::MasterStartStopError => Self::Bus,
+            i2c::Error::UnexpectedState { .. } => Self::Bus,
+            i2c::Error::UnknownSlaveState(_) => Self::Bus,
+            i2c::Error::BusRecoveryFailed => Self::Bus,
+            i2c::Error::MonitorOverflow => Self::Overrun,
+            i2c::Error::AddressOutOfRange => Self::Configuration,
+            i2c::Error::InvalidTransfer(error) => error.into(),
+        }
+    }
+}
+
+impl From<i2c::smbus::Error> for Error {
+    fn from(error: i2c::smbus::Error) -> Self {
+        match error {
+            i2c::smbus::Error::PecMismatch => Self::Bus,
+            i2c::smbus::Error::BlockTooLong => Self::Overrun,
+            i2c::smbus::Error::I2c(error) => error.into(),
+        }
+    }
+}
+
+impl From<i2c::InvalidClockConfig> for Error {
+    fn from(_: i2c::InvalidClockConfig) -> Self {
+        Self::Configuration
+    }
+}
+
+impl<Word> From<usart::Error<Word>> for Error {
+    fn from(error: usart::Error<Word>) -> Self {
+        match error {
+            usart::Error::Framing(_) => Self::Bus,
+            usart::Error::Noise(_) => Self::Bus,
+            usart::Error::Parity(_) => Self::Bus,
+            usart::Error::Overrun(_) => Self::Overrun,
+            usart::Error::Break => Self::Bus,
+            usart::Error::Collision(_) => Self::Bus,
+            usart::Error::TimedOut => Self::Timeout,
+        }
+    }
+}
+
+impl From<usart::AutobaudError> for Error {
+    fn from(_: usart::AutobaudError) -> Self {
+        Self::Autobaud
+    }
+}
+
+impl From<usart::InvalidClockConfig> for Error {
+    fn from(_: usart::InvalidClockConfig) -> Self {
+        Self::Configuration
+    }
+}
+
+impl From<spi::RxOverrunError> for Error {
+    fn from(_: spi::RxOverrunError) -> Self {
+        Self::Overrun
+    }
+}
+
+impl From<spi::TxUnderrunError> for Error {
+    fn from(_: spi::TxUnderrunError) -> Self {
+        Self::Overrun
+    }
+}
+
+impl From<mrt::TickConversionError> for Error {
+    fn from(_: mrt::TickConversionError) -> Self {
+        Self::Configuration
+    }
+}
+
+impl From<pmu::LowPowerError> for Error {
+    fn from(error: pmu::LowPowerError) -> Self {
+        match error {
+            pmu::LowPowerError::ClockNotAvailable => Self::Configuration,
+        }
+    }
+}
+
+impl From<safety::InvalidTimeout> for Error {
+    fn from(_: safety::InvalidTimeout) -> Self {
+        Self::Configuration
+    }
+}
+
+impl From<framing::Error> for Error {
+    fn from(error: framing::Error) -> Self {
+        match error {
+            framing::Error::PacketTooLarge => Self::Configuration,
+            framing::Error::FrameTooLong => Self::Overrun,
+            framing::Error::Malformed => Self::Bus,
+            framing::Error::Crc => Self::Bus,
+            framing::Error::Usart(error) => error.into(),
+        }
+    }
+}
+
+impl From<usart::idle::Error> for Error {
+    fn from(error: usart::idle::Error) -> Self {
+        match error {
+            usart::idle::Error::FrameTooLong => Self::Overrun,
+            usart::idle::Error::Usart(error) => error.into(),
+        }
+    }
+}
+
+impl From<selftest::Failure> for Error {
+    fn from(failure: selftest::Failure) -> Self {
+        match failure {
+            selftest::Failure::Timeout => Self::Timeout,
+            selftest::Failure::Mismatch { .. } => Self::Bus,
+            selftest::Failure::Usart(error) => error.into(),
+            selftest::Failure::I2c(error) => error.into(),
+        }
+    }
+}
+
+impl From<gpio::direction::DynamicPinErr> for Error {
+    fn from(error: gpio::direction::DynamicPinErr) -> Self {
+        match error {
+            gpio::direction::DynamicPinErr::WrongDirection => {
+                Self::Configuration
+            }
+        }
+    }
+}
+
+impl From<bitbang::i2c::Error> for Error {
+    fn from(error: bitbang::i2c::Error) -> Self {
+        match error {
+            bitbang::i2c::Error::AddressNack => Self::Bus,
+            bitbang::i2c::Error::DataNack => Self::Bus,
+            bitbang::i2c::Error::ArbitrationLoss => Self::Bus,
+            bitbang::i2c::Error::SclTimeout => Self::Timeout,
+            bitbang::i2c::Error::AddressOutOfRange => Self::Configuration,
+        }
+    }
+}
+
+impl From<bitbang::onewire::Error> for Error {
+    fn from(error: bitbang::onewire::Error) -> Self {
+        match error {
+            bitbang::onewire::Error::NoPresence => Self::Bus,
+            bitbang::onewire::Error::BusShorted => Self::Bus,
+            bitbang::onewire::Error::CrcMismatch => Self::Bus,
+        }
+    }
+}
+
+impl From<dma::transfer::InvalidTransfer> for Error {
+    fn from(_: dma::transfer::InvalidTransfer) -> Self {
+        Self::Configuration
+    }
+}
+
+impl<S, D> From<dma::transfer::Error<S, D>> for Error
+where
+    S: Into<Error>,
+    D: Into<Error>,
+{
+    fn from(error: dma::transfer::Error<S, D>) -> Self {
+        match error {
+            dma::transfer::Error::Source(error) => error.into(),
+            dma::transfer::Error::Dest(error) => error.into(),
+        }
+    }
+}
+
+impl From<void::Void> for Error {
+    fn from(void: void::Void) -> Self {
+        void::unreachable(void)
+    }
+}
+
+impl From<core::convert::Infallible> for Error {
+    fn from(error: core::convert::Infallible) -> Self {
+        match error {}
+    }
+}
@@ -0,0 +1,651 @@
+//! COBS-framed packets with CRC over USART
+//!
+//! Provides a packet interface on top of the USART byte stream. Each packet is
+//! followed by a CRC-16 (CCITT), encoded using [Consistent Overhead Byte
+//! Stuffing] (COBS), and terminated by a zero byte. Since COBS-encoded data
+//! never contains a zero byte, the receiver can always find the start of the
+//! next packet, even after corrupted or lost data.
+//!
+//! [`Sender`] encodes packets into a buffer and sends them using DMA.
+//! [`Receiver`] decodes packets from the received bytes. It needs to be polled
+//! regularly, for example from the USART interrupt handler, to not lose any
+//! data.
+//!
+//! The encoding functions are available separately ([`encode`], [`Decoder`]),
+//! for use with other transports.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     cortex_m,
+//!     framing::{Receiver, Sender},
+//!     prelude::*,
+//!     usart,
+//!     Peripherals,
+//! };
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut swm    = p.SWM.split();
+//! let dma        = p.DMA.enable(&mut syscon.handle);
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! #[cfg(feature = "82x")]
+//! let clock_config = {
+//!     syscon.uartfrg.set_clkdiv(6);
+//!     syscon.uartfrg.set_frgmult(22);
+//!     syscon.uartfrg.set_frgdiv(0xff);
+//!     usart::Clock::new(&syscon.uartfrg, 0, 16)
+//! };
+//! #[cfg(feature = "845")]
+//! let clock_config = usart::Clock::new_with_baudrate(115200);
+//!
+//! let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+//!     p.pins.pio0_0.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+//!     p.pins.pio0_4.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//!
+//! let serial = p.USART0.enable_async(
+//!     &clock_config,
+//!     &mut syscon.handle,
+//!     u0_rxd,
+//!     u0_txd,
+//!     usart::Settings::default(),
+//! );
+//!
+//! let tx_buffer = cortex_m::singleton!(: [u8; 64] = [0; 64]).unwrap();
+//! let rx_buffer = cortex_m::singleton!(: [u8; 64] = [0; 64]).unwrap();
+//!
+//! let mut sender = Sender::new(tx_buffer, serial.tx, dma.channels.channel1);
+//! let mut receiver = Receiver::new(rx_buffer, serial.rx);
+//!
+//! loop {
+//!     if let Ok(packet) = receiver.poll_packet() {
+//!         let mut reply = [0; 32];
+//!         let len = packet.len().min(reply.len());
+//!         reply[..len].copy_from_slice(&packet[..len]);
+//!
+//!         nb::block!(sender.send_packet(&reply[..len])).unwrap();
+//!     }
+//! }
+//! ```
+//!
+//! [Consistent Overhead Byte Stuffing]: https://en.wikipedia.org/wiki/Consistent_Overhead_Byte_Stuffing
+//! [`Sender`]: struct.Sender.html
+//! [`Receiver`]: struct.Receiver.html
+//! [`encode`]: fn.encode.html
+//! [`Decoder`]: struct.Decoder.html
+
+use embedded_hal::serial::Read;
+
+use crate::{
+    dma::{self, transfer::state::Started},
+    init_state,
+    usart::{self, state::Enabled},
+};
+
+/// The number of bytes the CRC adds to each packet
+pub const CRC_LEN: usize = 2;
+
+/// The maximum number of bytes a DMA transfer can send
+const MAX_TRANSFER: usize = 1024;
+
+/// Returns the maximum encoded length of a packet
+///
+/// This includes the CRC, the COBS overhead, and the delimiter.
+pub const fn max_encoded_len(payload_len: usize) -> usize {
+    let data_len = payload_len + CRC_LEN;
+
+    // One code byte for every 254 data bytes, one more for the first block,
+    // and the delimiter.
+    data_len + data_len / 254 + 2
+}
+
+/// Encode a packet
+///
+/// Appends the CRC to `payload`, encodes the result using COBS, and writes it
+/// to `buffer`, followed by the zero delimiter. Returns the number of bytes
+/// written.
+///
+/// Returns [`Error::PacketTooLarge`], if `buffer` is shorter than
+/// [`max_encoded_len`] for the length of `payload`.
+///
+/// [`Error::PacketTooLarge`]: enum.Error.html#variant.PacketTooLarge
+/// [`max_encoded_len`]: fn.max_encoded_len.html
+pub fn encode(payload: &[u8], buffer: &mut [u8]) -> Result<usize, Error> {
+    if buffer.len() < max_encoded_len(payload.len()) {
+        return Err(Error::PacketTooLarge);
+    }
+
+    let crc = crc16(payload).to_le_bytes();
+
+    let mut code_index = 0;
+    let mut code = 1;
+    let mut i = 1;
+
+    for &byte in payload.iter().chain(crc.iter()) {
+        if byte != 0 {
+            buffer[i] = byte;
+            i += 1;
+            code += 1;
+        }
+
+        if byte == 0 || code == 0xff {
+            buffer[code_index] = code;
+            code_index = i;
+            code = 1;
+            i += 1;
+        }
+    }
+
+    buffer[code_index] = code;
+    buffer[i] = 0;
+
+    Ok(i + 1)
+}
+
+/// Computes the CRC-16 (CCITT) of `data`
+///
+/// Uses the polynomial 0x1021 and the initial value 0xffff.
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xffff;
+
+    for &byte in data {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+/// Incremental packet decoder
+///
+/// Decodes packets that were encoded by [`encode`], one byte at a time.
+///
+/// [`encode`]: fn.encode.html
+pub struct Decoder {
+    buffer: &'static mut [u8],
+    len: usize,
+
+    /// Length of the most recently decoded payload
+    packet_len: usize,
+
+    /// Number of data bytes left in the current COBS block
+    remaining: u8,
+
+    /// The code byte of the current COBS block
+    code: u8,
+
+    /// Set, if the current frame is to be ignored until the next delimiter
+    discard: bool,
+}
+
+impl Decoder {
+    /// Create a new `Decoder`
+    ///
+    /// `buffer` must be large enough for the largest expected payload, plus
+    /// [`CRC_LEN`].
+    ///
+    /// [`CRC_LEN`]: constant.CRC_LEN.html
+    pub fn new(buffer: &'static mut [u8]) -> Self {
+        Self {
+            buffer,
+            len: 0,
+            packet_len: 0,
+            remaining: 0,
+            code: 0,
+            discard: false,
+        }
+    }
+
+    /// Feed one received byte into the decoder
+    ///
+    /// Returns the length of the payload, once a complete packet has been
+    /// decoded. The payload can then be accessed using [`Decoder::packet`],
+    /// until the next byte is fed into the decoder.
+    ///
+    /// If an error is returned, the rest of the current frame is ignored.
+    ///
+    /// [`Decoder::packet`]: #method.packet
+    pub fn feed(&mut self, byte: u8) -> Result<Option<usize>, Error> {
+        self.packet_len = 0;
+
+        if byte == 0 {
+            return self.finish_frame();
+        }
+
+        if self.discard {
+            return Ok(None);
+        }
+
+        if self.remaining == 0 {
+            // This is a code byte. All blocks except those of maximum length
+            // are followed by a zero, unless they're at the end of the frame.
+            if self.code != 0 && self.code != 0xff {
+                self.push(0)?;
+            }
+
+            self.code = byte;
+            self.remaining = byte - 1;
+        } else {
+            self.push(byte)?;
+            self.remaining -= 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the most recently decoded payload
+    ///
+    /// The returned slice is empty, if no packet was decoded by the most
+    /// recent call to [`Decoder::feed`].
+    ///
+    /// [`Decoder::feed`]: #method.feed
+    pub fn packet(&self) -> &[u8] {
+        &self.buffer[..self.packet_len]
+    }
+
+    /// Discard the current frame
+    ///
+    /// All bytes up to the next delimiter are ignored.
+    pub fn discard(&mut self) {
+        self.discard = true;
+        self.len = 0;
+    }
+
+    fn push(&mut self, byte: u8) -> Result<(), Error> {
+        if self.len >= self.buffer.len() {
+            self.discard();
+            return Err(Error::FrameTooLong);
+        }
+
+        self.buffer[self.len] = byte;
+        self.len += 1;
+
+        Ok(())
+    }
+
+    fn finish_frame(&mut self) -> Result<Option<usize>, Error> {
+        let discard = self.discard;
+        let complete = self.remaining == 0;
+        let empty = self.code == 0;
+        let len = self.len;
+
+        self.len = 0;
+        self.remaining = 0;
+        self.code = 0;
+        self.discard = false;
+
+        // Consecutive delimiters are allowed and ignored.
+        if discard || empty {
+            return Ok(None);
+        }
+        if !complete || len < CRC_LEN {
+            return Err(Error::Malformed);
+        }
+
+        let payload_len = len - CRC_LEN;
+        let crc = u16::from_le_bytes([
+            self.buffer[payload_len],
+            self.buffer[payload_len + 1],
+        ]);
+        if crc16(&self.buffer[..payload_len]) != crc {
+            return Err(Error::Crc);
+        }
+
+        self.packet_len = payload_len;
+
+        Ok(Some(payload_len))
+    }
+}
+
+/// Sends packets over USART, using DMA
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct Sender<I, Mode, Throttle>
+where
+    I: usart::Instance,
+{
+    buffer: &'static mut [u8],
+    state: Option<SendState<I, Mode, Throttle>>,
+}
+
+impl<I, Mode, Throttle> Sender<I, Mode, Throttle>
+where
+    I: usart::Instance,
+{
+    /// Create a new `Sender`
+    ///
+    /// `buffer` holds the encoded packet while it is being sent. Use
+    /// [`max_encoded_len`] to determine the required size. Packets are limited
+    /// to what can be sent in a single DMA transfer (1024 bytes, encoded).
+    ///
+    /// `channel` must be the DMA channel that belongs to the USART instance.
+    /// The type system enforces this.
+    ///
+    /// [`max_encoded_len`]: fn.max_encoded_len.html
+    pub fn new(
+        buffer: &'static mut [u8],
+        tx: usart::Tx<I, Enabled<u8, Mode>, Throttle>,
+        channel: dma::Channel<I::TxChannel, init_state::Enabled>,
+    ) -> Self {
+        Self {
+            buffer,
+            state: Some(SendState::Idle(tx, channel)),
+        }
+    }
+
+    /// Send a packet
+    ///
+    /// Returns `WouldBlock`, if the previous packet is still being sent.
+    /// Otherwise encodes the packet and starts sending it, without waiting for
+    /// that to finish.
+    pub fn send_packet(&mut self, payload: &[u8]) -> nb::Result<(), Error> {
+        let (tx, channel) = match self.poll() {
+            Some(idle) => idle,
+            None => return Err(nb::Error::WouldBlock),
+        };
+
+        let len = match encode(payload, self.buffer) {
+            Ok(len) if len <= MAX_TRANSFER => len,
+            Ok(_) | Err(_) => {
+                self.state = Some(SendState::Idle(tx, channel));
+                return Err(nb::Error::Other(Error::PacketTooLarge));
+            }
+        };
+
+        // Sound, as the region is within the buffer, and the buffer is not
+        // written to until the transfer has finished.
+        let source = unsafe { dma::Buffer::new(self.buffer.as_mut_ptr(), len) };
+
+        match dma::Transfer::new(channel, source, tx) {
+            Ok(transfer) => {
+                self.state = Some(SendState::Busy(transfer.start()));
+                Ok(())
+            }
+            Err((_, payload)) => {
+                self.state =
+                    Some(SendState::Idle(payload.dest, payload.channel));
+                Err(nb::Error::Other(Error::PacketTooLarge))
+            }
+        }
+    }
+
+    /// Indicates whether the previous packet has been handed to the USART
+    pub fn is_idle(&mut self) -> bool {
+        match self.poll() {
+            Some((tx, channel)) => {
+                self.state = Some(SendState::Idle(tx, channel));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the transmitter and DMA channel, if no transfer is active
+    ///
+    /// If this returns `None`, the state is left unchanged. Otherwise, the
+    /// caller must restore it.
+    fn poll(
+        &mut self,
+    ) -> Option<(
+        usart::Tx<I, Enabled<u8, Mode>, Throttle>,
+        dma::Channel<I::TxChannel, init_state::Enabled>,
+    )> {
+        match self.state.take()? {
+            SendState::Busy(transfer) if transfer.is_active() => {
+                self.state = Some(SendState::Busy(transfer));
+                None
+            }
+            SendState::Busy(transfer) => {
+                let payload = match transfer.wait() {
+                    Ok(payload) => payload,
+                    Err((dma::transfer::Error::Source(void), _)) => {
+                        void::unreachable(void)
+                    }
+                    Err((dma::transfer::Error::Dest(void), _)) => {
+                        void::unreachable(void)
+                    }
+                };
+
+                Some((payload.dest, payload.channel))
+            }
+            SendState::Idle(tx, channel) => Some((tx, channel)),
+        }
+    }
+}
+
+enum SendState<I, Mode, Throttle>
+where
+    I: usart::Instance,
+{
+    Idle(
+        usart::Tx<I, Enabled<u8, Mode>, Throttle>,
+        dma::Channel<I::TxChannel, init_state::Enabled>,
+    ),
+    Busy(
+        dma::Transfer<
+            Started,
+            I::TxChannel,
+            dma::Buffer,
+            usart::Tx<I, Enabled<u8, Mode>, Throttle>,
+        >,
+    ),
+}
+
+/// Receives packets over USART
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct Receiver<I, Mode> {
+    rx: usart::Rx<I, Enabled<u8, Mode>>,
+    decoder: Decoder,
+}
+
+impl<I, Mode> Receiver<I, Mode>
+where
+    I: usart::Instance,
+{
+    /// Create a new `Receiver`
+    ///
+    /// `buffer` must be large enough for the largest expected payload, plus
+    /// [`CRC_LEN`].
+    ///
+    /// [`CRC_LEN`]: constant.CRC_LEN.html
+    pub fn new(
+        buffer: &'static mut [u8],
+        rx: usart::Rx<I, Enabled<u8, Mode>>,
+    ) -> Self {
+        Self {
+            rx,
+            decoder: Decoder::new(buffer),
+        }
+    }
+
+    /// Poll for a received packet
+    ///
+    /// Reads all bytes that are currently available from the USART. Returns
+    /// the payload, as soon as a complete packet has been received, and
+    /// `WouldBlock`, if no complete packet is available yet.
+    ///
+    /// Can be called from the USART interrupt handler, with the RXRDY
+    /// interrupt enabled.
+    pub fn poll_packet(&mut self) -> nb::Result<&[u8], Error> {
+        loop {
+            let byte = match self.rx.read() {
+                Ok(byte) => byte,
+                Err(nb::Error::WouldBlock) => {
+                    return Err(nb::Error::WouldBlock)
+                }
+                Err(nb::Error::Other(error)) => {
+                    self.decoder.discard();
+                    return Err(nb::Error::Other(Error::Usart(error)));
+                }
+            };
+
+            if self.decoder.feed(byte)?.is_some() {
+                return Ok(self.decoder.packet());
+            }
+        }
+    }
+
+    /// Return the receiver
+    pub fn free(self) -> usart::Rx<I, Enabled<u8, Mode>> {
+        self.rx
+    }
+}
+
+/// A packet framing error
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The encoded packet doesn't fit into the buffer or a DMA transfer
+    PacketTooLarge,
+
+    /// A received frame didn't fit into the buffer
+    FrameTooLong,
+
+    /// A received frame was not valid COBS, or too short to contain a CRC
+    Malformed,
+
+    /// The CRC of a received frame didn't match its payload
+    Crc,
+
+    /// The USART reported an error
+    Usart(usart::Error<u8>),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{boxed::Box, vec, vec::Vec};
+
+    use super::{crc16, encode, max_encoded_len, Decoder, Error, CRC_LEN};
+
+    fn decoder(len: usize) -> Decoder {
+        Decoder::new(Box::leak(vec![0; len].into_boxed_slice()))
+    }
+
+    fn encode_to_vec(payload: &[u8]) -> Vec<u8> {
+        let mut buffer = vec![0; max_encoded_len(payload.len())];
+        let len = encode(payload, &mut buffer).unwrap();
+        buffer.truncate(len);
+        buffer
+    }
+
+    /// Feeds `frame` into `decoder` and returns the decoded packet
+    fn decode(decoder: &mut Decoder, frame: &[u8]) -> Result<Vec<u8>, Error> {
+        for &byte in frame {
+            if decoder.feed(byte)?.is_some() {
+                return Ok(decoder.packet().to_vec());
+            }
+        }
+
+        panic!("frame was not terminated");
+    }
+
+    #[test]
+    fn crc16_should_match_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29b1);
+    }
+
+    #[test]
+    fn packets_should_survive_round_trip() {
+        let mut payloads: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![0],
+            vec![0, 0, 0],
+            vec![0x11, 0x22, 0x00, 0x33],
+            vec![0xff; 3],
+        ];
+        // Lengths around the COBS block size of 254 data bytes
+        for &len in &[252, 253, 254, 255, 508, 600] {
+            payloads.push((1..=len).map(|i| i as u8 | 1).collect());
+            payloads.push((0..len).map(|i| i as u8).collect());
+        }
+
+        let mut decoder = decoder(600 + CRC_LEN);
+        for payload in payloads {
+            let frame = encode_to_vec(&payload);
+
+            assert!(frame.len() <= max_encoded_len(payload.len()));
+            assert_eq!(frame.last(), Some(&0));
+            assert!(!frame[..frame.len() - 1].contains(&0));
+
+            assert_eq!(decode(&mut decoder, &frame), Ok(payload));
+        }
+    }
+
+    #[test]
+    fn encode_should_reject_short_buffer() {
+        let payload = [1, 2, 3];
+        let mut buffer = [0; max_encoded_len(3) - 1];
+
+        assert_eq!(encode(&payload, &mut buffer), Err(Error::PacketTooLarge));
+    }
+
+    #[test]
+    fn decoder_should_ignore_consecutive_delimiters() {
+        let mut decoder = decoder(16);
+
+        for _ in 0..3 {
+            assert_eq!(decoder.feed(0), Ok(None));
+        }
+        assert_eq!(decode(&mut decoder, &encode_to_vec(&[5])), Ok(vec![5]));
+    }
+
+    #[test]
+    fn decoder_should_reject_truncated_block() {
+        let mut decoder = decoder(16);
+
+        // The code byte announces four more bytes, but only two follow.
+        assert_eq!(decode(&mut decoder, &[5, 1, 2, 0]), Err(Error::Malformed));
+    }
+
+    #[test]
+    fn decoder_should_reject_frame_without_crc() {
+        let mut decoder = decoder(16);
+
+        assert_eq!(decode(&mut decoder, &[2, 1, 0]), Err(Error::Malformed));
+    }
+
+    #[test]
+    fn decoder_should_reject_corrupted_frame() {
+        let mut decoder = decoder(16);
+        let mut frame = encode_to_vec(&[1, 2, 3]);
+        frame[2] ^= 0x04;
+
+        assert_eq!(decode(&mut decoder, &frame), Err(Error::Crc));
+    }
+
+    #[test]
+    fn decoder_should_reject_frame_too_long_and_recover() {
+        let mut decoder = decoder(4 + CRC_LEN);
+        let frame = encode_to_vec(&[1, 2, 3, 4, 5]);
+
+        let results: Vec<_> = frame.iter().map(|&b| decoder.feed(b)).collect();
+        let errors: Vec<_> = results.iter().filter(|r| r.is_err()).collect();
+        assert_eq!(errors, [&Err(Error::FrameTooLong)]);
+        assert_eq!(results.last(), Some(&Ok(None)));
+
+        assert_eq!(decode(&mut decoder, &encode_to_vec(&[6])), Ok(vec![6]));
+    }
+}
@@ -43,24 +43,26 @@
 
 use core::marker::PhantomData;
 
+#[cfg(feature = "embedded-hal-1")]
+use core::convert::Infallible;
+
 use embedded_hal::digital::v2::{
     InputPin, OutputPin, StatefulOutputPin, ToggleableOutputPin,
 };
-use embedded_hal_alpha::digital::{
-    InputPin as InputPinAlpha, OutputPin as OutputPinAlpha,
-    StatefulOutputPin as StatefulOutputPinAlpha,
-    ToggleableOutputPin as ToggleableOutputPinAlpha,
-};
 use void::Void;
 
-use crate::{init_state, pac, pins, syscon};
+use crate::{
+    init_state, pac,
+    pins::{self, Trait as _},
+    syscon,
+};
 
 #[cfg(feature = "845")]
-use crate::pac::gpio::{CLR, DIRCLR, DIRSET, NOT, PIN, SET};
+use crate::pac::gpio::{CLR, DIRCLR, DIRSET, MASK, MPIN, NOT, PIN, SET};
 #[cfg(feature = "82x")]
 use crate::pac::gpio::{
-    CLR0 as CLR, DIRCLR0 as DIRCLR, DIRSET0 as DIRSET, NOT0 as NOT,
-    PIN0 as PIN, SET0 as SET,
+    CLR0 as CLR, DIRCLR0 as DIRCLR, DIRSET0 as DIRSET, MASK0 as MASK,
+    MPIN0 as MPIN, NOT0 as NOT, PIN0 as PIN, SET0 as SET,
 };
 
 use self::direction::{Direction, DynamicPinErr};
@@ -96,6 +98,13 @@ pub struct GPIO<State = init_state::Enabled> {
     /// [`enable`]: #method.enable
     /// [`disable`]: #method.disable
     pub tokens: pins::Tokens<State>,
+
+    /// Port-wide access to PIO0
+    pub port0: GpioPort<State>,
+
+    /// Port-wide access to PIO1
+    #[cfg(feature = "845")]
+    pub port1: GpioPort<State>,
 }
 
 impl<State> GPIO<State> {
@@ -105,6 +114,9 @@ impl<State> GPIO<State> {
             _state: PhantomData,
 
             tokens: pins::Tokens::new(),
+            port0: GpioPort::new(0),
+            #[cfg(feature = "845")]
+            port1: GpioPort::new(1),
         }
     }
 
@@ -143,13 +155,16 @@ impl GPIO<init_state::Disabled> {
     ) -> GPIO<init_state::Enabled> {
         syscon.enable_clock(&self.gpio);
 
-        // Only works, if all tokens are available.
+        // Only works, if all tokens and ports are available.
         let tokens = self.tokens.switch_state();
 
         GPIO {
             gpio: self.gpio,
             _state: PhantomData,
             tokens,
+            port0: self.port0.switch_state(),
+            #[cfg(feature = "845")]
+            port1: self.port1.switch_state(),
         }
     }
 }
@@ -172,17 +187,291 @@ impl GPIO<init_state::Enabled> {
     ) -> GPIO<init_state::Disabled> {
         syscon.disable_clock(&self.gpio);
 
-        // Only works, if all tokens are available.
+        // Only works, if all tokens and ports are available.
         let tokens = self.tokens.switch_state();
 
         GPIO {
             gpio: self.gpio,
             _state: PhantomData,
             tokens,
+            port0: self.port0.switch_state(),
+            #[cfg(feature = "845")]
+            port1: self.port1.switch_state(),
         }
     }
 }
 
+/// Port-wide access to the GPIO pins
+///
+/// Allows reading all pins of a port at once, and setting, clearing, or
+/// toggling any number of a port's pins in a single write. This is useful for
+/// parallel buses, which would otherwise require one write per pin.
+///
+/// The masked operations restrict access to a subset of the port's pins, using
+/// the MASK and MPIN registers. They allow writing a value to multiple pins at
+/// once, without affecting the other pins of the port.
+///
+/// You can get access to an instance of this struct through [`GPIO`].
+///
+/// Please note that the pins are not owned by this struct. Writes only affect
+/// the pins selected by the mask, but those might be in use by a [`GpioPin`]
+/// too. The pins need to be configured as GPIO outputs for writes to have any
+/// effect.
+///
+/// [`GPIO`]: struct.GPIO.html
+/// [`GpioPin`]: struct.GpioPin.html
+pub struct GpioPort<State = init_state::Enabled> {
+    port: u8,
+    _state: PhantomData<State>,
+}
+
+impl<State> GpioPort<State> {
+    fn new(port: u8) -> Self {
+        Self {
+            port,
+            _state: PhantomData,
+        }
+    }
+
+    /// Switches the state of the port
+    ///
+    /// Since this consumes `self`, it can only be called if the port is
+    /// available.
+    fn switch_state<NewState>(self) -> GpioPort<NewState> {
+        GpioPort::new(self.port)
+    }
+}
+
+impl GpioPort<init_state::Enabled> {
+    /// Read the level of all of this port's pins
+    ///
+    /// Bit `n` of the returned value is set, if pin `n` is HIGH.
+    pub fn read(&self) -> u32 {
+        // This is sound, as we only read from a register.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        registers.pin[usize::from(self.port)].read().port().bits()
+    }
+
+    /// Set the outputs of the pins in `mask` to HIGH
+    pub fn set_high(&mut self, mask: u32) {
+        // This is sound, as we only do a stateless write to the port that this
+        // `GpioPort` instance represents.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        registers.set[usize::from(self.port)]
+            .write(|w| unsafe { w.setp().bits(mask) });
+    }
+
+    /// Set the outputs of the pins in `mask` to LOW
+    pub fn set_low(&mut self, mask: u32) {
+        // This is sound, as we only do a stateless write to the port that this
+        // `GpioPort` instance represents.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        registers.clr[usize::from(self.port)]
+            .write(|w| unsafe { w.clrp().bits(mask) });
+    }
+
+    /// Toggle the outputs of the pins in `mask`
+    pub fn toggle(&mut self, mask: u32) {
+        // This is sound, as we only do a stateless write to the port that this
+        // `GpioPort` instance represents.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        registers.not[usize::from(self.port)]
+            .write(|w| unsafe { w.notp().bits(mask) });
+    }
+
+    /// Select the pins that are accessed by the masked operations
+    ///
+    /// Only the pins in `mask` are affected by [`write_masked`] and read by
+    /// [`read_masked`]. Initially, all pins are selected.
+    ///
+    /// [`write_masked`]: #method.write_masked
+    /// [`read_masked`]: #method.read_masked
+    pub fn set_access_mask(&mut self, mask: u32) {
+        // This is sound, as only this `GpioPort` instance accesses the MASK
+        // register of this port.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        // MASK disables access to the pins whose bits are set.
+        registers.mask[usize::from(self.port)]
+            .write(|w| unsafe { w.maskp().bits(!mask) });
+    }
+
+    /// Read the level of the pins selected by the access mask
+    ///
+    /// Bit `n` of the returned value is set, if pin `n` is selected by the
+    /// access mask and HIGH. See [`set_access_mask`].
+    ///
+    /// [`set_access_mask`]: #method.set_access_mask
+    pub fn read_masked(&self) -> u32 {
+        // This is sound, as we only read from a register.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        registers.mpin[usize::from(self.port)]
+            .read()
+            .mportp()
+            .bits()
+    }
+
+    /// Set the outputs of the pins selected by the access mask
+    ///
+    /// Pin `n` is set HIGH, if bit `n` of `value` is set, and LOW otherwise.
+    /// Pins that are not selected by the access mask are not affected. This
+    /// takes a single write, without a read-modify-write. See
+    /// [`set_access_mask`].
+    ///
+    /// [`set_access_mask`]: #method.set_access_mask
+    pub fn write_masked(&mut self, value: u32) {
+        // This is sound, as MPIN only affects the pins selected by the access
+        // mask, which is controlled by this `GpioPort` instance.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        registers.mpin[usize::from(self.port)]
+            .write(|w| unsafe { w.mportp().bits(value) });
+    }
+}
+
+/// A group of GPIO pins that is read or written as one value
+///
+/// Bit `n` of the value corresponds to the `n`-th pin of the bus. The pins can
+/// be located anywhere on both ports. Writing to the bus requires at most two
+/// writes per port, reading at most one read per port.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     gpio::{self, PinBus},
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+///
+/// #[cfg(feature = "82x")]
+/// let gpio = p.GPIO;
+/// #[cfg(feature = "845")]
+/// let gpio = p.GPIO.enable(&mut syscon.handle);
+///
+/// let mut bus = PinBus::new([
+///     p.pins.pio0_12
+///         .into_output_pin(gpio.tokens.pio0_12, gpio::Level::Low)
+///         .into_generic(),
+///     p.pins.pio0_15
+///         .into_output_pin(gpio.tokens.pio0_15, gpio::Level::Low)
+///         .into_generic(),
+/// ]);
+///
+/// // Sets PIO0_12 HIGH and PIO0_15 LOW
+/// bus.write(0b01);
+/// ```
+pub struct PinBus<D, const N: usize> {
+    pins: [GpioPin<pins::GenericPin, D>; N],
+}
+
+impl<D, const N: usize> PinBus<D, N>
+where
+    D: Direction,
+{
+    // Each pin is represented by one bit of a `u32`. Evaluated in `new`, so a
+    // bus with more than 32 pins fails to compile.
+    const FITS_INTO_U32: () =
+        assert!(N <= 32, "`PinBus` has more than 32 pins");
+
+    /// Create a new `PinBus`
+    ///
+    /// `N` must not be larger than 32. This is checked at compile time.
+    pub fn new(pins: [GpioPin<pins::GenericPin, D>; N]) -> Self {
+        let () = Self::FITS_INTO_U32;
+        Self { pins }
+    }
+
+    /// Return the pins
+    pub fn free(self) -> [GpioPin<pins::GenericPin, D>; N] {
+        self.pins
+    }
+
+    /// Returns the masks of the pins that are selected by `value`, per port
+    ///
+    /// Pins whose bit is set in `value` are added to the first mask, the other
+    /// pins to the second one.
+    fn masks(&self, value: u32) -> [(u32, u32); NUM_PORTS] {
+        let mut masks = [(0, 0); NUM_PORTS];
+
+        for (i, pin) in self.pins.iter().enumerate() {
+            let (set, clear) = &mut masks[usize::from(pin.inner().port())];
+
+            if value & (0x1 << i) != 0 {
+                *set |= pin.inner().mask();
+            } else {
+                *clear |= pin.inner().mask();
+            }
+        }
+
+        masks
+    }
+}
+
+impl<const N: usize> PinBus<direction::Output, N> {
+    /// Set the outputs of all pins according to `value`
+    pub fn write(&mut self, value: u32) {
+        // This is sound, as we only do stateless writes to bits of pins that
+        // are owned by this `PinBus` instance.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        for (port, (set, clear)) in self.masks(value).iter().enumerate() {
+            if *set != 0 {
+                registers.set[port].write(|w| unsafe { w.setp().bits(*set) });
+            }
+            if *clear != 0 {
+                registers.clr[port].write(|w| unsafe { w.clrp().bits(*clear) });
+            }
+        }
+    }
+}
+
+impl<const N: usize> PinBus<direction::Input, N> {
+    /// Read the levels of all pins
+    ///
+    /// Bit `n` of the returned value is set, if the `n`-th pin is HIGH.
+    pub fn read(&self) -> u32 {
+        // This is sound, as we only read from registers.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        // Read each port only once, even if multiple pins are located there.
+        let mut levels = [0; NUM_PORTS];
+        for (port, (_, used)) in self.masks(0).iter().enumerate() {
+            if *used != 0 {
+                levels[port] = registers.pin[port].read().port().bits();
+            }
+        }
+
+        let mut value = 0;
+        for (i, pin) in self.pins.iter().enumerate() {
+            let level = levels[usize::from(pin.inner().port())];
+
+            if level & pin.inner().mask() != 0 {
+                value |= 0x1 << i;
+            }
+        }
+
+        value
+    }
+}
+
 /// A pin used for general purpose I/O (GPIO).
 ///
 /// This struct is a wrapper around the representation of a specific pin `P`– it enables said pin
@@ -198,6 +487,12 @@ impl GPIO<init_state::Enabled> {
 ///   - [`embedded_hal::digital::v2::OutputPin`] for setting the pin state
 ///   - [`embedded_hal::digital::v2::StatefulOutputPin`] for reading the pin output state
 ///   - [`embedded_hal::digital::v2::ToggleableOutputPin`] for toggling the pin state
+/// - While in open-drain output mode
+///   - [`embedded_hal::digital::v2::InputPin`] for reading the line state
+///   - [`embedded_hal::digital::v2::OutputPin`] for driving the pin low or
+///     releasing it
+///   - [`embedded_hal::digital::v2::StatefulOutputPin`] for reading the pin output state
+///   - [`embedded_hal::digital::v2::ToggleableOutputPin`] for toggling the pin state
 ///
 /// [`Pin::into_input_pin`]: ../pins/struct.Pin.html#method.into_input_pin
 /// [`Pin::into_output_pin`]: ../pins/struct.Pin.html#method.into_output_pin
@@ -237,6 +532,73 @@ where
         &self.inner
     }
 
+    /// Configure the pin's on-chip pull-up/pull-down resistor
+    ///
+    /// See [`Pin::set_resistor`] for details.
+    ///
+    /// [`Pin::set_resistor`]: ../pins/struct.Pin.html#method.set_resistor
+    pub fn set_resistor(
+        &mut self,
+        resistor: pins::Resistor,
+        iocon: &mut pac::IOCON,
+    ) {
+        pins::iocon::set_resistor(self.inner(), iocon, resistor);
+    }
+
+    /// Enable or disable inversion of the pin's input
+    ///
+    /// See [`Pin::set_inverted`] for details.
+    ///
+    /// [`Pin::set_inverted`]: ../pins/struct.Pin.html#method.set_inverted
+    pub fn set_inverted(&mut self, inverted: bool, iocon: &mut pac::IOCON) {
+        pins::iocon::set_inverted(self.inner(), iocon, inverted);
+    }
+
+    /// Enable or disable the pin's input hysteresis
+    ///
+    /// See [`Pin::set_hysteresis`] for details.
+    ///
+    /// [`Pin::set_hysteresis`]: ../pins/struct.Pin.html#method.set_hysteresis
+    pub fn set_hysteresis(&mut self, hysteresis: bool, iocon: &mut pac::IOCON) {
+        pins::iocon::set_hysteresis(self.inner(), iocon, hysteresis);
+    }
+
+    /// Configure the pin's digital input filter
+    ///
+    /// See [`Pin::set_filter`] for details.
+    ///
+    /// [`Pin::set_filter`]: ../pins/struct.Pin.html#method.set_filter
+    pub fn set_filter(&mut self, filter: pins::Filter, iocon: &mut pac::IOCON) {
+        pins::iocon::set_filter(self.inner(), iocon, filter);
+    }
+
+    /// Erase the pin's identity from its type
+    ///
+    /// Returns a pin with the same direction that holds a [`GenericPin`], so
+    /// pins can be stored in collections, for example to create a [`PinBus`].
+    ///
+    /// [`GenericPin`]: ../pins/struct.GenericPin.html
+    /// [`PinBus`]: struct.PinBus.html
+    pub fn into_generic(self) -> GpioPin<pins::GenericPin, D> {
+        GpioPin {
+            inner: pins::GenericPin::new(self.inner.port(), self.inner.id()),
+            _direction: self._direction,
+        }
+    }
+
+    /// Replace the pin, keeping the direction
+    ///
+    /// Used to convert [`GenericPin`]s back into typed pins. The caller must
+    /// make sure that both identify the same pin.
+    ///
+    /// [`GenericPin`]: ../pins/struct.GenericPin.html
+    pub(crate) fn with_inner<Q>(self, inner: Q) -> GpioPin<Q, D> {
+        GpioPin {
+            inner,
+            _direction: self._direction,
+        }
+    }
+
     /// Indicates wether the voltage at the pin is currently HIGH
     /// This is not accessible to the user to avoid confusion because `is_high()`
     /// semantics differ depending on pin direction. It is only used to implement
@@ -513,8 +875,106 @@ where
         let gpio = unsafe { &*pac::GPIO::ptr() };
         let registers = Registers::new(gpio);
 
-        registers.not[usize::from(self.inner().port())]
-            .write(|w| unsafe { w.notp().bits(self.inner().mask()) });
+        toggle(&registers, self.inner());
+    }
+}
+
+impl<P> GpioPin<P, direction::OpenDrain>
+where
+    P: pins::Trait,
+{
+    /// Release the pin output, letting the line float HIGH
+    ///
+    /// This method is only available, if the pin has been set to open-drain
+    /// output mode, using [`Pin::into_open_drain_output_pin`].
+    ///
+    /// [`Pin::into_open_drain_output_pin`]: ../pins/struct.Pin.html#method.into_open_drain_output_pin
+    pub fn set_high(&mut self) {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        set_high(&registers, self.inner());
+    }
+
+    /// Drive the pin output LOW
+    ///
+    /// This method is only available, if the pin has been set to open-drain
+    /// output mode, using [`Pin::into_open_drain_output_pin`].
+    ///
+    /// [`Pin::into_open_drain_output_pin`]: ../pins/struct.Pin.html#method.into_open_drain_output_pin
+    pub fn set_low(&mut self) {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        set_low(&registers, self.inner());
+    }
+
+    /// Indicates whether the pin output is currently released
+    ///
+    /// This reflects the level the pin has been set to, not the level of the
+    /// line. Use [`is_high`] for the latter.
+    ///
+    /// [`is_high`]: #method.is_high
+    pub fn is_set_high(&self) -> bool {
+        // This is sound, as we only read a bit from a register.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        // Reading SET returns the output bits, while PIN would return the
+        // level of the line.
+        registers.set[usize::from(self.inner().port())]
+            .read()
+            .setp()
+            .bits()
+            & self.inner().mask()
+            == self.inner().mask()
+    }
+
+    /// Indicates whether the pin output is currently driven LOW
+    ///
+    /// This reflects the level the pin has been set to, not the level of the
+    /// line. Use [`is_low`] for the latter.
+    ///
+    /// [`is_low`]: #method.is_low
+    pub fn is_set_low(&self) -> bool {
+        !self.is_set_high()
+    }
+
+    /// Toggle the pin output
+    ///
+    /// Drives the pin LOW, if it was released, and releases it otherwise.
+    pub fn toggle(&mut self) {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        toggle(&registers, self.inner());
+    }
+
+    /// Indicates whether the line is currently HIGH
+    ///
+    /// The line might be held LOW by another device, even if this pin's output
+    /// has been released.
+    pub fn is_high(&self) -> bool {
+        self.is_high_inner()
+    }
+
+    /// Indicates whether the line is currently LOW
+    ///
+    /// The line might be held LOW by another device, even if this pin's output
+    /// has been released.
+    pub fn is_low(&self) -> bool {
+        !self.is_high()
+    }
+
+    /// Returns the current level of the line
+    pub fn get_level(&self) -> Level {
+        Level::from_pin(&self)
     }
 }
 
@@ -602,6 +1062,17 @@ where
         set_low(&registers, self.inner());
     }
 
+    /// Toggle the pin level.
+    /// Note that this will be executed regardless of the current pin direction.
+    pub fn toggle(&mut self) {
+        // This is sound, as we only do a stateless write to a bit that no other
+        // `GpioPin` instance writes to.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        toggle(&registers, self.inner());
+    }
+
     /// Returns the current voltage level at this pin.
     /// This can be used when the pin is in any direction:
     ///
@@ -660,8 +1131,10 @@ where
     fn is_set_high(&self) -> Result<bool, Self::Error> {
         match self._direction.current_direction {
             pins::DynamicPinDirection::Output => {
-                // Re-use level reading function
-                self.is_set_high()
+                // Re-use level reading function. There's no inherent
+                // `is_set_high` for dynamic pins, so calling that would
+                // recurse into this method.
+                Ok(self.is_high_inner())
             }
             pins::DynamicPinDirection::Input => {
                 Err(Self::Error::WrongDirection)
@@ -673,7 +1146,7 @@ where
         match self._direction.current_direction {
             pins::DynamicPinDirection::Output => {
                 // Re-use level reading function
-                self.is_set_low()
+                Ok(!self.is_high_inner())
             }
             pins::DynamicPinDirection::Input => {
                 Err(Self::Error::WrongDirection)
@@ -730,58 +1203,85 @@ where
     }
 }
 
-impl<P> InputPinAlpha for GpioPin<P, direction::Input>
+impl<P> OutputPin for GpioPin<P, direction::Output>
 where
     P: pins::Trait,
 {
     type Error = Void;
 
-    fn try_is_high(&self) -> Result<bool, Self::Error> {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
         // Call the inherent method defined above.
-        Ok(self.is_high())
+        Ok(self.set_high())
     }
 
-    fn try_is_low(&self) -> Result<bool, Self::Error> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
         // Call the inherent method defined above.
-        Ok(self.is_low())
+        Ok(self.set_low())
     }
 }
 
-impl<P> OutputPin for GpioPin<P, direction::Output>
+impl<P> StatefulOutputPin for GpioPin<P, direction::Output>
+where
+    P: pins::Trait,
+{
+    fn is_set_high(&self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(self.is_set_high())
+    }
+
+    fn is_set_low(&self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(self.is_set_low())
+    }
+}
+
+impl<P> ToggleableOutputPin for GpioPin<P, direction::Output>
 where
     P: pins::Trait,
 {
     type Error = Void;
 
-    fn set_high(&mut self) -> Result<(), Self::Error> {
+    fn toggle(&mut self) -> Result<(), Self::Error> {
         // Call the inherent method defined above.
-        Ok(self.set_high())
+        Ok(self.toggle())
     }
+}
 
-    fn set_low(&mut self) -> Result<(), Self::Error> {
+impl<P> InputPin for GpioPin<P, direction::OpenDrain>
+where
+    P: pins::Trait,
+{
+    type Error = Void;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
         // Call the inherent method defined above.
-        Ok(self.set_low())
+        Ok(self.is_high())
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(self.is_low())
     }
 }
 
-impl<P> OutputPinAlpha for GpioPin<P, direction::Output>
+impl<P> OutputPin for GpioPin<P, direction::OpenDrain>
 where
     P: pins::Trait,
 {
     type Error = Void;
 
-    fn try_set_high(&mut self) -> Result<(), Self::Error> {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
         // Call the inherent method defined above.
         Ok(self.set_high())
     }
 
-    fn try_set_low(&mut self) -> Result<(), Self::Error> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
         // Call the inherent method defined above.
         Ok(self.set_low())
     }
 }
 
-impl<P> StatefulOutputPin for GpioPin<P, direction::Output>
+impl<P> StatefulOutputPin for GpioPin<P, direction::OpenDrain>
 where
     P: pins::Trait,
 {
@@ -796,47 +1296,233 @@ where
     }
 }
 
-impl<P> StatefulOutputPinAlpha for GpioPin<P, direction::Output>
+impl<P> ToggleableOutputPin for GpioPin<P, direction::Dynamic>
 where
     P: pins::Trait,
 {
-    fn try_is_set_high(&self) -> Result<bool, Self::Error> {
+    type Error = DynamicPinErr;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        match self._direction.current_direction {
+            pins::DynamicPinDirection::Output => {
+                // Call the inherent method defined above.
+                Ok(self.toggle())
+            }
+            pins::DynamicPinDirection::Input => {
+                Err(Self::Error::WrongDirection)
+            }
+        }
+    }
+}
+
+impl<P> ToggleableOutputPin for GpioPin<P, direction::OpenDrain>
+where
+    P: pins::Trait,
+{
+    type Error = Void;
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
         // Call the inherent method defined above.
-        Ok(self.is_set_high())
+        Ok(self.toggle())
     }
+}
 
-    fn try_is_set_low(&self) -> Result<bool, Self::Error> {
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::ErrorType for GpioPin<P, direction::Dynamic>
+where
+    P: pins::Trait,
+{
+    type Error = DynamicPinErr;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::OutputPin for GpioPin<P, direction::Dynamic>
+where
+    P: pins::Trait,
+{
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        OutputPin::set_high(self)
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        OutputPin::set_low(self)
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::StatefulOutputPin
+    for GpioPin<P, direction::Dynamic>
+where
+    P: pins::Trait,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        StatefulOutputPin::is_set_high(self)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        StatefulOutputPin::is_set_low(self)
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        ToggleableOutputPin::toggle(self)
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::InputPin for GpioPin<P, direction::Dynamic>
+where
+    P: pins::Trait,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        InputPin::is_high(self)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        InputPin::is_low(self)
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::ErrorType for GpioPin<P, direction::Input>
+where
+    P: pins::Trait,
+{
+    type Error = Infallible;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::InputPin for GpioPin<P, direction::Input>
+where
+    P: pins::Trait,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
         // Call the inherent method defined above.
-        Ok(self.is_set_low())
+        Ok(Self::is_high(self))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::is_low(self))
     }
 }
 
-impl<P> ToggleableOutputPin for GpioPin<P, direction::Output>
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::ErrorType for GpioPin<P, direction::Output>
 where
     P: pins::Trait,
 {
-    type Error = Void;
+    type Error = Infallible;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::OutputPin for GpioPin<P, direction::Output>
+where
+    P: pins::Trait,
+{
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::set_high(self))
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::set_low(self))
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::StatefulOutputPin
+    for GpioPin<P, direction::Output>
+where
+    P: pins::Trait,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::is_set_high(self))
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::is_set_low(self))
+    }
 
     fn toggle(&mut self) -> Result<(), Self::Error> {
         // Call the inherent method defined above.
-        Ok(self.toggle())
+        Ok(Self::toggle(self))
     }
 }
 
-impl<P> ToggleableOutputPinAlpha for GpioPin<P, direction::Output>
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::ErrorType for GpioPin<P, direction::OpenDrain>
 where
     P: pins::Trait,
 {
-    type Error = Void;
+    type Error = Infallible;
+}
 
-    fn try_toggle(&mut self) -> Result<(), Self::Error> {
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::InputPin for GpioPin<P, direction::OpenDrain>
+where
+    P: pins::Trait,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
         // Call the inherent method defined above.
-        Ok(self.toggle())
+        Ok(Self::is_high(self))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::is_low(self))
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::OutputPin for GpioPin<P, direction::OpenDrain>
+where
+    P: pins::Trait,
+{
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::set_high(self))
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::set_low(self))
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<P> embedded_hal_1::digital::StatefulOutputPin
+    for GpioPin<P, direction::OpenDrain>
+where
+    P: pins::Trait,
+{
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::is_set_high(self))
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::is_set_low(self))
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        // Call the inherent method defined above.
+        Ok(Self::toggle(self))
     }
 }
 
 /// The voltage level of a pin
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Level {
     /// High voltage
     High,
@@ -864,6 +1550,11 @@ fn set_low(registers: &Registers, inner: &impl pins::Trait) {
         .write(|w| unsafe { w.clrp().bits(inner.mask()) });
 }
 
+fn toggle(registers: &Registers, inner: &impl pins::Trait) {
+    registers.not[usize::from(inner.port())]
+        .write(|w| unsafe { w.notp().bits(inner.mask()) });
+}
+
 fn is_high(registers: &Registers, inner: &impl pins::Trait) -> bool {
     registers.pin[usize::from(inner.port())]
         .read()
@@ -889,6 +1580,14 @@ fn set_direction_input(registers: &Registers, inner: &impl pins::Trait) {
         .write(|w| unsafe { w.dirclrp().bits(inner.mask()) });
 }
 
+/// The number of GPIO ports
+#[cfg(feature = "82x")]
+const NUM_PORTS: usize = 1;
+
+/// The number of GPIO ports
+#[cfg(feature = "845")]
+const NUM_PORTS: usize = 2;
+
 /// This is an internal type that should be of no concern to users of this crate
 pub struct Registers<'gpio> {
     dirset: &'gpio [DIRSET],
@@ -897,6 +1596,8 @@ pub struct Registers<'gpio> {
     set: &'gpio [SET],
     clr: &'gpio [CLR],
     not: &'gpio [NOT],
+    mask: &'gpio [MASK],
+    mpin: &'gpio [MPIN],
 }
 
 impl<'gpio> Registers<'gpio> {
@@ -906,6 +1607,8 @@ impl<'gpio> Registers<'gpio> {
     /// caller, accessing all registers is still completely race-free, as long
     /// as the following rules are upheld:
     /// - Never write to `pin`, only use it for reading.
+    /// - Only access `mask` and `mpin` of a port through the `GpioPort`
+    ///   instance that represents it.
     /// - For all other registers, only set bits that no other callers are
     ///   setting.
     fn new(gpio: &'gpio pac::gpio::RegisterBlock) -> Self {
@@ -920,6 +1623,8 @@ impl<'gpio> Registers<'gpio> {
                 set: slice::from_ref(&gpio.set0),
                 clr: slice::from_ref(&gpio.clr0),
                 not: slice::from_ref(&gpio.not0),
+                mask: slice::from_ref(&gpio.mask0),
+                mpin: slice::from_ref(&gpio.mpin0),
             }
         }
 
@@ -931,6 +1636,8 @@ impl<'gpio> Registers<'gpio> {
             set: &gpio.set,
             clr: &gpio.clr,
             not: &gpio.not,
+            mask: &gpio.mask,
+            mpin: &gpio.mpin,
         }
     }
 }
@@ -1020,6 +1727,30 @@ pub mod direction {
         }
     }
 
+    /// Marks a GPIO pin as being configured for open-drain output
+    ///
+    /// The pin drives its output low, but only releases it, instead of driving
+    /// it high. The actual level of the line can be read back.
+    ///
+    /// This type is used as a type parameter of [`GpioPin`]. Please refer to
+    /// the documentation there to see how this type is used.
+    ///
+    /// [`GpioPin`]: ../struct.GpioPin.html
+    pub struct OpenDrain(());
+
+    impl Direction for OpenDrain {
+        type SwitchArg = Level;
+
+        fn switch<P: pins::Trait>(
+            registers: &Registers,
+            initial: Level,
+            inner: &P,
+        ) -> Self {
+            Output::switch(registers, initial, inner);
+            Self(())
+        }
+    }
+
     /// Marks a GPIO pin as being run-time configurable for in/output
     /// Initial direction is Output
     ///
@@ -1038,6 +1769,13 @@ pub mod direction {
         WrongDirection,
     }
 
+    #[cfg(feature = "embedded-hal-1")]
+    impl embedded_hal_1::digital::Error for DynamicPinErr {
+        fn kind(&self) -> embedded_hal_1::digital::ErrorKind {
+            embedded_hal_1::digital::ErrorKind::Other
+        }
+    }
+
     impl Direction for Dynamic {
         type SwitchArg = (Level, pins::DynamicPinDirection);
 
@@ -0,0 +1,149 @@
+//! Closure-based interrupt handlers
+//!
+//! This module is only available, if the `interrupt-handlers` feature is
+//! enabled. It provides the `#[interrupt]` functions for the peripherals listed
+//! below, and allows you to register a closure that is called from them. This
+//! makes it possible to write simple interrupt-driven applications without
+//! defining any interrupt handlers yourself.
+//!
+//! Since the interrupt functions are defined by LPC8xx HAL, you can't define
+//! your own interrupt handlers for the same interrupts, while this feature is
+//! enabled. If you need full control (or want to use a framework like RTIC),
+//! leave the feature disabled.
+//!
+//! Handlers are supported for the following interrupts:
+//! - `USART0`, `USART1`, `USART2`
+//! - `I2C0`, `I2C1`, `I2C2`, `I2C3`
+//! - `WKT`
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{cortex_m, handlers, pac};
+//!
+//! let handler = cortex_m::singleton!(: fn() = || {
+//!     // handle USART0 interrupt
+//! })
+//! .unwrap();
+//!
+//! handlers::register::<pac::USART0>(handler);
+//! ```
+
+use core::cell::RefCell;
+
+use cortex_m::{
+    interrupt::{self, Mutex},
+    peripheral::NVIC,
+};
+
+use crate::pac::{self, interrupt, Interrupt};
+
+/// A registered interrupt handler
+pub type Handler = &'static mut (dyn FnMut() + Send);
+
+/// Storage for a registered interrupt handler
+///
+/// This type is an internal implementation detail.
+pub struct Slot(Mutex<RefCell<Option<Handler>>>);
+
+impl Slot {
+    const fn new() -> Self {
+        Self(Mutex::new(RefCell::new(None)))
+    }
+
+    fn replace(&self, handler: Option<Handler>) -> Option<Handler> {
+        interrupt::free(|cs| self.0.borrow(cs).replace(handler))
+    }
+
+    fn call(&self) {
+        interrupt::free(|cs| {
+            if let Some(handler) = self.0.borrow(cs).borrow_mut().as_mut() {
+                handler();
+            }
+        })
+    }
+}
+
+/// Implemented for peripherals whose interrupt can be handled by a registered
+/// handler
+///
+/// This trait is an internal implementation detail and should neither be
+/// implemented nor used outside of LPC8xx HAL. Any changes to this trait won't
+/// be considered breaking changes.
+pub trait Source: private::Sealed {
+    /// The interrupt that the handler is called from
+    const INTERRUPT: Interrupt;
+
+    /// The storage for the handler
+    fn slot() -> &'static Slot;
+}
+
+/// Register a handler for the interrupt of peripheral `P`
+///
+/// The handler will be called from the interrupt function, from within a
+/// critical section. Registering a handler also unmasks the interrupt in the
+/// NVIC. The interrupt still needs to be enabled in the peripheral itself,
+/// using the respective peripheral API.
+///
+/// Returns the previously registered handler, if any.
+pub fn register<P>(handler: Handler) -> Option<Handler>
+where
+    P: Source,
+{
+    let previous = P::slot().replace(Some(handler));
+
+    // Sound, as the handler is fully set up, before the interrupt can fire.
+    unsafe { NVIC::unmask(P::INTERRUPT) };
+
+    previous
+}
+
+/// Remove the handler for the interrupt of peripheral `P`
+///
+/// Masks the interrupt in the NVIC and returns the handler that was
+/// registered, if any.
+pub fn unregister<P>() -> Option<Handler>
+where
+    P: Source,
+{
+    NVIC::mask(P::INTERRUPT);
+    P::slot().replace(None)
+}
+
+macro_rules! handlers {
+    ($($peripheral:ident, $interrupt:ident, $slot:ident;)*) => {
+        $(
+            static $slot: Slot = Slot::new();
+
+            impl private::Sealed for pac::$peripheral {}
+
+            impl Source for pac::$peripheral {
+                const INTERRUPT: Interrupt = Interrupt::$interrupt;
+
+                fn slot() -> &'static Slot {
+                    &$slot
+                }
+            }
+
+            #[interrupt]
+            fn $interrupt() {
+                $slot.call();
+            }
+        )*
+    };
+}
+
+handlers!(
+    USART0, USART0, USART0_HANDLER;
+    USART1, USART1, USART1_HANDLER;
+    USART2, USART2, USART2_HANDLER;
+    I2C0  , I2C0  , I2C0_HANDLER  ;
+    I2C1  , I2C1  , I2C1_HANDLER  ;
+    I2C2  , I2C2  , I2C2_HANDLER  ;
+    I2C3  , I2C3  , I2C3_HANDLER  ;
+    WKT   , WKT   , WKT_HANDLER   ;
+);
+
+mod private {
+    pub trait Sealed {}
+}
@@ -1,6 +1,9 @@
 use core::marker::PhantomData;
 
-use crate::syscon::{self, clock_source::PeripheralClockSelector};
+use crate::{
+    clock::Frequency,
+    syscon::{self, clock_source::PeripheralClockSelector},
+};
 
 /// Contains the clock configuration for an I2C instance
 pub struct Clock<Clock> {
@@ -17,18 +20,196 @@ where
     /// Create the clock config for the I2C peripheral
     ///
     /// `mstclhigh` and `mstcllow` have to be between 2-9.
-    pub fn new(_: &C, divval: u16, mstsclhigh: u8, mstscllow: u8) -> Self {
-        assert!(mstsclhigh > 1 && mstsclhigh < 10);
-        assert!(mstscllow > 1 && mstscllow < 10);
-        Self {
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `mstsclhigh` or `mstscllow` are out of range. Use
+    /// [`Clock::try_new`], if you need to handle this case without panicking.
+    pub fn new(clock: &C, divval: u16, mstsclhigh: u8, mstscllow: u8) -> Self {
+        match Self::try_new(clock, divval, mstsclhigh, mstscllow) {
+            Ok(clock) => clock,
+            Err(_) => panic!("`mstsclhigh` and `mstscllow` must be 2-9"),
+        }
+    }
+
+    /// Create the clock config for the I2C peripheral, without panicking
+    ///
+    /// Works like [`Clock::new`], but returns an error, if `mstsclhigh` or
+    /// `mstscllow` are not between 2-9.
+    pub fn try_new(
+        _: &C,
+        divval: u16,
+        mstsclhigh: u8,
+        mstscllow: u8,
+    ) -> Result<Self, InvalidClockConfig> {
+        if !(2..=9).contains(&mstsclhigh) || !(2..=9).contains(&mstscllow) {
+            return Err(InvalidClockConfig);
+        }
+
+        Ok(Self {
             divval,
             mstsclhigh: mstsclhigh - 2,
             mstscllow: mstscllow - 2,
             _clock: PhantomData,
+        })
+    }
+
+    /// Create the clock config for the I2C peripheral from a [`Divider`]
+    ///
+    /// Use this in combination with [`Divider::try_from_rate`], to compute the
+    /// configuration values at compile time.
+    pub fn from_divider(_: &C, divider: Divider) -> Self {
+        Self {
+            divval: divider.divval,
+            mstsclhigh: divider.mstsclhigh,
+            mstscllow: divider.mstscllow,
+            _clock: PhantomData,
+        }
+    }
+}
+
+impl<C> Clock<C>
+where
+    C: ClockSource + Frequency,
+{
+    /// Create the clock config for the I2C peripheral from a bit rate
+    ///
+    /// `rate_hz` is the desired SCL frequency. Computes the clock divider and
+    /// the SCL high and low times at runtime from the frequency of `clock`, as
+    /// described in [`Divider::try_from_rate`].
+    ///
+    /// Returns [`InvalidClockConfig`], if the resulting bit rate can't be
+    /// within `tolerance_percent` of `rate_hz`.
+    pub fn from_rate(
+        clock: &C,
+        rate_hz: u32,
+        tolerance_percent: u32,
+    ) -> Result<Self, InvalidClockConfig> {
+        match Divider::try_from_rate(clock.hz(), rate_hz, tolerance_percent) {
+            Some(divider) => Ok(Self::from_divider(clock, divider)),
+            None => Err(InvalidClockConfig),
+        }
+    }
+}
+
+impl Clock<target::Iosc> {
+    /// Create a new I2C clock configuration for 400 kHz
+    ///
+    /// Assumes the internal oscillator runs at 12 MHz.
+    pub fn new_400khz() -> Self {
+        Self {
+            divval: 5,
+            mstsclhigh: 0,
+            mstscllow: 1,
+            _clock: PhantomData,
+        }
+    }
+
+    /// Create a new I2C clock configuration for 1 MHz (Fast-mode Plus)
+    ///
+    /// Assumes the internal oscillator runs at 12 MHz. Fast-mode Plus is only
+    /// supported by I2C0. See [`I2C::enable_master_mode_fast_plus`].
+    ///
+    /// [`I2C::enable_master_mode_fast_plus`]: struct.I2C.html#method.enable_master_mode_fast_plus
+    pub fn new_1mhz() -> Self {
+        // SCL high for 5 and low for 7 cycles of the undivided clock.
+        Self {
+            divval: 0,
+            mstsclhigh: 3,
+            mstscllow: 5,
+            _clock: PhantomData,
         }
     }
 }
 
+/// Divider values for an I2C bit rate
+///
+/// Can be computed at compile time from a bit rate and the frequency of the
+/// I2C clock source, using [`Divider::try_from_rate`]. Pass it to
+/// [`Clock::from_divider`] to create the clock configuration.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::i2c;
+///
+/// // `None`, if the bit rate would be off by 5% or more.
+/// const DIVIDER: Option<i2c::Divider> =
+///     i2c::Divider::try_from_rate(12_000_000, 400_000, 5);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Divider {
+    divval: u16,
+    mstsclhigh: u8,
+    mstscllow: u8,
+}
+
+impl Divider {
+    /// Compute the divider values for a bit rate
+    ///
+    /// `clock_hz` is the frequency of the clock source that is passed to
+    /// [`Clock::from_divider`]. The resulting bit rate is never higher than
+    /// `rate_hz`, and lower by less than `tolerance_percent`.
+    ///
+    /// Chooses the values that result in the most accurate rate. The SCL low
+    /// time is always equal to or one clock longer than the SCL high time.
+    ///
+    /// Returns `None`, if no values within the tolerance can be found.
+    pub const fn try_from_rate(
+        clock_hz: u32,
+        rate_hz: u32,
+        tolerance_percent: u32,
+    ) -> Option<Self> {
+        if rate_hz == 0 {
+            return None;
+        }
+
+        let mut best: Option<Self> = None;
+        let mut best_deviation = tolerance_percent as u64 * rate_hz as u64;
+
+        // SCL high and low times are each 2-9 divided clocks, so one SCL
+        // period is 4-18 divided clocks. Prefer longer periods, as they result
+        // in a more even duty cycle.
+        let mut period = 18;
+        while period >= 4 {
+            let per_bit = rate_hz as u64 * period;
+
+            // Round the divider up, so we never exceed the requested rate.
+            let divider = (clock_hz as u64).div_ceil(per_bit);
+
+            if divider > 0 && divider - 1 <= u16::MAX as u64 {
+                let resulting_rate = clock_hz as u64 / (divider * period);
+
+                // Deviation in percent, multiplied by `rate_hz`, to avoid
+                // losing precision.
+                let deviation = (rate_hz as u64 - resulting_rate) * 100;
+
+                if deviation < best_deviation {
+                    let high = period / 2;
+                    let low = period - high;
+
+                    best_deviation = deviation;
+                    best = Some(Self {
+                        divval: (divider - 1) as u16,
+                        mstsclhigh: (high - 2) as u8,
+                        mstscllow: (low - 2) as u8,
+                    });
+                }
+            }
+
+            period -= 1;
+        }
+
+        best
+    }
+}
+
+/// Indicates that an I2C clock configuration is invalid
+///
+/// Returned by [`Clock::try_new`] and [`Clock::from_rate`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidClockConfig;
+
 /// Implemented for I2C clock sources
 pub trait ClockSource: private::Sealed {
     /// Select the clock source
@@ -46,11 +227,9 @@ pub trait ClockSource: private::Sealed {
 
 #[cfg(feature = "82x")]
 mod target {
-    use core::marker::PhantomData;
+    use crate::{clock, syscon};
 
-    use crate::syscon;
-
-    use super::{Clock, ClockSource};
+    use super::ClockSource;
 
     impl super::private::Sealed for () {}
 
@@ -61,32 +240,29 @@ mod target {
         }
     }
 
-    impl Clock<()> {
-        /// Create a new I2C clock configuration for 400 kHz
+    impl clock::Frequency for () {
+        /// The frequency of the system clock
         ///
-        /// Assumes the internal oscillator runs at 12 MHz.
-        pub fn new_400khz() -> Self {
-            Self {
-                divval: 5,
-                mstsclhigh: 0,
-                mstscllow: 1,
-                _clock: PhantomData,
-            }
+        /// Assumes the default configuration, in which the system clock is
+        /// derived from the 12 MHz internal oscillator.
+        fn hz(&self) -> u32 {
+            12_000_000
         }
     }
+
+    /// The default clock, which is derived from the 12 MHz internal oscillator
+    pub(super) type Iosc = ();
 }
 
 #[cfg(feature = "845")]
 mod target {
-    use core::marker::PhantomData;
-
     use crate::syscon::{
         self,
         clock_source::{PeripheralClock, PeripheralClockSelector},
         IOSC,
     };
 
-    use super::{Clock, ClockSource};
+    use super::ClockSource;
 
     impl<T> super::private::Sealed for T where T: PeripheralClock {}
     impl<T> ClockSource for T
@@ -101,19 +277,8 @@ mod target {
         }
     }
 
-    impl Clock<IOSC> {
-        /// Create a new I2C clock configuration for 400 kHz
-        ///
-        /// Assumes the internal oscillator runs at 12 MHz.
-        pub fn new_400khz() -> Self {
-            Self {
-                divval: 5,
-                mstsclhigh: 0,
-                mstscllow: 1,
-                _clock: PhantomData,
-            }
-        }
-    }
+    /// The internal oscillator, which runs at 12 MHz
+    pub(super) type Iosc = IOSC;
 }
 
 mod private {
@@ -1,3 +1,5 @@
+use crate::dma;
+
 use super::{master, Instance};
 
 /// I2C error
@@ -6,7 +8,11 @@ use super::{master, Instance};
 pub enum Error {
     /// Event Timeout
     ///
-    /// Corresponds to the EVENTTIMEOUT flag in the STAT register.
+    /// Corresponds to the EVENTTIMEOUT flag in the STAT register. Can only
+    /// occur, if the bus timeout has been enabled using
+    /// [`I2C::enable_timeout`].
+    ///
+    /// [`I2C::enable_timeout`]: struct.I2C.html#method.enable_timeout
     EventTimeout,
 
     /// Master Arbitration Loss
@@ -26,7 +32,11 @@ pub enum Error {
 
     /// SCL Timeout
     ///
-    /// Corresponds to the SCLTIMEOUT flag in the STAT register.
+    /// Corresponds to the SCLTIMEOUT flag in the STAT register. Can only
+    /// occur, if the bus timeout has been enabled using
+    /// [`I2C::enable_timeout`].
+    ///
+    /// [`I2C::enable_timeout`]: struct.I2C.html#method.enable_timeout
     SclTimeout,
 
     /// The I2C code encountered an unexpected hardware state
@@ -48,6 +58,21 @@ pub enum Error {
 
     /// While in slave mode, an unknown state was detected
     UnknownSlaveState(u8),
+
+    /// Bus recovery failed, as SDA is still held low
+    ///
+    /// Returned by [`I2C::recover_bus`].
+    ///
+    /// [`I2C::recover_bus`]: struct.I2C.html#method.recover_bus
+    BusRecoveryFailed,
+
+    /// A DMA transfer could not be started, due to an invalid buffer
+    ///
+    /// Returned by [`Master::write_all`] and [`Master::read_all`].
+    ///
+    /// [`Master::write_all`]: master/struct.Master.html#method.write_all
+    /// [`Master::read_all`]: master/struct.Master.html#method.read_all
+    InvalidTransfer(dma::transfer::InvalidTransfer),
 }
 
 impl Error {
@@ -90,3 +115,25 @@ impl Error {
         Ok(())
     }
 }
+
+#[cfg(feature = "embedded-hal-1")]
+impl embedded_hal_1::i2c::Error for Error {
+    fn kind(&self) -> embedded_hal_1::i2c::ErrorKind {
+        use embedded_hal_1::i2c::{ErrorKind, NoAcknowledgeSource};
+
+        match self {
+            Self::MasterArbitrationLoss => ErrorKind::ArbitrationLoss,
+            Self::MasterStartStopError => ErrorKind::Bus,
+            Self::MonitorOverflow => ErrorKind::Overrun,
+            Self::UnexpectedState {
+                actual: Ok(master::State::NackAddress),
+                ..
+            } => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            Self::UnexpectedState {
+                actual: Ok(master::State::NackData),
+                ..
+            } => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            _ => ErrorKind::Other,
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Async API for the I2C master
+//!
+//! Implements [`embedded_hal_async::i2c::I2c`] for the I2C master, which
+//! allows awaiting transactions from an async executor, instead of
+//! busy-waiting for the bus.
+//!
+//! This module is only available, if the `embedded-hal-1` feature is enabled.
+//!
+//! The futures are woken from the I2C interrupt. For this to work, you need to
+//! call [`on_interrupt`] from the interrupt handler of the I2C instance, and
+//! unmask the interrupt in the NVIC. The futures enable the Master Pending,
+//! Master Arbitration Loss, and Master Start/Stop Error interrupts as
+//! required, and [`on_interrupt`] disables them again, so you shouldn't use
+//! these interrupts for anything else, while using this API.
+//!
+//! Unlike the blocking operations, async transactions are never retried after
+//! an arbitration loss. The error is returned instead.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use core::marker::PhantomData;
+//!
+//! use lpc8xx_hal::{
+//!     embedded_hal_async::i2c::I2c as _,
+//!     i2c, pac,
+//!     init_state::Enabled,
+//!     syscon::IOSC,
+//! };
+//!
+//! async fn read_register(
+//!     master: &mut i2c::Master<pac::I2C0, Enabled<PhantomData<IOSC>>, Enabled>,
+//! ) -> Result<u8, i2c::Error> {
+//!     let mut buffer = [0];
+//!     master.write_read(0x48, &[0x00], &mut buffer).await?;
+//!     Ok(buffer[0])
+//! }
+//!
+//! // Call this from the I2C0 interrupt handler.
+//! i2c::futures::on_interrupt::<pac::I2C0>();
+//! ```
+//!
+//! [`embedded_hal_async::i2c::I2c`]: ../master/struct.Master.html#impl-I2c%3CSevenBitAddress%3E-1
+//! [`on_interrupt`]: fn.on_interrupt.html
+
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use embedded_hal_1::i2c::Operation;
+
+use crate::init_state::Enabled;
+
+use super::{
+    master::{Master, Rw, State},
+    Error, Instance,
+};
+
+/// Wake the future that is waiting for I2C instance `I`
+///
+/// Must be called from the interrupt handler of I2C instance `I`. Disables
+/// the interrupts that were enabled by the futures, and wakes the future that
+/// was waiting for them.
+pub fn on_interrupt<I>()
+where
+    I: Instance,
+{
+    // Sound, as we only write to a stateless register, and the interrupts we
+    // disable are managed by this module.
+    let i2c = unsafe { &*I::REGISTERS };
+
+    i2c.intenclr.write(|w| {
+        w.mstpendingclr().set_bit();
+        w.mstarblossclr().set_bit();
+        w.mstststperrclr().set_bit()
+    });
+
+    I::waker().wake();
+}
+
+impl<I, C> embedded_hal_async::i2c::I2c
+    for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    /// Execute a sequence of operations
+    ///
+    /// Adjacent operations of the same direction are merged. Operations of
+    /// different directions are separated by a repeated start. Please refer to
+    /// the [module documentation] for the requirements.
+    ///
+    /// [module documentation]: ../futures/index.html
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        TransactionFuture {
+            master: self,
+            address,
+            operations,
+            progress: Progress::Idle,
+        }
+        .await
+    }
+}
+
+struct TransactionFuture<'r, 'o, I: Instance, C> {
+    master: &'r mut Master<I, Enabled<PhantomData<C>>, Enabled>,
+    address: u8,
+    operations: &'r mut [Operation<'o>],
+    progress: Progress,
+}
+
+impl<I, C> TransactionFuture<'_, '_, I, C>
+where
+    I: Instance,
+{
+    /// Make as much progress as possible without waiting
+    fn advance(&mut self) -> nb::Result<(), Error> {
+        loop {
+            match self.progress {
+                Progress::Idle => {
+                    Error::check_address(self.address)?;
+                    self.master.poll_state(State::Idle)?;
+
+                    let op = match self.next_operation(0) {
+                        Some(op) => op,
+                        None => return Ok(()),
+                    };
+
+                    self.master.send_start(self.address, self.rw(op));
+                    self.progress = self.start_of(op);
+                }
+                Progress::Writing { op, pos } => {
+                    self.master.poll_state(State::TxReady)?;
+
+                    let bytes = match &self.operations[op] {
+                        Operation::Write(bytes) => bytes,
+                        Operation::Read(_) => unreachable!(),
+                    };
+
+                    if pos < bytes.len() {
+                        self.master.send_byte(bytes[pos]);
+                        self.progress = Progress::Writing { op, pos: pos + 1 };
+                        continue;
+                    }
+
+                    match self.next_operation(op + 1) {
+                        None => {
+                            self.master.stop();
+                            return Ok(());
+                        }
+                        Some(next) => {
+                            if let Rw::Read = self.rw(next) {
+                                // Repeated start
+                                self.master.send_start(self.address, Rw::Read);
+                            }
+                            self.progress = self.start_of(next);
+                        }
+                    }
+                }
+                Progress::Reading { op, pos } => {
+                    self.master.poll_state(State::RxReady)?;
+
+                    let byte = self.master.receive_byte();
+                    let len = match &mut self.operations[op] {
+                        Operation::Read(buffer) => {
+                            buffer[pos] = byte;
+                            buffer.len()
+                        }
+                        Operation::Write(_) => unreachable!(),
+                    };
+
+                    if pos + 1 < len {
+                        self.master.continue_read();
+                        self.progress = Progress::Reading { op, pos: pos + 1 };
+                        continue;
+                    }
+
+                    match self.next_operation(op + 1) {
+                        None => {
+                            self.master.stop();
+                            return Ok(());
+                        }
+                        Some(next) => {
+                            match self.rw(next) {
+                                Rw::Read => self.master.continue_read(),
+                                // Repeated start
+                                Rw::Write => self
+                                    .master
+                                    .send_start(self.address, Rw::Write),
+                            }
+                            self.progress = self.start_of(next);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the index of the next operation, starting at `from`
+    ///
+    /// Skips empty reads, as the master can't start a read without receiving
+    /// a byte.
+    fn next_operation(&self, from: usize) -> Option<usize> {
+        (from..self.operations.len()).find(|&i| match &self.operations[i] {
+            Operation::Read(buffer) => !buffer.is_empty(),
+            Operation::Write(_) => true,
+        })
+    }
+
+    fn rw(&self, op: usize) -> Rw {
+        match self.operations[op] {
+            Operation::Read(_) => Rw::Read,
+            Operation::Write(_) => Rw::Write,
+        }
+    }
+
+    fn start_of(&self, op: usize) -> Progress {
+        match self.rw(op) {
+            Rw::Read => Progress::Reading { op, pos: 0 },
+            Rw::Write => Progress::Writing { op, pos: 0 },
+        }
+    }
+}
+
+impl<I, C> Future for TransactionFuture<'_, '_, I, C>
+where
+    I: Instance,
+{
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Register first, so an interrupt right after the checks below can't
+        // get lost.
+        I::waker().register(cx.waker());
+
+        match self.get_mut().advance() {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(nb::Error::Other(err)) => Poll::Ready(Err(err)),
+            Err(nb::Error::WouldBlock) => {
+                // Sound, as we only write to a stateless register, and the
+                // interrupts we enable are managed by this module.
+                let i2c = unsafe { &*I::REGISTERS };
+
+                i2c.intenset.write(|w| {
+                    w.mstpendingen().enabled();
+                    w.mstarblossen().enabled();
+                    w.mstststperren().enabled()
+                });
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Progress of a transaction
+#[derive(Clone, Copy)]
+enum Progress {
+    /// The transaction hasn't been started yet
+    Idle,
+
+    /// Writing operation `op`, with `pos` bytes written so far
+    Writing { op: usize, pos: usize },
+
+    /// Reading operation `op`, with `pos` bytes read so far
+    Reading { op: usize, pos: usize },
+}
@@ -3,10 +3,14 @@ use core::ops::Deref;
 use crate::{
     dma,
     pac::{self, Interrupt},
+    pins::{GenericPin, Trait as _},
     swm,
     syscon::{self, clock_source::PeripheralClockSelector},
 };
 
+#[cfg(feature = "embedded-hal-1")]
+use crate::waker::WakerSlot;
+
 /// Implemented for all I2C instances
 pub trait Instance:
     private::Sealed
@@ -32,6 +36,13 @@ pub trait Instance:
 
     /// The DMA channel used with this instance for master mode
     type MstChannel: dma::channels::Instance;
+
+    /// The interrupt wake-up flag for this instance
+    type Wakeup: syscon::WakeUpInterrupt;
+
+    /// The storage for the waker of the async API
+    #[cfg(feature = "embedded-hal-1")]
+    fn waker() -> &'static WakerSlot;
 }
 
 macro_rules! instances {
@@ -43,12 +54,11 @@ macro_rules! instances {
             $rx:ident,
             $tx:ident,
             $slv_channel:ident,
-            $mst_channel:ident;
+            $mst_channel:ident,
+            $wakeup:ident;
         )*
     ) => {
         $(
-            impl private::Sealed for pac::$instance {}
-
             impl Instance for pac::$instance {
                 const INTERRUPT: Interrupt = Interrupt::$interrupt;
                 const REGISTERS: *const pac::i2c0::RegisterBlock =
@@ -59,6 +69,14 @@ macro_rules! instances {
 
                 type SlvChannel = dma::$slv_channel;
                 type MstChannel = dma::$mst_channel;
+
+                type Wakeup = syscon::$wakeup;
+
+                #[cfg(feature = "embedded-hal-1")]
+                fn waker() -> &'static WakerSlot {
+                    static WAKER: WakerSlot = WakerSlot::new();
+                    &WAKER
+                }
             }
 
             impl PeripheralClockSelector for pac::$instance {
@@ -70,20 +88,135 @@ macro_rules! instances {
 
 #[cfg(feature = "82x")]
 instances!(
-    I2C0, 5, I2C0, I2C0_SDA, I2C0_SCL, Channel10, Channel11;
-    I2C1, 6, I2C1, I2C1_SDA, I2C1_SCL, Channel12, Channel13;
-    I2C2, 7, I2C2, I2C2_SDA, I2C2_SCL, Channel14, Channel15;
-    I2C3, 8, I2C3, I2C3_SDA, I2C3_SCL, Channel16, Channel17;
+    I2C0, 5, I2C0, I2C0_SDA, I2C0_SCL, Channel10, Channel11, I2c0Wakeup;
+    I2C1, 6, I2C1, I2C1_SDA, I2C1_SCL, Channel12, Channel13, I2c1Wakeup;
+    I2C2, 7, I2C2, I2C2_SDA, I2C2_SCL, Channel14, Channel15, I2c2Wakeup;
+    I2C3, 8, I2C3, I2C3_SDA, I2C3_SCL, Channel16, Channel17, I2c3Wakeup;
 );
 
 #[cfg(feature = "845")]
 instances!(
-    I2C0, 5, I2C0, I2C0_SDA, I2C0_SCL, Channel14, Channel15;
-    I2C1, 6, I2C1, I2C1_SDA, I2C1_SCL, Channel16, Channel17;
-    I2C2, 7, I2C2, I2C2_SDA, I2C2_SCL, Channel18, Channel19;
-    I2C3, 8, I2C3, I2C3_SDA, I2C3_SCL, Channel20, Channel21;
+    I2C0, 5, I2C0, I2C0_SDA, I2C0_SCL, Channel14, Channel15, I2c0Wakeup;
+    I2C1, 6, I2C1, I2C1_SDA, I2C1_SCL, Channel16, Channel17, I2c1Wakeup;
+    I2C2, 7, I2C2, I2C2_SDA, I2C2_SCL, Channel18, Channel19, I2c2Wakeup;
+    I2C3, 8, I2C3, I2C3_SDA, I2C3_SCL, Channel20, Channel21, I2c3Wakeup;
+);
+
+impl private::Sealed for pac::I2C0 {
+    fn bus_pins(swm: &swm::Handle) -> Option<(GenericPin, GenericPin)> {
+        // SCL and SDA are fixed functions on I2C0. They are assigned, if their
+        // bits in PINENABLE0 are cleared.
+        let pinenable0 = swm.swm.pinenable0.read();
+        if pinenable0.i2c0_scl().bit_is_set()
+            || pinenable0.i2c0_sda().bit_is_set()
+        {
+            return None;
+        }
+
+        Some((GenericPin::new(0, 10), GenericPin::new(0, 11)))
+    }
+
+    fn release_bus_pins(swm: &mut swm::Handle) {
+        swm.swm
+            .pinenable0
+            .modify(|_, w| w.i2c0_scl().set_bit().i2c0_sda().set_bit());
+    }
+
+    fn restore_bus_pins(
+        swm: &mut swm::Handle,
+        _scl: &GenericPin,
+        _sda: &GenericPin,
+    ) {
+        swm.swm
+            .pinenable0
+            .modify(|_, w| w.i2c0_scl().clear_bit().i2c0_sda().clear_bit());
+    }
+}
+
+macro_rules! movable_bus_pins {
+    (
+        $(
+            $instance:ident,
+            $scl_reg:ident,
+            $scl_field:ident,
+            $sda_reg:ident,
+            $sda_field:ident;
+        )*
+    ) => {
+        $(
+            impl private::Sealed for pac::$instance {
+                fn bus_pins(
+                    swm: &swm::Handle,
+                ) -> Option<(GenericPin, GenericPin)> {
+                    let scl = swm.swm.$scl_reg.read().$scl_field().bits();
+                    let sda = swm.swm.$sda_reg.read().$sda_field().bits();
+
+                    // 0xff means that the function is not assigned.
+                    if scl == 0xff || sda == 0xff {
+                        return None;
+                    }
+
+                    Some((
+                        GenericPin::new(scl >> 5, scl & 0x1f),
+                        GenericPin::new(sda >> 5, sda & 0x1f),
+                    ))
+                }
+
+                fn release_bus_pins(swm: &mut swm::Handle) {
+                    swm.swm.$scl_reg.modify(|_, w| unsafe {
+                        w.$scl_field().bits(0xff)
+                    });
+                    swm.swm.$sda_reg.modify(|_, w| unsafe {
+                        w.$sda_field().bits(0xff)
+                    });
+                }
+
+                fn restore_bus_pins(
+                    swm: &mut swm::Handle,
+                    scl: &GenericPin,
+                    sda: &GenericPin,
+                ) {
+                    swm.swm.$scl_reg.modify(|_, w| unsafe {
+                        w.$scl_field().bits(scl.id() | scl.port() << 5)
+                    });
+                    swm.swm.$sda_reg.modify(|_, w| unsafe {
+                        w.$sda_field().bits(sda.id() | sda.port() << 5)
+                    });
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "82x")]
+movable_bus_pins!(
+    I2C1, pinassign9 , i2c1_scl_io, pinassign9 , i2c1_sda_io;
+    I2C2, pinassign10, i2c2_scl_io, pinassign9 , i2c2_sda_io;
+    I2C3, pinassign10, i2c3_scl_io, pinassign10, i2c3_sda_io;
+);
+
+#[cfg(feature = "845")]
+movable_bus_pins!(
+    I2C1, pinassign9 , i2c1_scl_io, pinassign9 , i2c1_sda_io;
+    I2C2, pinassign10, i2c2_scl_io, pinassign10, i2c2_sda_io;
+    I2C3, pinassign10, i2c3_scl_io, pinassign10, i2c3_sda_io;
 );
 
 mod private {
-    pub trait Sealed {}
+    use crate::{pins::GenericPin, swm};
+
+    pub trait Sealed {
+        /// Returns the pins that SCL and SDA are assigned to, if any
+        fn bus_pins(swm: &swm::Handle) -> Option<(GenericPin, GenericPin)>;
+
+        /// Unassigns SCL and SDA from their pins
+        fn release_bus_pins(swm: &mut swm::Handle);
+
+        /// Assigns SCL and SDA to the provided pins again
+        fn restore_bus_pins(
+            swm: &mut swm::Handle,
+            scl: &GenericPin,
+            sda: &GenericPin,
+        );
+    }
 }
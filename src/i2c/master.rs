@@ -7,6 +7,8 @@ use core::{
 };
 
 use embedded_hal::blocking::i2c;
+#[cfg(feature = "embedded-hal-1")]
+use embedded_hal_1::i2c::Operation;
 
 use crate::{
     dma::{self, transfer::state::Ready},
@@ -31,16 +33,33 @@ use super::{Error, Instance};
 /// # `embedded-hal` traits
 /// - [`embedded_hal::blocking::i2c::Read`] for blocking reads
 /// - [`embedded_hal::blocking::i2c::Write`] for blocking writes
+/// - [`embedded_hal::blocking::i2c::WriteIter`] for blocking writes from an
+///   iterator
+/// - [`embedded_hal::blocking::i2c::WriteIterRead`] for blocking writes from
+///   an iterator, followed by a read
+///
+/// From embedded-hal 1.0, [`embedded_hal_1::i2c::I2c`] is implemented too,
+/// which supports arbitrary sequences of reads and writes, separated by
+/// repeated starts.
+/// [`embedded_hal_async::i2c::I2c`] is implemented for async transactions
+/// (see the [`futures`] module).
 ///
 /// [`I2C`]: ../struct.I2C.html
 /// [`embedded_hal::blocking::i2c::Read`]: #impl-Read
 /// [`embedded_hal::blocking::i2c::Write`]: #impl-Write
+/// [`embedded_hal::blocking::i2c::WriteIter`]: #impl-WriteIter
+/// [`embedded_hal::blocking::i2c::WriteIterRead`]: #impl-WriteIterRead
+/// [`embedded_hal_1::i2c::I2c`]: #impl-I2c%3CSevenBitAddress%3E
+/// [`embedded_hal_async::i2c::I2c`]: #impl-I2c%3CSevenBitAddress%3E-1
+/// [`futures`]: ../futures/index.html
 pub struct Master<I: Instance, State, ModeState> {
     _state: PhantomData<State>,
     _mode_state: PhantomData<ModeState>,
 
     mstctl: RegProxy<MstCtl<I>>,
     mstdat: RegProxy<MstDat<I>>,
+
+    retry_policy: RetryPolicy,
 }
 
 impl<I, State, ModeState> Master<I, State, ModeState>
@@ -54,6 +73,8 @@ where
 
             mstctl: RegProxy::new(),
             mstdat: RegProxy::new(),
+
+            retry_policy: RetryPolicy::default(),
         }
     }
 }
@@ -62,11 +83,29 @@ impl<I, C> Master<I, Enabled<PhantomData<C>>, Enabled>
 where
     I: Instance,
 {
+    /// Set the policy for retrying after arbitration loss
+    ///
+    /// If another master wins arbitration, the blocking operations return
+    /// [`Error::MasterArbitrationLoss`] by default. This is relevant, if
+    /// multiple masters share the bus. See [`RetryPolicy`] for how to retry
+    /// automatically instead.
+    ///
+    /// Operations using DMA and the async API are never retried.
+    ///
+    /// [`Error::MasterArbitrationLoss`]: ../enum.Error.html#variant.MasterArbitrationLoss
+    /// [`RetryPolicy`]: struct.RetryPolicy.html
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
     /// Writes the provided buffer using DMA
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    /// Returns [`Error::InvalidTransfer`], if the length of `buffer` is 0 or
+    /// larger than 1024. Nothing is sent on the bus in that case.
+    ///
+    /// [`Error::InvalidTransfer`]: ../enum.Error.html#variant.InvalidTransfer
     pub fn write_all(
         mut self,
         address: u8,
@@ -74,17 +113,23 @@ where
         channel: dma::Channel<I::MstChannel, Enabled>,
     ) -> Result<dma::Transfer<Ready, I::MstChannel, &'static [u8], Self>, Error>
     {
+        dma::transfer::check(&buffer, &self).map_err(Error::InvalidTransfer)?;
+
         self.start_operation(address, Rw::Write)?;
         self.wait_for_state(State::TxReady)?;
         self.mstctl.modify(|_, w| w.mstdma().enabled());
-        Ok(dma::Transfer::new(channel, buffer, self))
+        dma::Transfer::new(channel, buffer, self)
+            .map_err(|(error, _)| Error::InvalidTransfer(error))
     }
 
     /// Reads until the provided buffer is full, using DMA
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    /// Returns [`Error::InvalidTransfer`], if the length of `buffer` is 0 or
+    /// larger than 1024. Nothing is sent on the bus in that case.
+    ///
+    /// [`Error::InvalidTransfer`]: ../enum.Error.html#variant.InvalidTransfer
     pub fn read_all(
         mut self,
         address: u8,
@@ -94,9 +139,12 @@ where
         dma::Transfer<Ready, I::MstChannel, Self, &'static mut [u8]>,
         Error,
     > {
+        dma::transfer::check(&self, &buffer).map_err(Error::InvalidTransfer)?;
+
         self.start_operation(address, Rw::Read)?;
         self.mstctl.modify(|_, w| w.mstdma().enabled());
-        Ok(dma::Transfer::new(channel, self, buffer))
+        dma::Transfer::new(channel, self, buffer)
+            .map_err(|(error, _)| Error::InvalidTransfer(error))
     }
 
     /// Wait while the peripheral is busy
@@ -104,27 +152,65 @@ where
     /// Once this method returns, the peripheral should either be idle or in a
     /// state that requires software interaction.
     fn wait_for_state(&self, expected: State) -> Result<(), Error> {
+        nb::block!(self.poll_state(expected))
+    }
+
+    /// Check the state, unless the peripheral is busy
+    ///
+    /// Returns `WouldBlock`, while the peripheral is busy.
+    pub(super) fn poll_state(&self, expected: State) -> nb::Result<(), Error> {
         // Sound, as we're only reading from the STAT register.
         let i2c = unsafe { &*I::REGISTERS };
 
-        while i2c.stat.read().mstpending().is_in_progress() {
-            Error::read::<I>()?;
+        Error::read::<I>()?;
+
+        if i2c.stat.read().mstpending().is_in_progress() {
+            return Err(nb::Error::WouldBlock);
         }
 
         let mststate = i2c.stat.read().mststate();
         let actual =
             mststate.variant().try_into().map_err(|()| mststate.bits());
         if Ok(&expected) != actual.as_ref() {
-            return Err(Error::UnexpectedState { expected, actual });
+            return Err(nb::Error::Other(Error::UnexpectedState {
+                expected,
+                actual,
+            }));
         }
 
         Ok(())
     }
 
-    fn start_operation(&mut self, address: u8, rw: Rw) -> Result<(), Error> {
+    pub(super) fn start_operation(
+        &mut self,
+        address: u8,
+        rw: Rw,
+    ) -> Result<(), Error> {
         Error::check_address(address)?;
         self.wait_for_state(State::Idle)?;
+        self.send_start(address, rw);
+
+        Ok(())
+    }
+
+    /// Send a repeated start, after an operation in direction `previous`
+    pub(super) fn restart_operation(
+        &mut self,
+        address: u8,
+        rw: Rw,
+        previous: Rw,
+    ) -> Result<(), Error> {
+        Error::check_address(address)?;
+        self.wait_for_state(match previous {
+            Rw::Write => State::TxReady,
+            Rw::Read => State::RxReady,
+        })?;
+        self.send_start(address, rw);
 
+        Ok(())
+    }
+
+    pub(super) fn send_start(&mut self, address: u8, rw: Rw) {
         // Write address
         let address_rw = (address << 1) | rw as u8;
         self.mstdat.write(|w| unsafe {
@@ -134,24 +220,173 @@ where
 
         // Start operation
         self.mstctl.write(|w| w.mststart().start());
+    }
+
+    pub(super) fn write_bytes(&mut self, data: &[u8]) -> Result<(), Error> {
+        for &b in data {
+            self.wait_for_state(State::TxReady)?;
+            self.send_byte(b);
+        }
 
         Ok(())
     }
 
-    fn finish_write(&mut self) -> Result<(), Error> {
-        self.wait_for_state(State::TxReady)?;
+    /// Send a byte, once the peripheral is ready to transmit
+    pub(super) fn send_byte(&mut self, b: u8) {
+        // Write byte
+        self.mstdat.write(|w| unsafe { w.data().bits(b) });
+
+        // Continue transmission
+        self.mstctl.write(|w| w.mstcontinue().continue_());
+    }
+
+    /// Read a received byte, once it is available
+    pub(super) fn receive_byte(&mut self) -> u8 {
+        self.mstdat.read().data().bits()
+    }
 
-        // Stop operation
+    /// Continue a read, after a received byte has been read
+    pub(super) fn continue_read(&mut self) {
+        self.mstctl.write(|w| w.mstcontinue().continue_());
+    }
+
+    /// Stop the current operation
+    pub(super) fn stop(&mut self) {
         self.mstctl.write(|w| w.mststop().stop());
+    }
+
+    /// Write bytes from an iterator
+    ///
+    /// Unlike the other blocking operations, this is never retried, as the
+    /// iterator can't be restarted.
+    fn write_iter_bytes<B>(
+        &mut self,
+        address: u8,
+        bytes: B,
+    ) -> Result<(), Error>
+    where
+        B: IntoIterator<Item = u8>,
+    {
+        self.start_operation(address, Rw::Write)?;
+
+        for b in bytes {
+            self.wait_for_state(State::TxReady)?;
+            self.send_byte(b);
+        }
 
         Ok(())
     }
 
-    fn finish_read(&mut self) -> Result<(), Error> {
-        self.wait_for_state(State::RxReady)?;
+    /// Read bytes into `buffer`
+    ///
+    /// If `continued` is `true`, the read continues a previous one, whose last
+    /// byte has already been read.
+    pub(super) fn read_bytes(
+        &mut self,
+        buffer: &mut [u8],
+        continued: bool,
+    ) -> Result<(), Error> {
+        for (i, b) in buffer.iter_mut().enumerate() {
+            if i != 0 || continued {
+                self.continue_read();
+            }
 
-        // Stop operation
-        self.mstctl.write(|w| w.mststop().stop());
+            self.wait_for_state(State::RxReady)?;
+            *b = self.receive_byte();
+        }
+
+        Ok(())
+    }
+
+    /// Run an operation, retrying it according to the retry policy
+    fn retrying(
+        &mut self,
+        mut operation: impl FnMut(&mut Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let mut retries = 0;
+
+        loop {
+            match operation(self) {
+                Err(Error::MasterArbitrationLoss)
+                    if retries < self.retry_policy.max_retries =>
+                {
+                    retries += 1;
+
+                    // The master returns to idle after losing arbitration, so
+                    // we can start over after backing off.
+                    let backoff = self
+                        .retry_policy
+                        .backoff_cycles
+                        .saturating_mul(retries.into());
+                    cortex_m::asm::delay(backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Execute a sequence of operations
+    ///
+    /// Adjacent operations of the same direction are merged. Operations of
+    /// different directions are separated by a repeated start.
+    #[cfg(feature = "embedded-hal-1")]
+    fn exec(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation],
+    ) -> Result<(), Error> {
+        self.retrying(|master| master.exec_once(address, operations))
+    }
+
+    #[cfg(feature = "embedded-hal-1")]
+    fn exec_once(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation],
+    ) -> Result<(), Error> {
+        let mut previous = None;
+
+        for operation in operations {
+            let rw = match operation {
+                Operation::Read(_) => Rw::Read,
+                Operation::Write(_) => Rw::Write,
+            };
+
+            match previous {
+                None => self.start_operation(address, rw)?,
+                Some(previous) if previous != rw => {
+                    self.restart_operation(address, rw, previous)?
+                }
+                Some(_) => {}
+            }
+
+            match operation {
+                Operation::Read(buffer) => {
+                    self.read_bytes(buffer, previous == Some(Rw::Read))?
+                }
+                Operation::Write(data) => self.write_bytes(data)?,
+            }
+
+            previous = Some(rw);
+        }
+
+        match previous {
+            Some(Rw::Write) => self.finish_write(),
+            Some(Rw::Read) => self.finish_read(),
+            None => Ok(()),
+        }
+    }
+
+    pub(super) fn finish_write(&mut self) -> Result<(), Error> {
+        self.wait_for_state(State::TxReady)?;
+        self.stop();
+
+        Ok(())
+    }
+
+    pub(super) fn finish_read(&mut self) -> Result<(), Error> {
+        self.wait_for_state(State::RxReady)?;
+        self.stop();
 
         Ok(())
     }
@@ -169,21 +404,11 @@ where
     ///
     /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.1/embedded_hal/blocking/i2c/trait.Write.html#tymethod.write
     fn write(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
-        self.start_operation(address, Rw::Write)?;
-
-        for &b in data {
-            self.wait_for_state(State::TxReady)?;
-
-            // Write byte
-            self.mstdat.write(|w| unsafe { w.data().bits(b) });
-
-            // Continue transmission
-            self.mstctl.write(|w| w.mstcontinue().continue_());
-        }
-
-        self.finish_write()?;
-
-        Ok(())
+        self.retrying(|master| {
+            master.start_operation(address, Rw::Write)?;
+            master.write_bytes(data)?;
+            master.finish_write()
+        })
     }
 }
 
@@ -203,23 +428,82 @@ where
         address: u8,
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.start_operation(address, Rw::Read)?;
+        self.retrying(|master| {
+            master.start_operation(address, Rw::Read)?;
+            master.read_bytes(buffer, false)?;
+            master.finish_read()
+        })
+    }
+}
 
-        for (i, b) in buffer.iter_mut().enumerate() {
-            if i != 0 {
-                // Continue transmission
-                self.mstctl.write(|w| w.mstcontinue().continue_());
-            }
+impl<I, C> i2c::WriteIter for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
 
-            self.wait_for_state(State::RxReady)?;
+    /// Write to the I2C bus from an iterator
+    ///
+    /// Please refer to the [embedded-hal documentation] for details.
+    ///
+    /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.4/embedded_hal/blocking/i2c/trait.WriteIter.html#tymethod.write
+    fn write<B>(&mut self, address: u8, bytes: B) -> Result<(), Self::Error>
+    where
+        B: IntoIterator<Item = u8>,
+    {
+        self.write_iter_bytes(address, bytes)?;
+        self.finish_write()
+    }
+}
 
-            // Read received byte
-            *b = self.mstdat.read().data().bits();
-        }
+impl<I, C> i2c::WriteIterRead for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
 
-        self.finish_read()?;
+    /// Write to the I2C bus from an iterator, then read from it
+    ///
+    /// Please refer to the [embedded-hal documentation] for details.
+    ///
+    /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.4/embedded_hal/blocking/i2c/trait.WriteIterRead.html#tymethod.write_iter_read
+    fn write_iter_read<B>(
+        &mut self,
+        address: u8,
+        bytes: B,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error>
+    where
+        B: IntoIterator<Item = u8>,
+    {
+        self.write_iter_bytes(address, bytes)?;
+        self.restart_operation(address, Rw::Read, Rw::Write)?;
+        self.read_bytes(buffer, false)?;
+        self.finish_read()
+    }
+}
 
-        Ok(())
+#[cfg(feature = "embedded-hal-1")]
+impl<I, C> embedded_hal_1::i2c::ErrorType
+    for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<I, C> embedded_hal_1::i2c::I2c
+    for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.exec(address, operations)
     }
 }
 
@@ -310,19 +594,40 @@ where
             .field("_mode_state", &self._mode_state)
             .field("mstctl", &self.mstctl)
             .field("mstdat", &self.mstdat)
+            .field("retry_policy", &self.retry_policy)
             .finish()
     }
 }
 
+/// Policy for retrying operations after arbitration loss
+///
+/// Used by [`Master::set_retry_policy`]. The default policy doesn't retry.
+///
+/// [`Master::set_retry_policy`]: struct.Master.html#method.set_retry_policy
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of times an operation is retried
+    pub max_retries: u8,
+
+    /// The base back-off time before a retry, in core clock cycles
+    ///
+    /// The back-off time grows linearly: The first retry waits for this number
+    /// of cycles, the second one for twice that number, and so forth.
+    /// Choosing different values on each master makes repeated collisions less
+    /// likely.
+    pub backoff_cycles: u32,
+}
+
 /// Private helper struct to model the R/W bit
+#[derive(Clone, Copy, Eq, PartialEq)]
 #[repr(u8)]
-enum Rw {
+pub(super) enum Rw {
     Write = 0,
     Read = 1,
 }
 
 /// The state of an I2C instance set to master mode
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum State {
     /// The peripheral is currently idle
     ///
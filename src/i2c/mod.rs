@@ -70,11 +70,14 @@ mod instances;
 mod interrupts;
 mod peripheral;
 
+#[cfg(feature = "embedded-hal-1")]
+pub mod futures;
 pub mod master;
 pub mod slave;
+pub mod smbus;
 
 pub use self::{
-    clock::{Clock, ClockSource},
+    clock::{Clock, ClockSource, Divider, InvalidClockConfig},
     error::Error,
     instances::Instance,
     interrupts::Interrupts,
@@ -1,8 +1,18 @@
 use core::{fmt, marker::PhantomData};
 
-use crate::{init_state, swm, syscon};
+use embedded_hal::blocking::delay::DelayUs;
 
-use super::{Clock, ClockSource, Error, Instance, Interrupts, Master, Slave};
+use crate::{
+    gpio::{direction, GpioPin, Level},
+    init_state, pac,
+    pins::DynamicPinDirection,
+    swm, syscon,
+};
+
+use super::{
+    slave::AddressQualifier, Clock, ClockSource, Error, Instance, Interrupts,
+    Master, Slave,
+};
 
 /// Interface to an I2C peripheral
 ///
@@ -92,7 +102,10 @@ where
     /// Check out the LPC84x user manual, section 19.4, for example.
     ///
     /// If you don't mess with the IOCON configuration and use I2C clock rates
-    /// of up to 400 kHz, you should be fine.
+    /// of up to 400 kHz, you should be fine. For Fast-mode Plus on I2C0, use
+    /// [`enable_master_mode_fast_plus`] instead.
+    ///
+    /// [`enable_master_mode_fast_plus`]: #method.enable_master_mode_fast_plus
     pub fn enable_master_mode(
         self,
         clock: &Clock<C>,
@@ -124,6 +137,48 @@ where
     }
 }
 
+impl<C, SlaveMode>
+    I2C<
+        pac::I2C0,
+        init_state::Enabled<PhantomData<C>>,
+        init_state::Disabled,
+        SlaveMode,
+    >
+{
+    /// Enable master mode in Fast-mode Plus
+    ///
+    /// Works like [`enable_master_mode`], but also sets the I2C mode of the
+    /// I2C0 pins (PIO0_10 and PIO0_11) to Fast-mode Plus in IOCON, which is
+    /// required for bit rates of up to 1 MHz. Use this with
+    /// [`Clock::new_1mhz`], or another clock configuration for a bit rate
+    /// above 400 kHz.
+    ///
+    /// [`enable_master_mode`]: #method.enable_master_mode
+    /// [`Clock::new_1mhz`]: struct.Clock.html#method.new_1mhz
+    pub fn enable_master_mode_fast_plus(
+        self,
+        clock: &Clock<C>,
+        iocon: &mut pac::IOCON,
+    ) -> I2C<
+        pac::I2C0,
+        init_state::Enabled<PhantomData<C>>,
+        init_state::Enabled,
+        SlaveMode,
+    > {
+        // I2CMODE 0x2 selects Fast-mode Plus.
+        //
+        // Sound, as that is a valid value for the I2CMODE field.
+        iocon
+            .pio0_10
+            .modify(|_, w| unsafe { w.i2cmode().bits(0x2) });
+        iocon
+            .pio0_11
+            .modify(|_, w| unsafe { w.i2cmode().bits(0x2) });
+
+        self.enable_master_mode(clock)
+    }
+}
+
 impl<I, C, MasterMode>
     I2C<
         I,
@@ -179,6 +234,120 @@ where
     }
 }
 
+impl<I, C, MasterMode>
+    I2C<I, init_state::Enabled<PhantomData<C>>, MasterMode, init_state::Enabled>
+where
+    I: Instance,
+{
+    /// Set or clear one of the additional slave addresses
+    ///
+    /// Besides the address passed to [`enable_slave_mode`], which is slave
+    /// address 0, the slave can answer on up to three more addresses. `index`
+    /// selects the slave address (1 to 3, or 0 to replace the original one).
+    /// Passing `None` as the `address` disables the slave address.
+    ///
+    /// [`AddressMatched::index`] tells which address has been matched.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `index` is larger than 3.
+    ///
+    /// [`enable_slave_mode`]: #method.enable_slave_mode
+    /// [`AddressMatched::index`]: slave/struct.AddressMatched.html#method.index
+    pub fn set_slave_address(
+        &mut self,
+        index: usize,
+        address: Option<u8>,
+    ) -> Result<(), Error> {
+        assert!(index <= 3);
+
+        match address {
+            Some(address) => {
+                Error::check_address(address)?;
+
+                self.i2c.slvadr[index].write(|w| {
+                    w.sadisable().enabled();
+
+                    // Sound, as all possible 7-bit values are acceptable here.
+                    unsafe { w.slvadr().bits(address) }
+                });
+            }
+            None => {
+                self.i2c.slvadr[index].write(|w| w.sadisable().disabled());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Configure the address qualifier for slave address 0
+    ///
+    /// The qualifier allows slave address 0 to match a whole set of
+    /// addresses, which can be used to emulate devices with address selection
+    /// pins, for example. See [`AddressQualifier`] for the options.
+    ///
+    /// [`AddressMatched::address`] returns the address that has actually been
+    /// received.
+    ///
+    /// [`AddressQualifier`]: slave/enum.AddressQualifier.html
+    /// [`AddressMatched::address`]: slave/struct.AddressMatched.html#method.address
+    pub fn set_address_qualifier(
+        &mut self,
+        qualifier: AddressQualifier,
+    ) -> Result<(), Error> {
+        // QUALMODE0 selects between mask (cleared) and range (set).
+        let (range, value) = match qualifier {
+            AddressQualifier::Mask(mask) => (false, mask),
+            AddressQualifier::Range(upper) => (true, upper),
+        };
+        Error::check_address(value)?;
+
+        self.i2c.slvqual0.write(|w| {
+            w.qualmode0().bit(range);
+
+            // Sound, as we've checked that the value fits into SLVQUAL0.
+            unsafe { w.slvqual0().bits(value) }
+        });
+
+        Ok(())
+    }
+
+    /// Enable wake-up from deep-sleep and power-down modes on address match
+    ///
+    /// Enables the slave pending interrupt, and configures SYSCON to wake up
+    /// the system, once it is triggered. While the system is asleep, the
+    /// slave stretches the clock after receiving a matching address, so no
+    /// data is lost. The interrupt still needs to be unmasked in the NVIC.
+    ///
+    /// After waking up, the slave is in the "address matched" state. Use
+    /// [`Slave::wait`] to handle it, as usual.
+    ///
+    /// [`Slave::wait`]: slave/struct.Slave.html#method.wait
+    pub fn enable_address_match_wakeup(&mut self, syscon: &mut syscon::Handle) {
+        self.enable_interrupts(Interrupts {
+            slave_pending: true,
+            ..Interrupts::default()
+        });
+        syscon.enable_interrupt_wakeup::<I::Wakeup>();
+    }
+
+    /// Disable wake-up from deep-sleep and power-down modes on address match
+    ///
+    /// Reverts the configuration done by [`enable_address_match_wakeup`].
+    ///
+    /// [`enable_address_match_wakeup`]: #method.enable_address_match_wakeup
+    pub fn disable_address_match_wakeup(
+        &mut self,
+        syscon: &mut syscon::Handle,
+    ) {
+        self.disable_interrupts(Interrupts {
+            slave_pending: true,
+            ..Interrupts::default()
+        });
+        syscon.disable_interrupt_wakeup::<I::Wakeup>();
+    }
+}
+
 impl<I, C, MasterMode, SlaveMode>
     I2C<I, init_state::Enabled<PhantomData<C>>, MasterMode, SlaveMode>
 where
@@ -200,6 +369,117 @@ where
         interrupts.disable(&self.i2c);
     }
 
+    /// Enable the bus timeout
+    ///
+    /// Once enabled, a bus that is stuck, for example because a slave holds
+    /// SCL low, results in an [`Error::SclTimeout`] or [`Error::EventTimeout`]
+    /// instead of hanging forever.
+    ///
+    /// `timeout` is specified in units of 16 I2C function clock cycles. The
+    /// bus times out after `(timeout + 1) * 16` clock cycles.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `timeout` is larger than `0xfff`.
+    ///
+    /// [`Error::SclTimeout`]: enum.Error.html#variant.SclTimeout
+    /// [`Error::EventTimeout`]: enum.Error.html#variant.EventTimeout
+    pub fn enable_timeout(&mut self, timeout: u16) {
+        assert!(timeout <= 0xfff);
+
+        self.i2c.timeout.write(|w|
+            // Sound, as we've checked that the value fits into TO.
+            unsafe { w.to().bits(timeout) });
+        self.i2c.cfg.modify(|_, w| w.timeouten().enabled());
+    }
+
+    /// Disable the bus timeout
+    pub fn disable_timeout(&mut self) {
+        self.i2c.cfg.modify(|_, w| w.timeouten().disabled());
+    }
+
+    /// Release a bus that is blocked by a slave
+    ///
+    /// If a transfer is interrupted, for example by a reset of the
+    /// microcontroller, a slave might still hold SDA low, waiting for more
+    /// clock pulses. This method temporarily unassigns SCL and SDA from their
+    /// pins using the switch matrix, clocks out up to nine pulses on SCL,
+    /// until the slave releases SDA, then issues a stop condition. Finally, it
+    /// assigns SCL and SDA to their pins again.
+    ///
+    /// While unassigned, SCL and SDA are only ever driven low, or left
+    /// floating, like an I2C master would do. The bus is clocked at roughly
+    /// 100 kHz, using `delay`.
+    ///
+    /// Does nothing, if SCL or SDA are not assigned to a pin. The GPIO
+    /// peripheral must be enabled.
+    ///
+    /// Returns [`Error::BusRecoveryFailed`], if SDA is still held low
+    /// afterwards.
+    ///
+    /// [`Error::BusRecoveryFailed`]: enum.Error.html#variant.BusRecoveryFailed
+    pub fn recover_bus(
+        &mut self,
+        swm: &mut swm::Handle,
+        delay: &mut impl DelayUs<u32>,
+    ) -> Result<(), Error> {
+        let (scl, sda) = match I::bus_pins(swm) {
+            Some(pins) => pins,
+            None => return Ok(()),
+        };
+
+        // Make sure both pins are inputs, before they are released by the
+        // switch matrix. The pull-up resistors on the bus will keep them high,
+        // unless someone else is driving them low.
+        let mut scl = GpioPin::<_, direction::Dynamic>::new(
+            scl,
+            (Level::High, DynamicPinDirection::Input),
+        );
+        let mut sda = GpioPin::<_, direction::Dynamic>::new(
+            sda,
+            (Level::High, DynamicPinDirection::Input),
+        );
+
+        I::release_bus_pins(swm);
+
+        // Half of a clock period at 100 kHz
+        const HALF_PERIOD_US: u32 = 5;
+
+        for _ in 0..9 {
+            if let Level::High = sda.get_level() {
+                break;
+            }
+
+            scl.switch_to_output(Level::Low);
+            delay.delay_us(HALF_PERIOD_US);
+            scl.switch_to_input();
+            delay.delay_us(HALF_PERIOD_US);
+        }
+
+        // Stop condition: SDA goes high while SCL is high.
+        scl.switch_to_output(Level::Low);
+        delay.delay_us(HALF_PERIOD_US);
+        sda.switch_to_output(Level::Low);
+        delay.delay_us(HALF_PERIOD_US);
+        scl.switch_to_input();
+        delay.delay_us(HALF_PERIOD_US);
+        sda.switch_to_input();
+        delay.delay_us(HALF_PERIOD_US);
+
+        let released = match sda.get_level() {
+            Level::High => true,
+            Level::Low => false,
+        };
+
+        I::restore_bus_pins(swm, scl.inner(), sda.inner());
+
+        if !released {
+            return Err(Error::BusRecoveryFailed);
+        }
+
+        Ok(())
+    }
+
     /// Read and clear a detected error
     ///
     /// The `read` and `write` methods will return an error and clear it, if one
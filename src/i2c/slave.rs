@@ -3,8 +3,12 @@
 use core::{fmt, marker::PhantomData};
 
 use crate::{
+    dma::{self, transfer::state::Ready},
     init_state,
-    pac::i2c0::{SLVCTL, SLVDAT},
+    pac::{
+        dma0::channel::xfercfg::{DSTINC_A, SRCINC_A},
+        i2c0::{SLVCTL, SLVDAT},
+    },
     reg_proxy::{Reg, RegProxy},
 };
 
@@ -85,6 +89,144 @@ where
             slave_state.bits(),
         )))
     }
+
+    /// Transmit the provided buffer using DMA
+    ///
+    /// Call this after acknowledging an address match for a read by the
+    /// master (see [`AddressMatched::ack`]). The data is then sent without any
+    /// further software intervention, for as long as the master keeps reading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if the length of `buffer` is 0 or larger than 1024.
+    /// The error is returned together with the slave, buffer, and channel.
+    ///
+    /// [`AddressMatched::ack`]: struct.AddressMatched.html#method.ack
+    pub fn transmit_all(
+        self,
+        buffer: &'static [u8],
+        channel: dma::Channel<I::SlvChannel, init_state::Enabled>,
+    ) -> Result<
+        dma::Transfer<Ready, I::SlvChannel, &'static [u8], Self>,
+        (
+            dma::transfer::InvalidTransfer,
+            dma::Payload<I::SlvChannel, &'static [u8], Self>,
+        ),
+    > {
+        self.slvctl.modify(|_, w| w.slvdma().enabled());
+        dma::Transfer::new(channel, buffer, self).map_err(|(error, payload)| {
+            payload.dest.slvctl.modify(|_, w| w.slvdma().disabled());
+            (error, payload)
+        })
+    }
+
+    /// Receive into the provided buffer using DMA
+    ///
+    /// Call this after acknowledging an address match for a write by the
+    /// master (see [`AddressMatched::ack`]). Received data is then
+    /// acknowledged and stored without any further software intervention,
+    /// until the buffer is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if the length of `buffer` is 0 or larger than 1024.
+    /// The error is returned together with the slave, buffer, and channel.
+    ///
+    /// [`AddressMatched::ack`]: struct.AddressMatched.html#method.ack
+    pub fn receive_all(
+        self,
+        buffer: &'static mut [u8],
+        channel: dma::Channel<I::SlvChannel, init_state::Enabled>,
+    ) -> Result<
+        dma::Transfer<Ready, I::SlvChannel, Self, &'static mut [u8]>,
+        (
+            dma::transfer::InvalidTransfer,
+            dma::Payload<I::SlvChannel, Self, &'static mut [u8]>,
+        ),
+    > {
+        self.slvctl.modify(|_, w| w.slvdma().enabled());
+        dma::Transfer::new(channel, self, buffer).map_err(|(error, payload)| {
+            payload.source.slvctl.modify(|_, w| w.slvdma().disabled());
+            (error, payload)
+        })
+    }
+}
+
+impl<I, State, ModeState> crate::private::Sealed for Slave<I, State, ModeState> where
+    I: Instance
+{
+}
+
+impl<I, C> dma::Dest
+    for Slave<I, init_state::Enabled<PhantomData<C>>, init_state::Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
+
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    fn is_full(&self) -> bool {
+        false
+    }
+
+    fn increment(&self) -> DSTINC_A {
+        DSTINC_A::NO_INCREMENT
+    }
+
+    fn transfer_count(&self) -> Option<u16> {
+        None
+    }
+
+    fn end_addr(&mut self) -> *mut u8 {
+        // Sound, because we're dereferencing a register address that is always
+        // valid on the target hardware.
+        (unsafe { &(*I::REGISTERS).slvdat }) as *const _ as *mut u8
+    }
+
+    fn finish(&mut self) -> nb::Result<(), Self::Error> {
+        self.slvctl.modify(|_, w| w.slvdma().disabled());
+        Error::read::<I>()?;
+        Ok(())
+    }
+}
+
+impl<I, C> dma::Source
+    for Slave<I, init_state::Enabled<PhantomData<C>>, init_state::Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
+
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    fn is_empty(&self) -> bool {
+        false
+    }
+
+    fn increment(&self) -> SRCINC_A {
+        SRCINC_A::NO_INCREMENT
+    }
+
+    fn transfer_count(&self) -> Option<u16> {
+        None
+    }
+
+    fn end_addr(&self) -> *const u8 {
+        // Sound, because we're dereferencing a register address that is always
+        // valid on the target hardware.
+        (unsafe { &(*I::REGISTERS).slvdat }) as *const _ as *mut u8
+    }
+
+    fn finish(&mut self) -> nb::Result<(), Self::Error> {
+        self.slvctl.modify(|_, w| w.slvdma().disabled());
+        Error::read::<I>()?;
+        Ok(())
+    }
 }
 
 // Can't derive, because peripheral structs from the PAC don't implement
@@ -120,6 +262,22 @@ pub enum State<'r, I: Instance> {
     TxReady(TxReady<'r, I>),
 }
 
+/// Qualifies which addresses match slave address 0
+///
+/// Used by [`I2C::set_address_qualifier`].
+///
+/// [`I2C::set_address_qualifier`]: ../struct.I2C.html#method.set_address_qualifier
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AddressQualifier {
+    /// Ignore the address bits that are set in the mask
+    ///
+    /// `Mask(0)`, which is the default, requires an exact match.
+    Mask(u8),
+
+    /// Match all addresses from slave address 0 to the given address
+    Range(u8),
+}
+
 /// API for handling the "address matched" state
 ///
 /// You can gain access to this API through [`State`].
@@ -142,6 +300,20 @@ where
         Ok(address)
     }
 
+    /// Return the index of the slave address that has been matched
+    ///
+    /// This is 0 for the address passed to [`I2C::enable_slave_mode`], and 1
+    /// to 3 for the addresses set with [`I2C::set_slave_address`].
+    ///
+    /// [`I2C::enable_slave_mode`]: ../struct.I2C.html#method.enable_slave_mode
+    /// [`I2C::set_slave_address`]: ../struct.I2C.html#method.set_slave_address
+    pub fn index(&self) -> u8 {
+        // Sound, as we're only reading from the STAT register.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        i2c.stat.read().slvidx().bits()
+    }
+
     /// Acknowledge the matched address
     pub fn ack(self) -> Result<(), Error> {
         Error::read::<I>()?;
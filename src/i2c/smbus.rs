@@ -0,0 +1,332 @@
+//! SMBus operations on top of the I2C master
+//!
+//! Many devices, like battery fuel gauges and power management ICs, use
+//! SMBus, which defines a set of operations on top of I2C. [`SmBus`] wraps an
+//! I2C [`Master`] and provides these operations: Reading and writing bytes,
+//! words, and blocks, each selected by a command code.
+//!
+//! Optionally, packet error checking (PEC) can be enabled using
+//! [`SmBus::set_pec`]. A CRC-8 is then appended to each write, and expected at
+//! the end of each read.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{i2c::{self, smbus::SmBus}, Peripherals};
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut swm    = p.SWM.split();
+//! let mut syscon = p.SYSCON.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! let (i2c0_sda, _) = swm.fixed_functions.i2c0_sda.assign(
+//!     p.pins.pio0_11.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (i2c0_scl, _) = swm.fixed_functions.i2c0_scl.assign(
+//!     p.pins.pio0_10.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//!
+//! #[cfg(feature = "82x")]
+//! let clock = &(); // I2C is always powered by system clock on LPC82x
+//! #[cfg(feature = "845")]
+//! let clock = &syscon.iosc;
+//!
+//! let i2c = p.I2C0
+//!     .enable(
+//!         clock,
+//!         i2c0_scl,
+//!         i2c0_sda,
+//!         &mut syscon.handle,
+//!     )
+//!     .enable_master_mode(
+//!         &i2c::Clock::new_400khz(),
+//!     );
+//!
+//! let mut smbus = SmBus::new(i2c.master);
+//! smbus.set_pec(true);
+//!
+//! // Read the voltage from a smart battery
+//! let voltage_mv = smbus.read_word(0x0b, 0x09)
+//!     .expect("Failed to read voltage");
+//! ```
+//!
+//! [`SmBus`]: struct.SmBus.html
+//! [`Master`]: ../master/struct.Master.html
+//! [`SmBus::set_pec`]: struct.SmBus.html#method.set_pec
+
+use core::marker::PhantomData;
+
+use crate::init_state::Enabled;
+
+use super::{
+    master::{Master, Rw},
+    Instance,
+};
+
+/// The maximum length of an SMBus block
+pub const MAX_BLOCK_LEN: usize = 32;
+
+/// SMBus operations on top of an I2C master
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct SmBus<I: Instance, C> {
+    master: Master<I, Enabled<PhantomData<C>>, Enabled>,
+    pec: bool,
+}
+
+impl<I, C> SmBus<I, C>
+where
+    I: Instance,
+{
+    /// Create a new `SmBus`
+    ///
+    /// Packet error checking is disabled initially.
+    pub fn new(master: Master<I, Enabled<PhantomData<C>>, Enabled>) -> Self {
+        Self { master, pec: false }
+    }
+
+    /// Enable or disable packet error checking
+    pub fn set_pec(&mut self, enabled: bool) {
+        self.pec = enabled;
+    }
+
+    /// Write a byte to the register selected by `command`
+    pub fn write_byte(
+        &mut self,
+        address: u8,
+        command: u8,
+        value: u8,
+    ) -> Result<(), Error> {
+        self.write(address, command, &[], &[value])
+    }
+
+    /// Read a byte from the register selected by `command`
+    pub fn read_byte(&mut self, address: u8, command: u8) -> Result<u8, Error> {
+        let mut value = [0];
+        self.read(address, command, &mut value)?;
+        Ok(value[0])
+    }
+
+    /// Write a word to the register selected by `command`
+    ///
+    /// The word is sent least significant byte first.
+    pub fn write_word(
+        &mut self,
+        address: u8,
+        command: u8,
+        value: u16,
+    ) -> Result<(), Error> {
+        self.write(address, command, &[], &value.to_le_bytes())
+    }
+
+    /// Read a word from the register selected by `command`
+    ///
+    /// The word is received least significant byte first.
+    pub fn read_word(
+        &mut self,
+        address: u8,
+        command: u8,
+    ) -> Result<u16, Error> {
+        let mut value = [0; 2];
+        self.read(address, command, &mut value)?;
+        Ok(u16::from_le_bytes(value))
+    }
+
+    /// Write a block of data
+    ///
+    /// Sends the length of `data`, followed by `data` itself. Returns
+    /// [`Error::BlockTooLong`], if `data` is longer than [`MAX_BLOCK_LEN`].
+    ///
+    /// [`Error::BlockTooLong`]: enum.Error.html#variant.BlockTooLong
+    /// [`MAX_BLOCK_LEN`]: constant.MAX_BLOCK_LEN.html
+    pub fn block_write(
+        &mut self,
+        address: u8,
+        command: u8,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        if data.len() > MAX_BLOCK_LEN {
+            return Err(Error::BlockTooLong);
+        }
+
+        self.write(address, command, &[data.len() as u8], data)
+    }
+
+    /// Read a block of data
+    ///
+    /// Reads the length of the block sent by the slave, then the block itself
+    /// into `buffer`. Returns the length of the block.
+    ///
+    /// Returns [`Error::BlockTooLong`], if the slave announces a block that is
+    /// longer than `buffer` or [`MAX_BLOCK_LEN`].
+    ///
+    /// [`Error::BlockTooLong`]: enum.Error.html#variant.BlockTooLong
+    /// [`MAX_BLOCK_LEN`]: constant.MAX_BLOCK_LEN.html
+    pub fn block_read(
+        &mut self,
+        address: u8,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<usize, Error> {
+        self.start_read(address, command)?;
+
+        let mut len = [0];
+        self.master.read_bytes(&mut len, false)?;
+        let len = len[0];
+
+        if usize::from(len) > MAX_BLOCK_LEN || usize::from(len) > buffer.len() {
+            self.master.finish_read()?;
+            return Err(Error::BlockTooLong);
+        }
+
+        let data = &mut buffer[..usize::from(len)];
+        self.master.read_bytes(data, true)?;
+
+        let mut pec = Pec::new();
+        pec.update(&[address << 1, command, (address << 1) | 1, len]);
+        pec.update(data);
+        self.finish_read(pec, true)?;
+
+        Ok(usize::from(len))
+    }
+
+    /// Return the I2C master
+    pub fn free(self) -> Master<I, Enabled<PhantomData<C>>, Enabled> {
+        self.master
+    }
+
+    /// Write `command`, followed by `header` and `data`, and the PEC
+    fn write(
+        &mut self,
+        address: u8,
+        command: u8,
+        header: &[u8],
+        data: &[u8],
+    ) -> Result<(), Error> {
+        self.master.start_operation(address, Rw::Write)?;
+        self.master.write_bytes(&[command])?;
+        self.master.write_bytes(header)?;
+        self.master.write_bytes(data)?;
+
+        if self.pec {
+            let mut pec = Pec::new();
+            pec.update(&[address << 1, command]);
+            pec.update(header);
+            pec.update(data);
+            self.master.write_bytes(&[pec.value()])?;
+        }
+
+        self.master.finish_write()?;
+
+        Ok(())
+    }
+
+    /// Write `command`, then read into `buffer` after a repeated start
+    fn read(
+        &mut self,
+        address: u8,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        self.start_read(address, command)?;
+        self.master.read_bytes(buffer, false)?;
+
+        let mut pec = Pec::new();
+        pec.update(&[address << 1, command, (address << 1) | 1]);
+        pec.update(buffer);
+        self.finish_read(pec, !buffer.is_empty())
+    }
+
+    fn start_read(&mut self, address: u8, command: u8) -> Result<(), Error> {
+        self.master.start_operation(address, Rw::Write)?;
+        self.master.write_bytes(&[command])?;
+        self.master
+            .restart_operation(address, Rw::Read, Rw::Write)?;
+
+        Ok(())
+    }
+
+    /// Read and check the PEC, if enabled, then stop the read
+    ///
+    /// `continued` indicates, whether any bytes have been read before.
+    fn finish_read(&mut self, pec: Pec, continued: bool) -> Result<(), Error> {
+        if self.pec {
+            let mut received = [0];
+            self.master.read_bytes(&mut received, continued)?;
+            self.master.finish_read()?;
+
+            if received[0] != pec.value() {
+                return Err(Error::PecMismatch);
+            }
+
+            return Ok(());
+        }
+
+        self.master.finish_read()?;
+
+        Ok(())
+    }
+}
+
+/// Compute the SMBus PEC of `data`
+///
+/// The PEC is a CRC-8 with the polynomial `x^8 + x^2 + x + 1`. It covers all
+/// bytes of a transaction, including the address bytes.
+pub fn pec(data: &[u8]) -> u8 {
+    let mut pec = Pec::new();
+    pec.update(data);
+    pec.value()
+}
+
+struct Pec(u8);
+
+impl Pec {
+    fn new() -> Self {
+        Self(0)
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.0 ^= byte;
+            for _ in 0..8 {
+                self.0 = if self.0 & 0x80 != 0 {
+                    (self.0 << 1) ^ 0x07
+                } else {
+                    self.0 << 1
+                };
+            }
+        }
+    }
+
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+/// SMBus error
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The received PEC didn't match the received data
+    PecMismatch,
+
+    /// A block was longer than allowed, or than the provided buffer
+    BlockTooLong,
+
+    /// The I2C master reported an error
+    I2c(super::Error),
+}
+
+impl From<super::Error> for Error {
+    fn from(error: super::Error) -> Self {
+        Self::I2c(error)
+    }
+}
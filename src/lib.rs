@@ -102,31 +102,56 @@ pub extern crate cortex_m;
 #[cfg(feature = "rt-selected")]
 pub extern crate cortex_m_rt;
 pub extern crate embedded_hal;
-pub extern crate embedded_hal_alpha;
+#[cfg(feature = "embedded-hal-1")]
+pub extern crate embedded_hal_1;
+#[cfg(feature = "embedded-hal-1")]
+pub extern crate embedded_hal_async;
+#[cfg(feature = "embedded-hal-1")]
+pub extern crate embedded_hal_nb;
+#[cfg(feature = "embedded-hal-1")]
+pub extern crate embedded_io_async;
 pub extern crate embedded_time;
 pub extern crate nb;
 pub extern crate void;
 
+#[cfg(all(feature = "interrupt-handlers", not(feature = "rt-selected")))]
+compile_error!(
+    "The `interrupt-handlers` feature requires runtime support. Please also \
+    enable the `82x-rt` or `845-rt` feature."
+);
+
 #[macro_use]
 pub(crate) mod reg_proxy;
+#[cfg(feature = "embedded-hal-1")]
+pub(crate) mod waker;
 
 pub mod adc;
+pub mod bitbang;
+pub mod bootloader;
 pub mod clock;
 #[cfg(feature = "845")]
 pub mod ctimer;
 pub mod delay;
 pub mod dma;
+pub mod error;
+pub mod framing;
 pub mod gpio;
+#[cfg(feature = "interrupt-handlers")]
+pub mod handlers;
 pub mod i2c;
+pub mod logger;
 pub mod mrt;
 #[cfg(feature = "845")]
 pub mod pinint;
 pub mod pins;
 pub mod pmu;
+pub mod safety;
+pub mod selftest;
 pub mod sleep;
 pub mod spi;
 pub mod swm;
 pub mod syscon;
+pub mod units;
 pub mod usart;
 pub mod wkt;
 
@@ -148,6 +173,7 @@ pub mod prelude {
     pub use crate::clock::{Enabled as _, Frequency as _};
     pub use crate::embedded_hal::{digital::v2::*, prelude::*};
     pub use crate::sleep::Sleep as _;
+    pub use crate::units::{Rate as _, U32Ext as _};
 }
 
 #[cfg(feature = "82x")]
@@ -159,6 +185,7 @@ pub use self::adc::ADC;
 #[cfg(feature = "845")]
 pub use self::ctimer::CTIMER;
 pub use self::dma::DMA;
+pub use self::error::Error;
 pub use self::gpio::GPIO;
 pub use self::i2c::I2C;
 pub use self::mrt::MRT;
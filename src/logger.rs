@@ -0,0 +1,261 @@
+//! Non-blocking log sink that drains over USART via DMA
+//!
+//! Writing log messages over USART using blocking writes takes a long time
+//! (almost 1 ms per 10 characters at 115200 baud), which is not acceptable in
+//! time-critical code. [`Logger`] instead appends log messages to a ring
+//! buffer and drains that buffer in the background, using DMA.
+//!
+//! [`Logger`] doesn't drain the buffer on its own. [`Logger::poll`] needs to
+//! be called regularly, for example from the main loop or from the DMA
+//! interrupt, to start the next transfer once the previous one has finished.
+//! Writing to the logger also calls [`Logger::poll`].
+//!
+//! If the buffer is full, log messages are truncated. The number of bytes that
+//! were dropped that way can be queried using [`Logger::dropped`].
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use core::fmt::Write as _;
+//!
+//! use lpc8xx_hal::{
+//!     cortex_m,
+//!     logger::Logger,
+//!     usart,
+//!     Peripherals,
+//! };
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut swm    = p.SWM.split();
+//! let dma        = p.DMA.enable(&mut syscon.handle);
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! #[cfg(feature = "82x")]
+//! let clock_config = {
+//!     syscon.uartfrg.set_clkdiv(6);
+//!     syscon.uartfrg.set_frgmult(22);
+//!     syscon.uartfrg.set_frgdiv(0xff);
+//!     usart::Clock::new(&syscon.uartfrg, 0, 16)
+//! };
+//! #[cfg(feature = "845")]
+//! let clock_config = usart::Clock::new_with_baudrate(115200);
+//!
+//! let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+//!     p.pins.pio0_0.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+//!     p.pins.pio0_4.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//!
+//! let serial = p.USART0.enable_async(
+//!     &clock_config,
+//!     &mut syscon.handle,
+//!     u0_rxd,
+//!     u0_txd,
+//!     usart::Settings::default(),
+//! );
+//!
+//! let buffer = cortex_m::singleton!(: [u8; 256] = [0; 256]).unwrap();
+//! let mut logger = Logger::new(buffer, serial.tx, dma.channels.channel1);
+//!
+//! write!(logger, "Hello, {}!\n", "world");
+//!
+//! loop {
+//!     logger.poll();
+//! }
+//! ```
+//!
+//! [`Logger`]: struct.Logger.html
+//! [`Logger::poll`]: struct.Logger.html#method.poll
+//! [`Logger::dropped`]: struct.Logger.html#method.dropped
+
+use core::{cmp, fmt};
+
+use crate::{
+    dma::{self, transfer::state::Started},
+    init_state,
+    usart::{self, state::Enabled},
+};
+
+/// Non-blocking log sink
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct Logger<I, Mode, Throttle>
+where
+    I: usart::Instance,
+{
+    buffer: &'static mut [u8],
+
+    /// Index of the oldest byte in the buffer
+    tail: usize,
+
+    /// Number of bytes in the buffer, including those currently being sent
+    len: usize,
+
+    /// Number of bytes currently being sent
+    in_flight: usize,
+
+    dropped: usize,
+    drain: Option<Drain<I, Mode, Throttle>>,
+}
+
+impl<I, Mode, Throttle> Logger<I, Mode, Throttle>
+where
+    I: usart::Instance,
+{
+    /// Create a new `Logger`
+    ///
+    /// `channel` must be the DMA channel that belongs to the USART instance.
+    /// The type system enforces this.
+    pub fn new(
+        buffer: &'static mut [u8],
+        tx: usart::Tx<I, Enabled<u8, Mode>, Throttle>,
+        channel: dma::Channel<I::TxChannel, init_state::Enabled>,
+    ) -> Self {
+        Self {
+            buffer,
+            tail: 0,
+            len: 0,
+            in_flight: 0,
+            dropped: 0,
+            drain: Some(Drain::Idle(tx, channel)),
+        }
+    }
+
+    /// Append bytes to the buffer
+    ///
+    /// Bytes that don't fit into the buffer are dropped. Returns the number of
+    /// bytes that were appended.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> usize {
+        let capacity = self.buffer.len();
+        let n = cmp::min(bytes.len(), capacity - self.len);
+
+        for &byte in &bytes[..n] {
+            let head = (self.tail + self.len) % capacity;
+            self.buffer[head] = byte;
+            self.len += 1;
+        }
+
+        self.dropped += bytes.len() - n;
+        self.poll();
+
+        n
+    }
+
+    /// Drain the buffer
+    ///
+    /// Checks whether the current DMA transfer has finished, and if so, starts
+    /// the next one, if there's data left in the buffer. Never blocks.
+    pub fn poll(&mut self) {
+        let drain = match self.drain.take() {
+            Some(drain) => drain,
+            // Can only happen, if a previous call panicked.
+            None => return,
+        };
+
+        let (tx, channel) = match drain {
+            Drain::Busy(transfer) if transfer.is_active() => {
+                self.drain = Some(Drain::Busy(transfer));
+                return;
+            }
+            Drain::Busy(transfer) => {
+                let payload = match transfer.wait() {
+                    Ok(payload) => payload,
+                    Err((dma::transfer::Error::Source(void), _)) => {
+                        void::unreachable(void)
+                    }
+                    Err((dma::transfer::Error::Dest(void), _)) => {
+                        void::unreachable(void)
+                    }
+                };
+
+                self.tail = (self.tail + self.in_flight) % self.buffer.len();
+                self.len -= self.in_flight;
+                self.in_flight = 0;
+
+                (payload.dest, payload.channel)
+            }
+            Drain::Idle(tx, channel) => (tx, channel),
+        };
+
+        if self.len == 0 {
+            self.drain = Some(Drain::Idle(tx, channel));
+            return;
+        }
+
+        // Only send the contiguous part at the end of the buffer. Anything
+        // that wrapped around will be sent by the next transfer. DMA transfers
+        // are limited to 1024 bytes.
+        let contiguous = cmp::min(self.len, self.buffer.len() - self.tail);
+        let n = cmp::min(contiguous, 1024);
+
+        // Sound, as the region is within the buffer, and it is not written to
+        // until the transfer has finished, as it's still counted in `len`.
+        let source = unsafe {
+            dma::Buffer::new(self.buffer.as_mut_ptr().add(self.tail), n)
+        };
+
+        // Can't fail, as `n` is between 1 and 1024. Just go back to idle, if it
+        // does anyway.
+        self.drain = match dma::Transfer::new(channel, source, tx) {
+            Ok(transfer) => {
+                self.in_flight = n;
+                Some(Drain::Busy(transfer.start()))
+            }
+            Err((_, payload)) => {
+                Some(Drain::Idle(payload.dest, payload.channel))
+            }
+        };
+    }
+
+    /// Indicates whether all buffered data has been handed to the USART
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes that were dropped, as the buffer was full
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+impl<I, Mode, Throttle> fmt::Write for Logger<I, Mode, Throttle>
+where
+    I: usart::Instance,
+{
+    /// Append a string to the buffer
+    ///
+    /// Never fails. If the buffer is full, the string is truncated.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+enum Drain<I, Mode, Throttle>
+where
+    I: usart::Instance,
+{
+    Idle(
+        usart::Tx<I, Enabled<u8, Mode>, Throttle>,
+        dma::Channel<I::TxChannel, init_state::Enabled>,
+    ),
+    Busy(
+        dma::Transfer<
+            Started,
+            I::TxChannel,
+            dma::Buffer,
+            usart::Tx<I, Enabled<u8, Mode>, Throttle>,
+        >,
+    ),
+}
@@ -1,9 +1,6 @@
 use crate::reg_proxy::{Reg, RegProxy};
 
 use embedded_hal::timer::{CountDown, Periodic};
-use embedded_hal_alpha::timer::{
-    CountDown as CountDownAlpha, Periodic as PeriodicAlpha,
-};
 use embedded_time::{clock, fraction::Fraction, Instant};
 use void::Void;
 
@@ -92,34 +89,8 @@ where
     }
 }
 
-impl<T> CountDownAlpha for Channel<T>
-where
-    T: Trait,
-{
-    type Error = Void;
-
-    /// The timer operates in clock ticks from the system clock, that means it
-    /// runs at 12_000_000 ticks per second if you haven't changed it.
-    ///
-    /// It can also only use values smaller than 0x7FFFFFFF.
-    type Time = Ticks;
-
-    fn try_start<Time>(&mut self, count: Time) -> Result<(), Self::Error>
-    where
-        Time: Into<Self::Time>,
-    {
-        Ok(self.start(count.into()))
-    }
-
-    fn try_wait(&mut self) -> nb::Result<(), Self::Error> {
-        self.wait()
-    }
-}
-
 impl<T> Periodic for Channel<T> where T: Trait {}
 
-impl<T> PeriodicAlpha for Channel<T> where T: Trait {}
-
 impl<T> embedded_time::Clock for Channel<T>
 where
     T: Trait,
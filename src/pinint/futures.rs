@@ -0,0 +1,286 @@
+//! Async API for pin interrupts
+//!
+//! Implements [`embedded_hal_async::digital::Wait`] for edge-sensitive
+//! [`Interrupt`]s, which allows waiting for an edge or a level on the selected
+//! pin from an async executor, instead of busy-waiting for the pin to change.
+//!
+//! This module is only available, if the `embedded-hal-1` feature is enabled.
+//!
+//! The futures are woken from the pin interrupt. For this to work, you need to
+//! call [`on_interrupt`] from the interrupt handler of the pin interrupt, and
+//! unmask the interrupt in the NVIC. The futures configure and enable the pin
+//! interrupt as required, and [`on_interrupt`] disables it again, so you
+//! shouldn't use the same pin interrupt for anything else, while using this
+//! API.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     embedded_hal_async::digital::Wait as _,
+//!     init_state::Enabled,
+//!     pinint::{self, PININT0},
+//!     pins::PIO0_4,
+//! };
+//!
+//! async fn wait_for_button(
+//!     interrupt: &mut pinint::Interrupt<PININT0, PIO0_4, Enabled>,
+//! ) {
+//!     // The error type is `Infallible`.
+//!     interrupt.wait_for_falling_edge().await.unwrap();
+//! }
+//!
+//! // Call this from the PIN_INT0 interrupt handler.
+//! pinint::futures::on_interrupt::<PININT0>();
+//! ```
+//!
+//! [`embedded_hal_async::digital::Wait`]: ../struct.Interrupt.html#impl-Wait
+//! [`Interrupt`]: ../struct.Interrupt.html
+//! [`on_interrupt`]: fn.on_interrupt.html
+
+use core::{
+    convert::Infallible,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{gpio::Level, init_state::Enabled, pac, pins};
+
+use super::{
+    interrupt::{self, EdgeSensitive, Interrupt},
+    traits::Trait,
+};
+
+/// Wake the future that is waiting for pin interrupt `I`
+///
+/// Must be called from the interrupt handler of pin interrupt `I`. Disables
+/// the interrupt that was enabled by the futures, and wakes the future that
+/// was waiting for it.
+pub fn on_interrupt<I>()
+where
+    I: Trait,
+{
+    // Sound, as we're only doing atomic writes to a single bit that no other
+    // `Interrupt` instance is writing to.
+    let pint = unsafe { &*pac::PINT::ptr() };
+
+    pint.cienr.write(|w|
+        // Sound, as long as `Trait` is only implemented for valid interrupts.
+        unsafe { w.cenrl().bits(I::MASK) });
+    pint.cienf.write(|w|
+        // Sound, as long as `Trait` is only implemented for valid interrupts.
+        unsafe { w.cenaf().bits(I::MASK) });
+
+    I::waker().wake();
+}
+
+impl<I, P> embedded_hal_1::digital::ErrorType
+    for Interrupt<I, P, Enabled, EdgeSensitive>
+where
+    I: Trait,
+    P: pins::Trait,
+{
+    type Error = Infallible;
+}
+
+impl<I, P> embedded_hal_async::digital::Wait
+    for Interrupt<I, P, Enabled, EdgeSensitive>
+where
+    I: Trait + Unpin,
+    P: pins::Trait,
+{
+    /// Wait for the selected pin to be HIGH
+    ///
+    /// Resolves immediately, if the pin is already HIGH. Please refer to the
+    /// [module documentation] for the requirements.
+    ///
+    /// [module documentation]: futures/index.html
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        LevelFuture::<I>::new(Level::High).await;
+        Ok(())
+    }
+
+    /// Wait for the selected pin to be LOW
+    ///
+    /// Resolves immediately, if the pin is already LOW. Please refer to the
+    /// [module documentation] for the requirements.
+    ///
+    /// [module documentation]: futures/index.html
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        LevelFuture::<I>::new(Level::Low).await;
+        Ok(())
+    }
+
+    /// Wait for a rising edge on the selected pin
+    ///
+    /// Only edges that occur after the future has been polled for the first
+    /// time are taken into account. Please refer to the [module documentation]
+    /// for the requirements.
+    ///
+    /// [module documentation]: futures/index.html
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        EdgeFuture::<I>::new(true, false).await;
+        Ok(())
+    }
+
+    /// Wait for a falling edge on the selected pin
+    ///
+    /// Only edges that occur after the future has been polled for the first
+    /// time are taken into account. Please refer to the [module documentation]
+    /// for the requirements.
+    ///
+    /// [module documentation]: futures/index.html
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        EdgeFuture::<I>::new(false, true).await;
+        Ok(())
+    }
+
+    /// Wait for any edge on the selected pin
+    ///
+    /// Only edges that occur after the future has been polled for the first
+    /// time are taken into account. Please refer to the [module documentation]
+    /// for the requirements.
+    ///
+    /// [module documentation]: futures/index.html
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        EdgeFuture::<I>::new(true, true).await;
+        Ok(())
+    }
+}
+
+// The futures below access the registers through the helpers in the
+// `interrupt` module. They're only created by the `Wait` methods, which hold a
+// mutable reference to the `Interrupt`, so no other code can access the
+// interrupt while they exist.
+
+struct EdgeFuture<I>
+where
+    I: Trait,
+{
+    rising: bool,
+    falling: bool,
+    started: bool,
+    _interrupt: PhantomData<I>,
+}
+
+impl<I> EdgeFuture<I>
+where
+    I: Trait,
+{
+    fn new(rising: bool, falling: bool) -> Self {
+        Self {
+            rising,
+            falling,
+            started: false,
+            _interrupt: PhantomData,
+        }
+    }
+}
+
+impl<I> Future for EdgeFuture<I>
+where
+    I: Trait + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Register first, so an interrupt right after the checks below can't
+        // get lost.
+        I::waker().register(cx.waker());
+
+        let this = self.get_mut();
+
+        if !this.started {
+            // Ignore any edges that were detected before we started waiting.
+            interrupt::clear_rising_edge_flag::<I>();
+            interrupt::clear_falling_edge_flag::<I>();
+            this.started = true;
+        }
+
+        // Enable the interrupts before checking the flags, so an edge that is
+        // detected in between fires the interrupt.
+        interrupt::set_ienr::<I>(this.rising);
+        interrupt::set_ienf::<I>(this.falling);
+
+        let rising = this.rising && interrupt::clear_rising_edge_flag::<I>();
+        let falling = this.falling && interrupt::clear_falling_edge_flag::<I>();
+
+        if rising || falling {
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<I> Drop for EdgeFuture<I>
+where
+    I: Trait,
+{
+    fn drop(&mut self) {
+        // Leave both edges disabled, whether we completed or were cancelled.
+        interrupt::set_ienr::<I>(false);
+        interrupt::set_ienf::<I>(false);
+    }
+}
+
+struct LevelFuture<I>
+where
+    I: Trait,
+{
+    level: Level,
+    _interrupt: PhantomData<I>,
+}
+
+impl<I> LevelFuture<I>
+where
+    I: Trait,
+{
+    fn new(level: Level) -> Self {
+        Self {
+            level,
+            _interrupt: PhantomData,
+        }
+    }
+}
+
+impl<I> Future for LevelFuture<I>
+where
+    I: Trait,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Register first, so an interrupt right after the checks below can't
+        // get lost.
+        I::waker().register(cx.waker());
+
+        // Temporarily switch to level-sensitive mode. In this mode, the
+        // interrupt is pending, while the pin is at the active level.
+        interrupt::set_ienr::<I>(false);
+        interrupt::set_level_mode::<I>(true);
+        interrupt::set_active_level::<I>(self.level);
+        interrupt::set_ienr::<I>(true);
+
+        if interrupt::is_pending::<I>() {
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<I> Drop for LevelFuture<I>
+where
+    I: Trait,
+{
+    fn drop(&mut self) {
+        // Return to edge-sensitive mode, with both edges disabled, whether we
+        // completed or were cancelled.
+        interrupt::set_ienr::<I>(false);
+        interrupt::set_ienf::<I>(false);
+        interrupt::set_level_mode::<I>(false);
+    }
+}
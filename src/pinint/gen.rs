@@ -1,3 +1,6 @@
+#[cfg(feature = "embedded-hal-1")]
+use crate::waker::WakerSlot;
+
 use super::{interrupt::Interrupt, traits::Trait};
 
 macro_rules! interrupts {
@@ -26,6 +29,12 @@ macro_rules! interrupts {
             impl Trait for $struct {
                 const INDEX: usize = $index;
                 const MASK: u8 = 0x1 << $index;
+
+                #[cfg(feature = "embedded-hal-1")]
+                fn waker() -> &'static WakerSlot {
+                    static WAKER: WakerSlot = WakerSlot::new();
+                    &WAKER
+                }
             }
         )*
     };
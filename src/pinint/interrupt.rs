@@ -2,13 +2,20 @@ use core::marker::PhantomData;
 
 use super::traits::Trait;
 
-use crate::{init_state::Enabled, pac, pins, syscon};
+use crate::{gpio::Level, init_state::Enabled, pac, pins, syscon};
 
 /// API for controlling pin interrupts
-pub struct Interrupt<I, P, State> {
+///
+/// An interrupt is either edge-sensitive or level-sensitive, which is tracked
+/// by the `Mode` type parameter. See [`EdgeSensitive`] and [`LevelSensitive`].
+///
+/// [`EdgeSensitive`]: struct.EdgeSensitive.html
+/// [`LevelSensitive`]: struct.LevelSensitive.html
+pub struct Interrupt<I, P, State, Mode = EdgeSensitive> {
     interrupt: PhantomData<I>,
     _pin: PhantomData<P>,
     state: PhantomData<State>,
+    _mode: PhantomData<Mode>,
 }
 
 impl<I, P, State> Interrupt<I, P, State> {
@@ -17,11 +24,12 @@ impl<I, P, State> Interrupt<I, P, State> {
             interrupt: PhantomData,
             _pin: PhantomData,
             state: PhantomData,
+            _mode: PhantomData,
         }
     }
 }
 
-impl<I, OldPin, State> Interrupt<I, OldPin, State>
+impl<I, OldPin, State, Mode> Interrupt<I, OldPin, State, Mode>
 where
     I: Trait,
 {
@@ -46,7 +54,7 @@ where
         self,
         interrupt_pin: &P,
         _: &mut syscon::Handle,
-    ) -> Interrupt<I, P, State>
+    ) -> Interrupt<I, P, State, Mode>
     where
         P: pins::Trait,
     {
@@ -65,11 +73,12 @@ where
             interrupt: self.interrupt,
             _pin: PhantomData,
             state: self.state,
+            _mode: self._mode,
         }
     }
 }
 
-impl<I, P> Interrupt<I, P, Enabled>
+impl<I, P> Interrupt<I, P, Enabled, EdgeSensitive>
 where
     I: Trait,
     P: pins::Trait,
@@ -83,42 +92,17 @@ where
     /// Otherwise, the interrupt will be fired again immediately, after the
     /// interrupt handler exits.
     pub fn clear_rising_edge_flag(&mut self) -> bool {
-        // This is sound, as we're only doing an atomic read and write, both to // a single bit that no other `Interrupt` instance is writing to.
-        let pint = unsafe { &*pac::PINT::ptr() };
-
-        let is_set = pint.rise.read().rdet().bits() & I::MASK != 0;
-
-        // Clear flag
-        pint.rise.write(|w|
-            // Sound, as long as `Trait` is only implemented for valid
-            // interrupts.
-            unsafe { w.rdet().bits(I::MASK) });
-
-        is_set
+        clear_rising_edge_flag::<I>()
     }
 
     /// Fire interrupt on rising edge
     pub fn enable_rising_edge(&mut self) {
-        // This is sound, as we're only doing an atomic write to a single bit
-        // that no other `Interrupt` instance is writing to.
-        let pint = unsafe { &*pac::PINT::ptr() };
-
-        pint.sienr.write(|w|
-            // Sound, as long as `Trait` is only implemented for valid
-            // interrupts.
-            unsafe { w.setenrl().bits(I::MASK) });
+        set_ienr::<I>(true);
     }
 
     /// Don't fire interrupt on rising edge
     pub fn disable_rising_edge(&mut self) {
-        // This is sound, as we're only doing an atomic write to a single bit
-        // that no other `Interrupt` instance is writing to.
-        let pint = unsafe { &*pac::PINT::ptr() };
-
-        pint.cienr.write(|w|
-            // Sound, as long as `Trait` is only implemented for valid
-            // interrupts.
-            unsafe { w.cenrl().bits(I::MASK) });
+        set_ienr::<I>(false);
     }
 
     /// Returns whether a falling edge has been detected and clears the flag
@@ -130,41 +114,210 @@ where
     /// Otherwise, the interrupt will be fired again immediately, after the
     /// interrupt handler exits.
     pub fn clear_falling_edge_flag(&mut self) -> bool {
-        // This is sound, as we're only doing an atomic read and write, both to // a single bit that no other `Interrupt` instance is writing to.
-        let pint = unsafe { &*pac::PINT::ptr() };
+        clear_falling_edge_flag::<I>()
+    }
 
-        let is_set = pint.fall.read().fdet().bits() & I::MASK != 0;
+    /// Fire interrupt on falling edge
+    pub fn enable_falling_edge(&mut self) {
+        set_ienf::<I>(true);
+    }
 
-        // Clear flag
-        pint.fall.write(|w|
-            // Sound, as long as `Trait` is only implemented for valid
-            // interrupts.
-            unsafe { w.fdet().bits(I::MASK) });
+    /// Don't fire interrupt on falling edge
+    pub fn disable_falling_edge(&mut self) {
+        set_ienf::<I>(false);
+    }
+
+    /// Switch this interrupt to level-sensitive mode
+    ///
+    /// Disables both edge interrupts and configures the interrupt to fire
+    /// while the pin is at the given level. The interrupt stays disabled, until
+    /// [`Interrupt::enable`] is called on the returned instance.
+    ///
+    /// [`Interrupt::enable`]: #method.enable
+    pub fn into_level_sensitive(
+        self,
+        level: Level,
+    ) -> Interrupt<I, P, Enabled, LevelSensitive> {
+        set_ienr::<I>(false);
+        set_level_mode::<I>(true);
+        set_active_level::<I>(level);
 
-        is_set
+        Interrupt {
+            interrupt: self.interrupt,
+            _pin: self._pin,
+            state: self.state,
+            _mode: PhantomData,
+        }
     }
+}
 
-    /// Fire interrupt on falling edge
-    pub fn enable_falling_edge(&mut self) {
-        // This is sound, as we're only doing an atomic write to a single bit
-        // that no other `Interrupt` instance is writing to.
-        let pint = unsafe { &*pac::PINT::ptr() };
+impl<I, P> Interrupt<I, P, Enabled, LevelSensitive>
+where
+    I: Trait,
+    P: pins::Trait,
+{
+    /// Fire interrupt while the pin is at the active level
+    pub fn enable(&mut self) {
+        // In level-sensitive mode, IENR enables the interrupt.
+        set_ienr::<I>(true);
+    }
 
-        pint.sienf.write(|w|
+    /// Don't fire interrupt while the pin is at the active level
+    pub fn disable(&mut self) {
+        set_ienr::<I>(false);
+    }
+
+    /// Change the level at which the interrupt fires
+    pub fn set_level(&mut self, level: Level) {
+        set_active_level::<I>(level);
+    }
+
+    /// Switch this interrupt back to edge-sensitive mode
+    ///
+    /// Both edge interrupts are disabled on the returned instance.
+    pub fn into_edge_sensitive(
+        self,
+    ) -> Interrupt<I, P, Enabled, EdgeSensitive> {
+        set_ienr::<I>(false);
+        set_ienf::<I>(false);
+        set_level_mode::<I>(false);
+
+        Interrupt {
+            interrupt: self.interrupt,
+            _pin: self._pin,
+            state: self.state,
+            _mode: PhantomData,
+        }
+    }
+}
+
+impl<I, P, Mode> Interrupt<I, P, Enabled, Mode>
+where
+    I: Trait,
+    P: pins::Trait,
+{
+    /// Indicates whether this interrupt is currently requested
+    ///
+    /// In edge-sensitive mode, this is the case, if an enabled edge has been
+    /// detected, and the corresponding flag hasn't been cleared yet. In
+    /// level-sensitive mode, this is the case, while the interrupt is enabled
+    /// and the pin is at the active level.
+    pub fn is_pending(&self) -> bool {
+        is_pending::<I>()
+    }
+}
+
+/// Marks an [`Interrupt`] as edge-sensitive
+///
+/// This is the default mode of an [`Interrupt`].
+///
+/// [`Interrupt`]: struct.Interrupt.html
+pub struct EdgeSensitive;
+
+/// Marks an [`Interrupt`] as level-sensitive
+///
+/// [`Interrupt`]: struct.Interrupt.html
+pub struct LevelSensitive;
+
+// The following functions access the registers of pin interrupt `I` directly.
+// They are shared by the different modes of `Interrupt` and by the futures,
+// which need to switch modes while holding a reference to an `Interrupt`.
+//
+// All of them are sound, as they only do atomic writes to a single bit that
+// no other `Interrupt` instance is writing to, reads, or a read-modify-write
+// within a critical section.
+
+pub(super) fn clear_rising_edge_flag<I: Trait>() -> bool {
+    let pint = unsafe { &*pac::PINT::ptr() };
+
+    let is_set = pint.rise.read().rdet().bits() & I::MASK != 0;
+
+    // Clear flag
+    pint.rise.write(|w|
+        // Sound, as long as `Trait` is only implemented for valid
+        // interrupts.
+        unsafe { w.rdet().bits(I::MASK) });
+
+    is_set
+}
+
+pub(super) fn clear_falling_edge_flag<I: Trait>() -> bool {
+    let pint = unsafe { &*pac::PINT::ptr() };
+
+    let is_set = pint.fall.read().fdet().bits() & I::MASK != 0;
+
+    // Clear flag
+    pint.fall.write(|w|
+        // Sound, as long as `Trait` is only implemented for valid
+        // interrupts.
+        unsafe { w.fdet().bits(I::MASK) });
+
+    is_set
+}
+
+/// Enables the rising edge interrupt, or the level interrupt
+pub(super) fn set_ienr<I: Trait>(enabled: bool) {
+    let pint = unsafe { &*pac::PINT::ptr() };
+
+    if enabled {
+        pint.sienr.write(|w|
             // Sound, as long as `Trait` is only implemented for valid
             // interrupts.
-            unsafe { w.setenaf().bits(I::MASK) });
+            unsafe { w.setenrl().bits(I::MASK) });
+    } else {
+        pint.cienr.write(|w|
+            // Sound, as long as `Trait` is only implemented for valid
+            // interrupts.
+            unsafe { w.cenrl().bits(I::MASK) });
     }
+}
 
-    /// Don't fire interrupt on falling edge
-    pub fn disable_falling_edge(&mut self) {
-        // This is sound, as we're only doing an atomic write to a single bit
-        // that no other `Interrupt` instance is writing to.
-        let pint = unsafe { &*pac::PINT::ptr() };
+/// Enables the falling edge interrupt, or selects the active level
+pub(super) fn set_ienf<I: Trait>(enabled: bool) {
+    let pint = unsafe { &*pac::PINT::ptr() };
 
+    if enabled {
+        pint.sienf.write(|w|
+            // Sound, as long as `Trait` is only implemented for valid
+            // interrupts.
+            unsafe { w.setenaf().bits(I::MASK) });
+    } else {
         pint.cienf.write(|w|
             // Sound, as long as `Trait` is only implemented for valid
             // interrupts.
             unsafe { w.cenaf().bits(I::MASK) });
     }
 }
+
+/// In level-sensitive mode, IENF selects the active level
+pub(super) fn set_active_level<I: Trait>(level: Level) {
+    match level {
+        Level::High => set_ienf::<I>(true),
+        Level::Low => set_ienf::<I>(false),
+    }
+}
+
+pub(super) fn set_level_mode<I: Trait>(level_mode: bool) {
+    let pint = unsafe { &*pac::PINT::ptr() };
+
+    // ISEL is shared between all interrupts.
+    cortex_m::interrupt::free(|_| {
+        pint.isel.modify(|r, w| {
+            let pmode = if level_mode {
+                r.pmode().bits() | I::MASK
+            } else {
+                r.pmode().bits() & !I::MASK
+            };
+
+            // Sound, as long as `Trait` is only implemented for valid
+            // interrupts.
+            unsafe { w.pmode().bits(pmode) }
+        });
+    });
+}
+
+pub(super) fn is_pending<I: Trait>() -> bool {
+    let pint = unsafe { &*pac::PINT::ptr() };
+
+    pint.ist.read().pstat().bits() & I::MASK != 0
+}
@@ -1,13 +1,27 @@
 //! Interface to the pin interrupts/pattern matching engine
 //!
-//! This API is currently limited. It exposes a subset of the pin interrupts
-//! functionality, and none of the pattern matching functionality.
+//! Exposes the edge- and level-sensitive pin interrupts through [`Interrupt`],
+//! and the pattern match engine through [`PatternMatch`]. The [`futures`]
+//! module implements the embedded-hal-async `Wait` trait for waiting on pin
+//! edges and levels.
+//!
+//! [`Interrupt`]: struct.Interrupt.html
+//! [`PatternMatch`]: struct.PatternMatch.html
+//! [`futures`]: futures/index.html
+
+#[cfg(feature = "embedded-hal-1")]
+pub mod futures;
 
 mod gen;
 mod interrupt;
+mod pattern;
 mod peripheral;
 mod traits;
 
 pub use self::{
-    gen::*, interrupt::Interrupt, peripheral::PININT, traits::Trait,
+    gen::*,
+    interrupt::{EdgeSensitive, Interrupt, LevelSensitive},
+    pattern::{Condition, Input, PatternMatch, Slice},
+    peripheral::PININT,
+    traits::Trait,
 };
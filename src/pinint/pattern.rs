@@ -0,0 +1,274 @@
+use core::marker::PhantomData;
+
+use crate::{init_state::Enabled, pac};
+
+/// API for controlling the pattern match engine
+///
+/// The pattern match engine combines the inputs of the pin interrupts into
+/// boolean expressions. It consists of 8 bit slices, each of which evaluates a
+/// condition on one of the inputs. Consecutive slices are ANDed together into
+/// product terms, and a product term ends at a slice that is marked as an
+/// endpoint. The product terms are ORed together into the final result.
+///
+/// While the pattern match engine is enabled, a product term that matches
+/// fires the pin interrupt with the same number as its endpoint slice, instead
+/// of the regular pin interrupts. The result can also be output on the RXEV
+/// signal, to wake up the core from a `WFE`.
+///
+/// The inputs are the pins selected for the pin interrupts, using
+/// [`Interrupt::select`].
+///
+/// [`Interrupt::select`]: struct.Interrupt.html#method.select
+pub struct PatternMatch<State> {
+    _state: PhantomData<State>,
+}
+
+impl<State> PatternMatch<State> {
+    pub(super) fn new() -> Self {
+        Self {
+            _state: PhantomData,
+        }
+    }
+}
+
+impl PatternMatch<Enabled> {
+    /// Configure and enable the pattern match engine
+    ///
+    /// `slices` configures the 8 bit slices, starting with slice 0. Slice 7
+    /// always ends a product term, regardless of its configuration. If `rxev`
+    /// is `true`, the result is also output on the RXEV signal.
+    pub fn enable(&mut self, slices: &[Slice; 8], rxev: bool) {
+        // This is sound, as this `PatternMatch` instance is the only one
+        // accessing these registers.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.pmsrc.write(|w| {
+            w.src0().bits(slices[0].input.bits());
+            w.src1().bits(slices[1].input.bits());
+            w.src2().bits(slices[2].input.bits());
+            w.src3().bits(slices[3].input.bits());
+            w.src4().bits(slices[4].input.bits());
+            w.src5().bits(slices[5].input.bits());
+            w.src6().bits(slices[6].input.bits());
+            w.src7().bits(slices[7].input.bits())
+        });
+        pint.pmcfg.write(|w| {
+            // Slice 7 doesn't have a PROD_ENDPTS bit, as it's always an
+            // endpoint.
+            w.prod_endpts0().bit(slices[0].endpoint);
+            w.prod_endpts1().bit(slices[1].endpoint);
+            w.prod_endpts2().bit(slices[2].endpoint);
+            w.prod_endpts3().bit(slices[3].endpoint);
+            w.prod_endpts4().bit(slices[4].endpoint);
+            w.prod_endpts5().bit(slices[5].endpoint);
+            w.prod_endpts6().bit(slices[6].endpoint);
+
+            w.cfg0().bits(slices[0].condition.bits());
+            w.cfg1().bits(slices[1].condition.bits());
+            w.cfg2().bits(slices[2].condition.bits());
+            w.cfg3().bits(slices[3].condition.bits());
+            w.cfg4().bits(slices[4].condition.bits());
+            w.cfg5().bits(slices[5].condition.bits());
+            w.cfg6().bits(slices[6].condition.bits());
+            w.cfg7().bits(slices[7].condition.bits())
+        });
+
+        pint.pmctrl.write(|w| {
+            w.sel_pmatch().set_bit();
+            w.ena_rxev().bit(rxev)
+        });
+    }
+
+    /// Disable the pattern match engine
+    ///
+    /// The pin interrupts work as regular pin interrupts again.
+    pub fn disable(&mut self) {
+        // This is sound, as this `PatternMatch` instance is the only one
+        // accessing this register.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.pmctrl.write(|w| {
+            w.sel_pmatch().clear_bit();
+            w.ena_rxev().clear_bit()
+        });
+    }
+
+    /// Returns the current state of the product terms
+    ///
+    /// Bit `n` is set, if the product term that ends at slice `n` currently
+    /// matches.
+    pub fn matches(&self) -> u8 {
+        // This is sound, as we're only reading from a register.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.pmctrl.read().pmat().bits()
+    }
+}
+
+/// The configuration of a bit slice of the pattern match engine
+///
+/// Used with [`PatternMatch::enable`].
+///
+/// [`PatternMatch::enable`]: struct.PatternMatch.html#method.enable
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Slice {
+    /// The pin interrupt input that this slice evaluates
+    pub input: Input,
+
+    /// The condition this slice evaluates
+    pub condition: Condition,
+
+    /// Whether this slice ends a product term
+    pub endpoint: bool,
+}
+
+impl Default for Slice {
+    /// Returns a slice that always matches and doesn't end a product term
+    fn default() -> Self {
+        Self {
+            input: Input::Input0,
+            condition: Condition::ConstantHigh,
+            endpoint: false,
+        }
+    }
+}
+
+/// An input of the pattern match engine
+///
+/// Input `n` is the pin that has been selected for pin interrupt `n`, using
+/// [`Interrupt::select`].
+///
+/// [`Interrupt::select`]: struct.Interrupt.html#method.select
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Input {
+    /// The pin selected for pin interrupt 0
+    Input0,
+
+    /// The pin selected for pin interrupt 1
+    Input1,
+
+    /// The pin selected for pin interrupt 2
+    Input2,
+
+    /// The pin selected for pin interrupt 3
+    Input3,
+
+    /// The pin selected for pin interrupt 4
+    Input4,
+
+    /// The pin selected for pin interrupt 5
+    Input5,
+
+    /// The pin selected for pin interrupt 6
+    Input6,
+
+    /// The pin selected for pin interrupt 7
+    Input7,
+}
+
+impl Input {
+    fn bits(&self) -> u8 {
+        match self {
+            Self::Input0 => 0,
+            Self::Input1 => 1,
+            Self::Input2 => 2,
+            Self::Input3 => 3,
+            Self::Input4 => 4,
+            Self::Input5 => 5,
+            Self::Input6 => 6,
+            Self::Input7 => 7,
+        }
+    }
+}
+
+/// A condition that a bit slice of the pattern match engine evaluates
+///
+/// The sticky edge conditions stay matched after the edge has been detected,
+/// until the pattern match engine is reconfigured.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Condition {
+    /// Always matches
+    ConstantHigh,
+
+    /// Matches, once a rising edge has been detected
+    StickyRisingEdge,
+
+    /// Matches, once a falling edge has been detected
+    StickyFallingEdge,
+
+    /// Matches, once a rising or falling edge has been detected
+    StickyAnyEdge,
+
+    /// Matches, while the input is HIGH
+    High,
+
+    /// Matches, while the input is LOW
+    Low,
+
+    /// Never matches
+    ///
+    /// Can be used to disable a product term.
+    ConstantLow,
+
+    /// Matches for one clock cycle, when a rising or falling edge is detected
+    Event,
+}
+
+impl Condition {
+    fn bits(&self) -> u8 {
+        match self {
+            Self::ConstantHigh => 0x0,
+            Self::StickyRisingEdge => 0x1,
+            Self::StickyFallingEdge => 0x2,
+            Self::StickyAnyEdge => 0x3,
+            Self::High => 0x4,
+            Self::Low => 0x5,
+            Self::ConstantLow => 0x6,
+            Self::Event => 0x7,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Condition, Input, Slice};
+
+    #[test]
+    fn input_should_encode_pin_interrupt_number() {
+        let inputs = [
+            Input::Input0,
+            Input::Input1,
+            Input::Input2,
+            Input::Input3,
+            Input::Input4,
+            Input::Input5,
+            Input::Input6,
+            Input::Input7,
+        ];
+
+        for (i, input) in inputs.iter().enumerate() {
+            assert_eq!(usize::from(input.bits()), i);
+        }
+    }
+
+    #[test]
+    fn condition_should_encode_pmcfg_values() {
+        // See the description of the PMCFG register in the user manual.
+        assert_eq!(Condition::ConstantHigh.bits(), 0b000);
+        assert_eq!(Condition::StickyRisingEdge.bits(), 0b001);
+        assert_eq!(Condition::StickyFallingEdge.bits(), 0b010);
+        assert_eq!(Condition::StickyAnyEdge.bits(), 0b011);
+        assert_eq!(Condition::High.bits(), 0b100);
+        assert_eq!(Condition::Low.bits(), 0b101);
+        assert_eq!(Condition::ConstantLow.bits(), 0b110);
+        assert_eq!(Condition::Event.bits(), 0b111);
+    }
+
+    #[test]
+    fn default_slice_should_be_neutral_in_product_term() {
+        let slice = Slice::default();
+
+        assert_eq!(slice.condition.bits(), 0b000);
+        assert!(!slice.endpoint);
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(feature = "embedded-hal-1")]
+use crate::waker::WakerSlot;
+
 /// Implemented by types that identify pin interrupts
 ///
 /// This trait is an internal implementation detail and should neither be
@@ -13,4 +16,8 @@ pub trait Trait {
     ///
     /// Used in various registers.
     const MASK: u8;
+
+    /// The storage for the waker of the async API
+    #[cfg(feature = "embedded-hal-1")]
+    fn waker() -> &'static WakerSlot;
 }
@@ -1,15 +1,69 @@
 //! Types generated by a macro
 
-use core::marker::PhantomData;
+use core::{convert::TryFrom, marker::PhantomData};
 
-use super::{pin::Pin, state, traits::Trait};
+use crate::{
+    gpio::{direction::Direction, GpioPin},
+    pac,
+};
+
+use super::{
+    iocon::Config,
+    pin::{GenericPin, Pin},
+    state::{self, State},
+    traits::Trait,
+};
+
+/// Applies an IOCON configuration to the IOCON register of a pin
+///
+/// The I2C pins of I2C0 are true open-drain pins, and their IOCON registers
+/// have a different layout. They don't support open-drain mode, on-chip
+/// resistors, or hysteresis, and those configurations are ignored for them.
+macro_rules! modify_iocon {
+    (pio0_10, $register:expr, $config:expr) => {
+        modify_iocon!(@i2c, $register, $config)
+    };
+    (pio0_11, $register:expr, $config:expr) => {
+        modify_iocon!(@i2c, $register, $config)
+    };
+    (@i2c, $register:expr, $config:expr) => {
+        $register.modify(|_, w| match $config {
+            Config::Inverted(inverted) => w.inv().bit(inverted),
+            Config::Filter(filter) => {
+                let (s_mode, clk_div) = filter.fields();
+                w.s_mode().bits(s_mode);
+                // Sound, as `FilterClock` only provides valid values.
+                unsafe { w.clk_div().bits(clk_div) }
+            }
+            Config::OpenDrain(_)
+            | Config::Resistor(_)
+            | Config::Hysteresis(_) => w,
+        })
+    };
+    ($name:ident, $register:expr, $config:expr) => {
+        $register.modify(|_, w| match $config {
+            Config::OpenDrain(open_drain) => w.od().bit(open_drain),
+            Config::Resistor(resistor) => w.mode().bits(resistor.bits()),
+            Config::Inverted(inverted) => w.inv().bit(inverted),
+            Config::Hysteresis(hysteresis) => w.hys().bit(hysteresis),
+            Config::Filter(filter) => {
+                let (s_mode, clk_div) = filter.fields();
+                w.s_mode().bits(s_mode);
+                // Sound, as `FilterClock` only provides valid values.
+                unsafe { w.clk_div().bits(clk_div) }
+            }
+        })
+    };
+}
 
 macro_rules! pins {
     ($(
+        $(#[$attr:meta])*
         $field:ident, // e.g. pio0_0
         $type:ident,  // e.g. PIO0_0
-        $port:expr,
-        $id:expr,     // e.g. 0x00
+        $port:literal,
+        $id:literal,  // e.g. 0x00
+        $iocon:ident, // e.g. pio0_0
         $default_state_ty:ty;
     )*) => {
         /// Provides access to all pins
@@ -19,21 +73,27 @@ macro_rules! pins {
         ///
         /// # Limitations
         ///
-        /// This struct currently provides access to all pins that can be
-        /// available on an LPC8xx part. Please make sure that you are aware of
-        /// which pins are actually available on your specific part, and only
-        /// use those.
+        /// If you have selected a specific part (e.g. `845m301jbd48`), this
+        /// struct only provides access to the pins that are available in that
+        /// part's package. If you have only selected a family (e.g. `845`), it
+        /// provides access to all pins that can be available on that family.
+        /// Please make sure that you are aware of which pins are actually
+        /// available on your specific part, and only use those.
         ///
         /// [`Peripherals`]: ../struct.Peripherals.html
         #[allow(missing_docs)]
         pub struct Pins {
-            $(pub $field: Pin<$type, $default_state_ty>,)*
+            $(
+                $(#[$attr])*
+                pub $field: Pin<$type, $default_state_ty>,
+            )*
         }
 
         impl Pins {
             pub(crate) fn new() -> Self {
                 Pins {
                     $(
+                        $(#[$attr])*
                         $field: Pin {
                             ty:     $type(()),
                             _state: <$default_state_ty>::new(),
@@ -51,9 +111,11 @@ macro_rules! pins {
             /// [`Pin`]'s documentation for more information.
             ///
             /// [`Pin`]: struct.Pin.html
+            $(#[$attr])*
             #[allow(non_camel_case_types)]
             pub struct $type(());
 
+            $(#[$attr])*
             impl Trait for $type {
                 fn port(&self) -> u8 {
                     $port
@@ -67,6 +129,51 @@ macro_rules! pins {
                     0x1 << $id
                 }
             }
+
+            /// Restores the pin's identity, if the generic pin is this pin
+            ///
+            /// Returns the generic pin as the error, if it is another pin.
+            $(#[$attr])*
+            impl<S> TryFrom<Pin<GenericPin, S>> for Pin<$type, S>
+            where
+                S: State,
+            {
+                type Error = Pin<GenericPin, S>;
+
+                fn try_from(
+                    pin: Pin<GenericPin, S>,
+                ) -> Result<Self, Self::Error> {
+                    if (pin.ty.port(), pin.ty.id()) != ($port, $id) {
+                        return Err(pin);
+                    }
+
+                    Ok(Pin {
+                        ty: $type(()),
+                        _state: pin._state,
+                    })
+                }
+            }
+
+            /// Restores the pin's identity, if the generic pin is this pin
+            ///
+            /// Returns the generic pin as the error, if it is another pin.
+            $(#[$attr])*
+            impl<D> TryFrom<GpioPin<GenericPin, D>> for GpioPin<$type, D>
+            where
+                D: Direction,
+            {
+                type Error = GpioPin<GenericPin, D>;
+
+                fn try_from(
+                    pin: GpioPin<GenericPin, D>,
+                ) -> Result<Self, Self::Error> {
+                    if (pin.inner().port(), pin.inner().id()) != ($port, $id) {
+                        return Err(pin);
+                    }
+
+                    Ok(pin.with_inner($type(())))
+                }
+            }
         )*
 
 
@@ -79,6 +186,7 @@ macro_rules! pins {
         pub struct Tokens<State> {
             $(
                 /// A token representing a pin
+                $(#[$attr])*
                 pub $field: Token<$type, State>,
             )*
         }
@@ -87,6 +195,7 @@ macro_rules! pins {
             pub(crate) fn new() -> Self {
                 Self {
                     $(
+                        $(#[$attr])*
                         $field: Token(PhantomData, PhantomData),
                     )*
                 }
@@ -99,6 +208,7 @@ macro_rules! pins {
             pub(crate) fn switch_state<NewState>(self) -> Tokens<NewState> {
                 Tokens {
                     $(
+                        $(#[$attr])*
                         $field: Token(self.$field.0, PhantomData),
                     )*
                 }
@@ -112,96 +222,153 @@ macro_rules! pins {
         ///
         /// [`GPIO`]: ../gpio/struct.GPIO.html
         pub struct Token<Pin, State>(PhantomData<Pin>, PhantomData<State>);
+
+        /// Apply a configuration to the IOCON register of the provided pin
+        pub(super) fn modify_iocon(
+            pin: &impl Trait,
+            iocon: &mut pac::IOCON,
+            config: Config,
+        ) {
+            match (pin.port(), pin.id()) {
+                $(
+                    $(#[$attr])*
+                    ($port, $id) => modify_iocon!($iocon, iocon.$iocon, config),
+                )*
+                _ => unreachable!(),
+            }
+        }
     }
 }
 
+// Pins that are not available in the TSSOP20 package are gated behind the
+// package feature. If only the family is selected, all pins are available.
 #[cfg(feature = "82x")]
 pins!(
-    pio0_0 , PIO0_0 , 0, 0x00, state::Unused;
-    pio0_1 , PIO0_1 , 0, 0x01, state::Unused;
-    pio0_2 , PIO0_2 , 0, 0x02, state::Swm<((),), ()>;
-    pio0_3 , PIO0_3 , 0, 0x03, state::Swm<((),), ()>;
-    pio0_4 , PIO0_4 , 0, 0x04, state::Unused;
-    pio0_5 , PIO0_5 , 0, 0x05, state::Swm<(), ((),)>;
-    pio0_6 , PIO0_6 , 0, 0x06, state::Unused;
-    pio0_7 , PIO0_7 , 0, 0x07, state::Unused;
-    pio0_8 , PIO0_8 , 0, 0x08, state::Unused;
-    pio0_9 , PIO0_9 , 0, 0x09, state::Unused;
-    pio0_10, PIO0_10, 0, 0x0a, state::Unused;
-    pio0_11, PIO0_11, 0, 0x0b, state::Unused;
-    pio0_12, PIO0_12, 0, 0x0c, state::Unused;
-    pio0_13, PIO0_13, 0, 0x0d, state::Unused;
-    pio0_14, PIO0_14, 0, 0x0e, state::Unused;
-    pio0_15, PIO0_15, 0, 0x0f, state::Unused;
-    pio0_16, PIO0_16, 0, 0x10, state::Unused;
-    pio0_17, PIO0_17, 0, 0x11, state::Unused;
-    pio0_18, PIO0_18, 0, 0x12, state::Unused;
-    pio0_19, PIO0_19, 0, 0x13, state::Unused;
-    pio0_20, PIO0_20, 0, 0x14, state::Unused;
-    pio0_21, PIO0_21, 0, 0x15, state::Unused;
-    pio0_22, PIO0_22, 0, 0x16, state::Unused;
-    pio0_23, PIO0_23, 0, 0x17, state::Unused;
-    pio0_24, PIO0_24, 0, 0x18, state::Unused;
-    pio0_25, PIO0_25, 0, 0x19, state::Unused;
-    pio0_26, PIO0_26, 0, 0x1a, state::Unused;
-    pio0_27, PIO0_27, 0, 0x1b, state::Unused;
-    pio0_28, PIO0_28, 0, 0x1c, state::Unused;
+    pio0_0 , PIO0_0 , 0, 0x00, pio0_0 , state::Unused;
+    pio0_1 , PIO0_1 , 0, 0x01, pio0_1 , state::Unused;
+    pio0_2 , PIO0_2 , 0, 0x02, pio0_2 , state::Swm<((),), ()>;
+    pio0_3 , PIO0_3 , 0, 0x03, pio0_3 , state::Swm<((),), ()>;
+    pio0_4 , PIO0_4 , 0, 0x04, pio0_4 , state::Unused;
+    pio0_5 , PIO0_5 , 0, 0x05, pio0_5 , state::Swm<(), ((),)>;
+    #[cfg(not(feature = "20"))]
+    pio0_6 , PIO0_6 , 0, 0x06, pio0_6 , state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_7 , PIO0_7 , 0, 0x07, pio0_7 , state::Unused;
+    pio0_8 , PIO0_8 , 0, 0x08, pio0_8 , state::Unused;
+    pio0_9 , PIO0_9 , 0, 0x09, pio0_9 , state::Unused;
+    pio0_10, PIO0_10, 0, 0x0a, pio0_10, state::Unused;
+    pio0_11, PIO0_11, 0, 0x0b, pio0_11, state::Unused;
+    pio0_12, PIO0_12, 0, 0x0c, pio0_12, state::Unused;
+    pio0_13, PIO0_13, 0, 0x0d, pio0_13, state::Unused;
+    pio0_14, PIO0_14, 0, 0x0e, pio0_14, state::Unused;
+    pio0_15, PIO0_15, 0, 0x0f, pio0_15, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_16, PIO0_16, 0, 0x10, pio0_16, state::Unused;
+    pio0_17, PIO0_17, 0, 0x11, pio0_17, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_18, PIO0_18, 0, 0x12, pio0_18, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_19, PIO0_19, 0, 0x13, pio0_19, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_20, PIO0_20, 0, 0x14, pio0_20, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_21, PIO0_21, 0, 0x15, pio0_21, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_22, PIO0_22, 0, 0x16, pio0_22, state::Unused;
+    pio0_23, PIO0_23, 0, 0x17, pio0_23, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_24, PIO0_24, 0, 0x18, pio0_24, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_25, PIO0_25, 0, 0x19, pio0_25, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_26, PIO0_26, 0, 0x1a, pio0_26, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_27, PIO0_27, 0, 0x1b, pio0_27, state::Unused;
+    #[cfg(not(feature = "20"))]
+    pio0_28, PIO0_28, 0, 0x1c, pio0_28, state::Unused;
 );
 
+// Pins that are not available in every package are gated behind the package
+// features. If only the family is selected, all pins are available.
 #[cfg(feature = "845")]
 pins!(
-    pio0_0 , PIO0_0 , 0, 0x00, state::Unused;
-    pio0_1 , PIO0_1 , 0, 0x01, state::Unused;
-    pio0_2 , PIO0_2 , 0, 0x02, state::Swm<((),), ()>;
-    pio0_3 , PIO0_3 , 0, 0x03, state::Swm<((),), ()>;
-    pio0_4 , PIO0_4 , 0, 0x04, state::Unused;
-    pio0_5 , PIO0_5 , 0, 0x05, state::Swm<(), ((),)>;
-    pio0_6 , PIO0_6 , 0, 0x06, state::Unused;
-    pio0_7 , PIO0_7 , 0, 0x07, state::Unused;
-    pio0_8 , PIO0_8 , 0, 0x08, state::Unused;
-    pio0_9 , PIO0_9 , 0, 0x09, state::Unused;
-    pio0_10, PIO0_10, 0, 0x0a, state::Unused;
-    pio0_11, PIO0_11, 0, 0x0b, state::Unused;
-    pio0_12, PIO0_12, 0, 0x0c, state::Unused;
-    pio0_13, PIO0_13, 0, 0x0d, state::Unused;
-    pio0_14, PIO0_14, 0, 0x0e, state::Unused;
-    pio0_15, PIO0_15, 0, 0x0f, state::Unused;
-    pio0_16, PIO0_16, 0, 0x10, state::Unused;
-    pio0_17, PIO0_17, 0, 0x11, state::Unused;
-    pio0_18, PIO0_18, 0, 0x12, state::Unused;
-    pio0_19, PIO0_19, 0, 0x13, state::Unused;
-    pio0_20, PIO0_20, 0, 0x14, state::Unused;
-    pio0_21, PIO0_21, 0, 0x15, state::Unused;
-    pio0_22, PIO0_22, 0, 0x16, state::Unused;
-    pio0_23, PIO0_23, 0, 0x17, state::Unused;
-    pio0_24, PIO0_24, 0, 0x18, state::Unused;
-    pio0_25, PIO0_25, 0, 0x19, state::Unused;
-    pio0_26, PIO0_26, 0, 0x1a, state::Unused;
-    pio0_27, PIO0_27, 0, 0x1b, state::Unused;
-    pio0_28, PIO0_28, 0, 0x1c, state::Unused;
-    pio0_29, PIO0_29, 0, 0x1d, state::Unused;
-    pio0_30, PIO0_30, 0, 0x1e, state::Unused;
-    pio0_31, PIO0_31, 0, 0x1f, state::Unused;
-    pio1_0 , PIO1_0 , 1, 0x00, state::Unused;
-    pio1_1 , PIO1_1 , 1, 0x01, state::Unused;
-    pio1_2 , PIO1_2 , 1, 0x02, state::Unused;
-    pio1_3 , PIO1_3 , 1, 0x03, state::Unused;
-    pio1_4 , PIO1_4 , 1, 0x04, state::Unused;
-    pio1_5 , PIO1_5 , 1, 0x05, state::Unused;
-    pio1_6 , PIO1_6 , 1, 0x06, state::Unused;
-    pio1_7 , PIO1_7 , 1, 0x07, state::Unused;
-    pio1_8 , PIO1_8 , 1, 0x08, state::Unused;
-    pio1_9 , PIO1_9 , 1, 0x09, state::Unused;
-    pio1_10, PIO1_10, 1, 0x0a, state::Unused;
-    pio1_11, PIO1_11, 1, 0x0b, state::Unused;
-    pio1_12, PIO1_12, 1, 0x0c, state::Unused;
-    pio1_13, PIO1_13, 1, 0x0d, state::Unused;
-    pio1_14, PIO1_14, 1, 0x0e, state::Unused;
-    pio1_15, PIO1_15, 1, 0x0f, state::Unused;
-    pio1_16, PIO1_16, 1, 0x10, state::Unused;
-    pio1_17, PIO1_17, 1, 0x11, state::Unused;
-    pio1_18, PIO1_18, 1, 0x12, state::Unused;
-    pio1_19, PIO1_19, 1, 0x13, state::Unused;
-    pio1_20, PIO1_20, 1, 0x14, state::Unused;
-    pio1_21, PIO1_21, 1, 0x15, state::Unused;
+    pio0_0 , PIO0_0 , 0, 0x00, pio0_0 , state::Unused;
+    pio0_1 , PIO0_1 , 0, 0x01, pio0_1 , state::Unused;
+    pio0_2 , PIO0_2 , 0, 0x02, pio0_2 , state::Swm<((),), ()>;
+    pio0_3 , PIO0_3 , 0, 0x03, pio0_3 , state::Swm<((),), ()>;
+    pio0_4 , PIO0_4 , 0, 0x04, pio0_4 , state::Unused;
+    pio0_5 , PIO0_5 , 0, 0x05, pio0_5 , state::Swm<(), ((),)>;
+    pio0_6 , PIO0_6 , 0, 0x06, pio0_6 , state::Unused;
+    pio0_7 , PIO0_7 , 0, 0x07, pio0_7 , state::Unused;
+    pio0_8 , PIO0_8 , 0, 0x08, pio0_8 , state::Unused;
+    pio0_9 , PIO0_9 , 0, 0x09, pio0_9 , state::Unused;
+    pio0_10, PIO0_10, 0, 0x0a, pio0_10, state::Unused;
+    pio0_11, PIO0_11, 0, 0x0b, pio0_11, state::Unused;
+    pio0_12, PIO0_12, 0, 0x0c, pio0_12, state::Unused;
+    pio0_13, PIO0_13, 0, 0x0d, pio0_13, state::Unused;
+    pio0_14, PIO0_14, 0, 0x0e, pio0_14, state::Unused;
+    pio0_15, PIO0_15, 0, 0x0f, pio0_15, state::Unused;
+    pio0_16, PIO0_16, 0, 0x10, pio0_16, state::Unused;
+    pio0_17, PIO0_17, 0, 0x11, pio0_17, state::Unused;
+    pio0_18, PIO0_18, 0, 0x12, pio0_18, state::Unused;
+    pio0_19, PIO0_19, 0, 0x13, pio0_19, state::Unused;
+    pio0_20, PIO0_20, 0, 0x14, pio0_20, state::Unused;
+    pio0_21, PIO0_21, 0, 0x15, pio0_21, state::Unused;
+    pio0_22, PIO0_22, 0, 0x16, pio0_22, state::Unused;
+    pio0_23, PIO0_23, 0, 0x17, pio0_23, state::Unused;
+    pio0_24, PIO0_24, 0, 0x18, pio0_24, state::Unused;
+    pio0_25, PIO0_25, 0, 0x19, pio0_25, state::Unused;
+    pio0_26, PIO0_26, 0, 0x1a, pio0_26, state::Unused;
+    pio0_27, PIO0_27, 0, 0x1b, pio0_27, state::Unused;
+    pio0_28, PIO0_28, 0, 0x1c, pio0_28, state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio0_29, PIO0_29, 0, 0x1d, pio0_29, state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio0_30, PIO0_30, 0, 0x1e, pio0_30, state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio0_31, PIO0_31, 0, 0x1f, pio0_31, state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_0 , PIO1_0 , 1, 0x00, pio1_0 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_1 , PIO1_1 , 1, 0x01, pio1_1 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_2 , PIO1_2 , 1, 0x02, pio1_2 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_3 , PIO1_3 , 1, 0x03, pio1_3 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_4 , PIO1_4 , 1, 0x04, pio1_4 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_5 , PIO1_5 , 1, 0x05, pio1_5 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_6 , PIO1_6 , 1, 0x06, pio1_6 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_7 , PIO1_7 , 1, 0x07, pio1_7 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_8 , PIO1_8 , 1, 0x08, pio1_8 , state::Unused;
+    #[cfg(not(feature = "33"))]
+    pio1_9 , PIO1_9 , 1, 0x09, pio1_9 , state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_10, PIO1_10, 1, 0x0a, pio1_10, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_11, PIO1_11, 1, 0x0b, pio1_11, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_12, PIO1_12, 1, 0x0c, pio1_12, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_13, PIO1_13, 1, 0x0d, pio1_13, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_14, PIO1_14, 1, 0x0e, pio1_14, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_15, PIO1_15, 1, 0x0f, pio1_15, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_16, PIO1_16, 1, 0x10, pio1_16, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_17, PIO1_17, 1, 0x11, pio1_17, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_18, PIO1_18, 1, 0x12, pio1_18, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_19, PIO1_19, 1, 0x13, pio1_19, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_20, PIO1_20, 1, 0x14, pio1_20, state::Unused;
+    #[cfg(not(any(feature = "33", feature = "48")))]
+    pio1_21, PIO1_21, 1, 0x15, pio1_21, state::Unused;
 );
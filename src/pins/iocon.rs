@@ -0,0 +1,207 @@
+//! Access to the IOCON register of a pin
+
+use crate::pac;
+
+use super::{gen::modify_iocon, traits::Trait};
+
+/// The on-chip resistor configuration of a pin
+///
+/// Used with [`Pin::set_resistor`] and [`GpioPin::set_resistor`].
+///
+/// [`Pin::set_resistor`]: struct.Pin.html#method.set_resistor
+/// [`GpioPin::set_resistor`]: ../gpio/struct.GpioPin.html#method.set_resistor
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Resistor {
+    /// No pull-up or pull-down resistor
+    Inactive,
+
+    /// Pull-down resistor enabled
+    PullDown,
+
+    /// Pull-up resistor enabled
+    ///
+    /// This is the reset configuration of all pins that support it.
+    PullUp,
+
+    /// Repeater mode
+    ///
+    /// Enables the pull-up resistor, if the pin is HIGH, and the pull-down
+    /// resistor, if it is LOW. Keeps the pin at its last level, if it is no
+    /// longer driven.
+    Repeater,
+}
+
+impl Resistor {
+    pub(super) fn bits(self) -> u8 {
+        match self {
+            Self::Inactive => 0x0,
+            Self::PullDown => 0x1,
+            Self::PullUp => 0x2,
+            Self::Repeater => 0x3,
+        }
+    }
+}
+
+/// The digital input filter configuration of a pin
+///
+/// The filter rejects input pulses that are shorter than the configured number
+/// of filter clock cycles. Used with [`Pin::set_filter`] and
+/// [`GpioPin::set_filter`].
+///
+/// [`Pin::set_filter`]: struct.Pin.html#method.set_filter
+/// [`GpioPin::set_filter`]: ../gpio/struct.GpioPin.html#method.set_filter
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Filter {
+    /// The input filter is bypassed
+    ///
+    /// This is the reset configuration.
+    Bypass,
+
+    /// The input filter is enabled
+    Enabled {
+        /// The number of filter clock cycles a pulse needs to be stable for
+        samples: FilterSamples,
+
+        /// The filter clock
+        ///
+        /// The filter clock dividers are configured using
+        /// [`syscon::Handle::set_iocon_clock_divider`].
+        ///
+        /// [`syscon::Handle::set_iocon_clock_divider`]: ../syscon/struct.Handle.html#method.set_iocon_clock_divider
+        clock: FilterClock,
+    },
+}
+
+impl Filter {
+    /// Returns the values of the S_MODE and CLK_DIV fields
+    pub(super) fn fields(self) -> (u8, u8) {
+        match self {
+            Self::Bypass => (0x0, 0x0),
+            Self::Enabled { samples, clock } => {
+                let s_mode = match samples {
+                    FilterSamples::One => 0x1,
+                    FilterSamples::Two => 0x2,
+                    FilterSamples::Three => 0x3,
+                };
+
+                (s_mode, clock.index())
+            }
+        }
+    }
+}
+
+/// The number of filter clock cycles used by [`Filter::Enabled`]
+///
+/// [`Filter::Enabled`]: enum.Filter.html#variant.Enabled
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FilterSamples {
+    /// Reject pulses shorter than 1 filter clock cycle
+    One,
+
+    /// Reject pulses shorter than 2 filter clock cycles
+    Two,
+
+    /// Reject pulses shorter than 3 filter clock cycles
+    Three,
+}
+
+/// Selects one of the filter clocks used by [`Filter::Enabled`]
+///
+/// Each filter clock is derived from the main clock by one of the IOCON clock
+/// dividers, IOCONCLKDIV0 to IOCONCLKDIV6. The dividers are configured using
+/// [`syscon::Handle::set_iocon_clock_divider`].
+///
+/// [`Filter::Enabled`]: enum.Filter.html#variant.Enabled
+/// [`syscon::Handle::set_iocon_clock_divider`]: ../syscon/struct.Handle.html#method.set_iocon_clock_divider
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FilterClock {
+    /// The clock provided by IOCONCLKDIV0
+    Div0,
+
+    /// The clock provided by IOCONCLKDIV1
+    Div1,
+
+    /// The clock provided by IOCONCLKDIV2
+    Div2,
+
+    /// The clock provided by IOCONCLKDIV3
+    Div3,
+
+    /// The clock provided by IOCONCLKDIV4
+    Div4,
+
+    /// The clock provided by IOCONCLKDIV5
+    Div5,
+
+    /// The clock provided by IOCONCLKDIV6
+    Div6,
+}
+
+impl FilterClock {
+    fn index(self) -> u8 {
+        match self {
+            Self::Div0 => 0,
+            Self::Div1 => 1,
+            Self::Div2 => 2,
+            Self::Div3 => 3,
+            Self::Div4 => 4,
+            Self::Div5 => 5,
+            Self::Div6 => 6,
+        }
+    }
+}
+
+/// A configuration that can be applied to the IOCON register of a pin
+#[derive(Clone, Copy)]
+pub(super) enum Config {
+    OpenDrain(bool),
+    Resistor(Resistor),
+    Inverted(bool),
+    Hysteresis(bool),
+    Filter(Filter),
+}
+
+/// Enable or disable open-drain mode for the provided pin
+pub(crate) fn set_open_drain(
+    pin: &impl Trait,
+    iocon: &mut pac::IOCON,
+    open_drain: bool,
+) {
+    modify_iocon(pin, iocon, Config::OpenDrain(open_drain));
+}
+
+/// Set the resistor configuration of the provided pin
+pub(crate) fn set_resistor(
+    pin: &impl Trait,
+    iocon: &mut pac::IOCON,
+    resistor: Resistor,
+) {
+    modify_iocon(pin, iocon, Config::Resistor(resistor));
+}
+
+/// Enable or disable input inversion for the provided pin
+pub(crate) fn set_inverted(
+    pin: &impl Trait,
+    iocon: &mut pac::IOCON,
+    inverted: bool,
+) {
+    modify_iocon(pin, iocon, Config::Inverted(inverted));
+}
+
+/// Enable or disable input hysteresis for the provided pin
+pub(crate) fn set_hysteresis(
+    pin: &impl Trait,
+    iocon: &mut pac::IOCON,
+    hysteresis: bool,
+) {
+    modify_iocon(pin, iocon, Config::Hysteresis(hysteresis));
+}
+
+/// Set the input filter configuration of the provided pin
+pub(crate) fn set_filter(
+    pin: &impl Trait,
+    iocon: &mut pac::IOCON,
+    filter: Filter,
+) {
+    modify_iocon(pin, iocon, Config::Filter(filter));
+}
@@ -9,9 +9,16 @@ mod gen;
 mod pin;
 mod traits;
 
+pub(crate) mod iocon;
+
 pub mod state;
 
 pub use self::{
-    gen::*, pin::DynamicPinDirection, pin::GenericPin, pin::Pin, state::State,
+    gen::*,
+    iocon::{Filter, FilterClock, FilterSamples, Resistor},
+    pin::DynamicPinDirection,
+    pin::GenericPin,
+    pin::Pin,
+    state::State,
     traits::Trait,
 };
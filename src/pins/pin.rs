@@ -1,10 +1,11 @@
 use crate::{
     gpio::{direction, GpioPin, Level},
-    init_state,
+    init_state, pac,
 };
 
 use super::{
     gen::Token,
+    iocon::{self, Filter, Resistor},
     state::{self, State},
     traits::Trait,
 };
@@ -181,6 +182,12 @@ pub struct Pin<T: Trait, S: State> {
 /// [`pins::Trait`] having pin information compiled into its type, e.g. if you'd like to store
 /// several pin instances in a collection, or modify them in batches.
 ///
+/// A [`Pin`] or [`GpioPin`] holding a `GenericPin` can be converted back into
+/// one holding the specific pin type, using `TryFrom`. The conversion fails
+/// and returns the original pin, if the generic pin is a different pin.
+///
+/// [`Pin`]: struct.Pin.html
+/// [`GpioPin`]: ../gpio/struct.GpioPin.html
 /// [`pins::Token`]: struct.Token.html
 /// [`pins::Trait`]: trait.Trait.html
 pub struct GenericPin {
@@ -315,6 +322,69 @@ where
         GpioPin::new(self.ty, initial)
     }
 
+    /// Transition pin to GPIO open-drain output mode
+    ///
+    /// This method is only available while the pin is in the unused state. Code
+    /// that attempts to call this method while the pin is in any other state
+    /// will not compile. See [State Management] for more information on
+    /// managing pin states.
+    ///
+    /// Enables open-drain mode in the pin's IOCON register, then works like
+    /// [`into_output_pin`]. Setting the output HIGH releases the pin instead of
+    /// driving it, which allows for multiple devices to share a line, like an
+    /// interrupt request line or a bit-banged bus. Such lines need a pull-up
+    /// resistor.
+    ///
+    /// The I2C pins of I2C0 (PIO0_10 and PIO0_11) are always open-drain and
+    /// don't need this method.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     prelude::*,
+    ///     Peripherals,
+    ///     gpio,
+    /// };
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let gpio = p.GPIO;
+    /// #[cfg(feature = "845")]
+    /// let gpio = p.GPIO.enable(&mut syscon.handle);
+    ///
+    /// let mut pin = p.pins.pio0_12.into_open_drain_output_pin(
+    ///     gpio.tokens.pio0_12,
+    ///     gpio::Level::High,
+    ///     &mut p.IOCON,
+    /// );
+    ///
+    /// // Pull the line low, then release it again
+    /// pin.set_low();
+    /// pin.set_high();
+    ///
+    /// // Check whether another device holds the line low
+    /// let is_low = pin.is_low();
+    /// ```
+    ///
+    /// [State Management]: #state-management
+    /// [`into_output_pin`]: #method.into_output_pin
+    pub fn into_open_drain_output_pin(
+        self,
+        _token: Token<T, init_state::Enabled>,
+        initial: Level,
+        iocon: &mut pac::IOCON,
+    ) -> GpioPin<T, direction::OpenDrain> {
+        // Enable open-drain mode before the pin starts driving its output, to
+        // never drive the line high.
+        iocon::set_open_drain(&self.ty, iocon, true);
+
+        GpioPin::new(self.ty, initial)
+    }
+
     /// Transition pin to Dynamic mode, i.e. GPIO direction switchable at runtime
     ///
     /// This method is only available while the pin is in the unused state. Code
@@ -503,6 +573,119 @@ where
     }
 }
 
+impl<T, S> Pin<T, S>
+where
+    T: Trait,
+    S: State,
+{
+    /// Configure the pin's on-chip pull-up/pull-down resistor
+    ///
+    /// This method is available in every pin state, so inputs can be
+    /// configured regardless of whether they are used for GPIO or by a
+    /// peripheral.
+    ///
+    /// Has no effect on the I2C pins of I2C0 (PIO0_10 and PIO0_11), as those
+    /// don't have on-chip resistors.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{pins::Resistor, Peripherals};
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut pin = p.pins.pio0_12.into_swm_pin();
+    /// pin.set_resistor(Resistor::PullDown, &mut p.IOCON);
+    /// ```
+    pub fn set_resistor(&mut self, resistor: Resistor, iocon: &mut pac::IOCON) {
+        iocon::set_resistor(&self.ty, iocon, resistor);
+    }
+
+    /// Enable or disable inversion of the pin's input
+    ///
+    /// If enabled, the input is inverted before it reaches GPIO or any
+    /// peripheral.
+    pub fn set_inverted(&mut self, inverted: bool, iocon: &mut pac::IOCON) {
+        iocon::set_inverted(&self.ty, iocon, inverted);
+    }
+
+    /// Enable or disable the pin's input hysteresis
+    ///
+    /// Has no effect on the I2C pins of I2C0 (PIO0_10 and PIO0_11), as those
+    /// don't support this configuration.
+    pub fn set_hysteresis(&mut self, hysteresis: bool, iocon: &mut pac::IOCON) {
+        iocon::set_hysteresis(&self.ty, iocon, hysteresis);
+    }
+
+    /// Configure the pin's digital input filter
+    ///
+    /// The filter can be used to clean up noisy inputs, for example from
+    /// mechanical switches, in hardware.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{
+    ///     pins::{Filter, FilterClock, FilterSamples},
+    ///     Peripherals,
+    /// };
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// // Filter clock 0 runs at the main clock divided by 255
+    /// syscon
+    ///     .handle
+    ///     .set_iocon_clock_divider(FilterClock::Div0, 255);
+    ///
+    /// let mut pin = p.pins.pio0_12.into_swm_pin();
+    /// pin.set_filter(
+    ///     Filter::Enabled {
+    ///         samples: FilterSamples::Three,
+    ///         clock: FilterClock::Div0,
+    ///     },
+    ///     &mut p.IOCON,
+    /// );
+    /// ```
+    pub fn set_filter(&mut self, filter: Filter, iocon: &mut pac::IOCON) {
+        iocon::set_filter(&self.ty, iocon, filter);
+    }
+}
+
+impl<T, O, Is> Pin<T, state::Swm<O, Is>>
+where
+    T: Trait,
+{
+    /// Enable or disable open-drain mode
+    ///
+    /// In open-drain mode, the pin only ever drives its output low. This is
+    /// required for functions that share a line with other devices, like the
+    /// pin of a USART in half-duplex mode (see
+    /// [`USART::enable_async_half_duplex`]). For GPIO, use
+    /// [`into_open_drain_output_pin`] instead.
+    ///
+    /// Has no effect on the I2C pins of I2C0 (PIO0_10 and PIO0_11), as those
+    /// are always open-drain.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// let mut p = Peripherals::take().unwrap();
+    ///
+    /// let mut pin = p.pins.pio0_12.into_swm_pin();
+    /// pin.set_open_drain(true, &mut p.IOCON);
+    /// ```
+    ///
+    /// [`USART::enable_async_half_duplex`]: ../usart/struct.USART.html#method.enable_async_half_duplex
+    /// [`into_open_drain_output_pin`]: #method.into_open_drain_output_pin
+    pub fn set_open_drain(&mut self, open_drain: bool, iocon: &mut pac::IOCON) {
+        iocon::set_open_drain(&self.ty, iocon, open_drain);
+    }
+}
+
 impl<T> Pin<T, state::Swm<(), ()>>
 where
     T: Trait,
@@ -528,11 +711,67 @@ where
             _state: state::Unused,
         }
     }
+
+    /// Transitions this pin into the dynamic SWM state
+    ///
+    /// This method is only available, if two conditions are met:
+    /// - The pin is in the SWM state.
+    /// - No functions are assigned to this pin.
+    ///
+    /// Unless both of these conditions are met, code trying to call this method
+    /// will not compile.
+    ///
+    /// Erases the pin's identity from its type, and tracks the functions that
+    /// are assigned to it at runtime instead. This allows choosing the pin for
+    /// a function at runtime, for example based on a configuration value. See
+    /// [`Function::assign_dynamic`].
+    ///
+    /// [`Function::assign_dynamic`]: ../swm/struct.Function.html#method.assign_dynamic
+    pub fn into_dynamic_swm_pin(self) -> Pin<GenericPin, state::DynamicSwm> {
+        Pin {
+            ty: GenericPin::new(self.ty.port(), self.ty.id()),
+            _state: state::DynamicSwm::new(),
+        }
+    }
+}
+
+impl Pin<GenericPin, state::DynamicSwm> {
+    /// Returns the state of this pin
+    ///
+    /// Can be used to check which functions are currently assigned.
+    pub fn state(&self) -> &state::DynamicSwm {
+        &self._state
+    }
+
+    /// Transitions this pin from the dynamic SWM state to the unused state
+    ///
+    /// This is the counterpart to [`into_dynamic_swm_pin`]. Returns the pin
+    /// unchanged, if any functions are still assigned to it (see
+    /// [`DynamicSwm::is_unassigned`]).
+    ///
+    /// The returned pin still holds a [`GenericPin`]. It can be converted back
+    /// into a pin of the specific type using `TryFrom`.
+    ///
+    /// [`into_dynamic_swm_pin`]: #method.into_dynamic_swm_pin
+    /// [`DynamicSwm::is_unassigned`]: state/struct.DynamicSwm.html#method.is_unassigned
+    /// [`GenericPin`]: struct.GenericPin.html
+    pub fn into_unused_pin(
+        self,
+    ) -> Result<Pin<GenericPin, state::Unused>, Self> {
+        if !self._state.is_unassigned() {
+            return Err(self);
+        }
+
+        Ok(Pin {
+            ty: self.ty,
+            _state: state::Unused,
+        })
+    }
 }
 
 impl GenericPin {
     /// Creates a new `GenericPin`
-    pub(super) fn new(port: u8, id: u8) -> Self {
+    pub(crate) fn new(port: u8, id: u8) -> Self {
         Self { port, id }
     }
 }
@@ -67,3 +67,33 @@ impl<Output, Inputs> Swm<Output, Inputs> {
 }
 
 impl<Output, Inputs> State for Swm<Output, Inputs> {}
+
+/// Marks a [`Pin`] as being available for runtime-checked function assignment
+///
+/// Unlike [`Swm`], this state tracks the assigned functions at runtime. This
+/// allows assigning functions to pins that are only known at runtime. See
+/// [`Function::assign_dynamic`].
+///
+/// [`Pin`]: ../struct.Pin.html
+/// [`Swm`]: struct.Swm.html
+/// [`Function::assign_dynamic`]: ../../swm/struct.Function.html#method.assign_dynamic
+pub struct DynamicSwm {
+    pub(crate) output: bool,
+    pub(crate) inputs: u8,
+}
+
+impl DynamicSwm {
+    pub(crate) const fn new() -> Self {
+        Self {
+            output: false,
+            inputs: 0,
+        }
+    }
+
+    /// Indicates whether no functions are assigned to the pin
+    pub fn is_unassigned(&self) -> bool {
+        !self.output && self.inputs == 0
+    }
+}
+
+impl State for DynamicSwm {}
@@ -31,7 +31,16 @@
 
 use cortex_m::{asm, interrupt};
 
-use crate::{clock, init_state, pac};
+use crate::{
+    clock, init_state, pac,
+    spi::{self, SPI},
+    syscon::{self, IoscDerivedClock, WakeUpInterrupt, WktWakeup},
+    usart::{self, USART},
+    wkt::WKT,
+};
+
+#[cfg(feature = "845")]
+use crate::pinint;
 
 /// Entry point to the PMU API
 ///
@@ -112,7 +121,7 @@ pub struct Parts {
 ///
 /// [module documentation]: index.html
 pub struct Handle {
-    pmu: pac::PMU,
+    pub(crate) pmu: pac::PMU,
 }
 
 impl Handle {
@@ -146,7 +155,7 @@ impl Handle {
     ///
     /// If you intend to wake up from this mode again, you need to configure the
     /// STARTERP0 and STARTERP1 registers of the SYSCON appropriately. See user
-    /// manual, section 6.5.1.
+    /// manual, section 6.5.1. [`LowPower`] can do that for you.
     ///
     /// # Safety
     ///
@@ -158,7 +167,9 @@ impl Handle {
     ///
     /// Please make sure that the peripheral states configured in PDAWAKECFG
     /// match the peripheral states as tracked by the API before calling this
-    /// method.
+    /// method. [`LowPower`] can help you with that.
+    ///
+    /// [`LowPower`]: struct.LowPower.html
     pub unsafe fn enter_deep_sleep_mode(&mut self, scb: &mut pac::SCB) {
         interrupt::free(|_| {
             self.pmu.pcon.modify(|_, w| w.pm().deep_sleep_mode());
@@ -184,7 +195,7 @@ impl Handle {
     ///
     /// If you intend to wake up from this mode again, you need to configure the
     /// STARTERP0 and STARTERP1 registers of the SYSCON appropriately. See user
-    /// manual, section 6.5.1.
+    /// manual, section 6.5.1. [`LowPower`] can do that for you.
     ///
     /// # Safety
     ///
@@ -196,7 +207,9 @@ impl Handle {
     ///
     /// Please make sure that the peripheral states configured in PDAWAKECFG
     /// match the peripheral states as tracked by the API before calling this
-    /// method.
+    /// method. [`LowPower`] can help you with that.
+    ///
+    /// [`LowPower`]: struct.LowPower.html
     pub unsafe fn enter_power_down_mode(&mut self, scb: &mut pac::SCB) {
         interrupt::free(|_| {
             self.pmu.pcon.modify(|_, w| w.pm().power_down_mode());
@@ -285,3 +298,246 @@ impl<State> clock::Frequency for LowPowerClock<State> {
 }
 
 impl clock::Enabled for LowPowerClock<init_state::Enabled> {}
+
+/// The low-power modes that can be configured using [`LowPower`]
+///
+/// [`LowPower`]: struct.LowPower.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Sleep mode; see [`Handle::enter_sleep_mode`]
+    ///
+    /// [`Handle::enter_sleep_mode`]: struct.Handle.html#method.enter_sleep_mode
+    Sleep,
+
+    /// Deep-sleep mode; see [`Handle::enter_deep_sleep_mode`]
+    ///
+    /// [`Handle::enter_deep_sleep_mode`]: struct.Handle.html#method.enter_deep_sleep_mode
+    DeepSleep,
+
+    /// Power-down mode; see [`Handle::enter_power_down_mode`]
+    ///
+    /// [`Handle::enter_power_down_mode`]: struct.Handle.html#method.enter_power_down_mode
+    PowerDown,
+}
+
+/// Builds the power configuration for a low-power mode
+///
+/// Before entering deep-sleep or power-down mode, the PDSLEEPCFG register must
+/// be configured to select the analog blocks that stay powered during the
+/// low-power mode, and PDAWAKECFG must be configured to restore the current
+/// power configuration after wake-up. In addition, the interrupts that should
+/// wake up the microcontroller must be enabled in STARTERP0 and STARTERP1. This
+/// builder computes all of those from the requested [`Mode`] and the drivers
+/// that are declared to be in use.
+///
+/// Drivers are declared by passing references to them to the respective
+/// methods. Please note that the HAL can't know what you intend to do during
+/// the low-power mode, so only drivers that you declare are checked.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{pmu, Peripherals};
+///
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+/// let mut pmu    = p.PMU.split();
+///
+/// let low_power_clock = pmu.low_power_clock.enable(&mut pmu.handle);
+/// let wkt = p.WKT.enable(&mut syscon.handle);
+///
+/// pmu::LowPower::new(pmu::Mode::DeepSleep)
+///     .wkt_with_low_power_clock(&wkt, &low_power_clock)
+///     .apply(&mut syscon.handle)
+///     .expect("WKT should be able to run in deep-sleep mode");
+/// ```
+///
+/// [`Mode`]: enum.Mode.html
+#[derive(Debug)]
+pub struct LowPower {
+    mode: Mode,
+    keep_bod: bool,
+    keep_wdtosc: bool,
+    requires_main_clock: bool,
+    starterp0: u32,
+    starterp1: u32,
+}
+
+impl LowPower {
+    /// Start building the power configuration for the given mode
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            keep_bod: false,
+            keep_wdtosc: false,
+            requires_main_clock: false,
+            starterp0: 0,
+            starterp1: 0,
+        }
+    }
+
+    /// Keep brown-out detection powered during the low-power mode
+    pub fn keep_brown_out_detection(mut self) -> Self {
+        self.keep_bod = true;
+        self
+    }
+
+    /// Keep the watchdog oscillator powered during the low-power mode
+    ///
+    /// This is required, if the windowed watchdog timer should keep running.
+    pub fn keep_watchdog_oscillator(mut self) -> Self {
+        self.keep_wdtosc = true;
+        self
+    }
+
+    /// Declare that the WKT is running from the low-power clock
+    ///
+    /// The low-power clock keeps running in all low-power modes. The WKT
+    /// interrupt is enabled as a wake-up source.
+    pub fn wkt_with_low_power_clock(
+        self,
+        _: &WKT<init_state::Enabled>,
+        _: &LowPowerClock<init_state::Enabled>,
+    ) -> Self {
+        self.wake_up_on::<WktWakeup>()
+    }
+
+    /// Declare that the WKT is running from the IRC/FRO-derived clock
+    ///
+    /// That clock is not available in deep-sleep and power-down modes.
+    pub fn wkt_with_iosc_derived_clock(
+        mut self,
+        _: &WKT<init_state::Enabled>,
+        _: &IoscDerivedClock<init_state::Enabled>,
+    ) -> Self {
+        self.requires_main_clock = true;
+        self
+    }
+
+    /// Declare that a USART in asynchronous mode is in use
+    ///
+    /// Asynchronous mode requires the USART's function clock, which is not
+    /// available in deep-sleep and power-down modes. USARTs in synchronous
+    /// slave mode are clocked externally and don't need to be declared.
+    /// Neither do USARTs that are only used to wake up (see
+    /// [`USART::enable_wakeup_on_receive`]).
+    ///
+    /// [`USART::enable_wakeup_on_receive`]: ../usart/struct.USART.html#method.enable_wakeup_on_receive
+    pub fn usart<I, W>(
+        mut self,
+        _: &USART<I, usart::state::Enabled<W, usart::state::AsyncMode>>,
+    ) -> Self
+    where
+        I: usart::Instance,
+        W: usart::state::Word,
+    {
+        self.requires_main_clock = true;
+        self
+    }
+
+    /// Declare that an SPI master is in use
+    ///
+    /// SPI masters require the main clock, which is not available in
+    /// deep-sleep and power-down modes. SPI slaves are clocked externally and
+    /// don't need to be declared.
+    pub fn spi_master<I>(
+        mut self,
+        _: &SPI<I, init_state::Enabled<spi::Master>>,
+    ) -> Self
+    where
+        I: spi::Instance,
+    {
+        self.requires_main_clock = true;
+        self
+    }
+
+    /// Declare that a pin interrupt is used to wake up
+    ///
+    /// Pin interrupts are detected asynchronously, so they work in all
+    /// low-power modes. The interrupt is enabled as a wake-up source.
+    #[cfg(feature = "845")]
+    pub fn pin_interrupt<I, P, Mode>(
+        mut self,
+        _: &pinint::Interrupt<I, P, init_state::Enabled, Mode>,
+    ) -> Self
+    where
+        I: pinint::Trait,
+    {
+        self.starterp0 |= u32::from(I::MASK);
+        self
+    }
+
+    /// Enable an interrupt as a wake-up source
+    ///
+    /// Use this for wake-up sources that aren't covered by the other methods,
+    /// for example a USART that is set up using
+    /// [`USART::enable_wakeup_on_receive`].
+    ///
+    /// [`USART::enable_wakeup_on_receive`]: ../usart/struct.USART.html#method.enable_wakeup_on_receive
+    pub fn wake_up_on<I>(mut self) -> Self
+    where
+        I: WakeUpInterrupt,
+    {
+        self.starterp1 |= I::MASK;
+        self
+    }
+
+    /// Write the power configuration to PDSLEEPCFG, PDAWAKECFG, and STARTERP
+    ///
+    /// PDAWAKECFG is set to the current contents of PDRUNCFG, meaning all
+    /// analog blocks that are currently powered will be powered again after
+    /// wake-up. This means this method should be called after all peripherals
+    /// have been set up, right before entering the low-power mode.
+    ///
+    /// Returns an error, if a declared driver can't operate in the requested
+    /// mode. Nothing is written in that case.
+    ///
+    /// The declared wake-up sources are enabled in STARTERP0 and STARTERP1.
+    /// Wake-up sources that are already enabled stay enabled. The interrupts
+    /// still need to be enabled in the NVIC.
+    pub fn apply(
+        self,
+        syscon: &mut syscon::Handle,
+    ) -> Result<(), LowPowerError> {
+        if self.mode == Mode::Sleep {
+            // In sleep mode, the power configuration doesn't change.
+            return Ok(());
+        }
+
+        if self.requires_main_clock {
+            return Err(LowPowerError::ClockNotAvailable);
+        }
+
+        syscon.pdsleepcfg.write(|w| {
+            w.bod_pd().bit(!self.keep_bod);
+            w.wdtosc_pd().bit(!self.keep_wdtosc)
+        });
+
+        let pdruncfg = syscon.pdruncfg.read().bits();
+        syscon.pdawakecfg.write(|w|
+            // Sound, as PDRUNCFG and PDAWAKECFG have the same layout.
+            unsafe { w.bits(pdruncfg) });
+
+        // Sound, as only bits that correspond to wake-up sources are set.
+        syscon
+            .starterp0
+            .modify(|r, w| unsafe { w.bits(r.bits() | self.starterp0) });
+        syscon
+            .starterp1
+            .modify(|r, w| unsafe { w.bits(r.bits() | self.starterp1) });
+
+        Ok(())
+    }
+}
+
+/// Error returned by [`LowPower::apply`]
+///
+/// [`LowPower::apply`]: struct.LowPower.html#method.apply
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LowPowerError {
+    /// A declared driver requires a clock that is not available in the
+    /// requested mode
+    ClockNotAvailable,
+}
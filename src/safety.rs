@@ -0,0 +1,283 @@
+//! Safety monitor, combining watchdog, brown-out detection, and reset causes
+//!
+//! [`SafetyMonitor`] configures the windowed watchdog timer (WWDT) and the
+//! brown-out detection (BOD), and records why the microcontroller was last
+//! reset. It also keeps a crash counter in a PMU general-purpose register,
+//! which survives all resets except for power-on and brown-out resets.
+//!
+//! The watchdog is clocked from the watchdog oscillator, which this module
+//! configures to run at 600 kHz (before its divider). This oscillator is not
+//! very accurate (±40%, according to the data sheet), so timeouts should be
+//! chosen with a generous margin.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     safety::{BodLevel, Config, SafetyMonitor},
+//!     Peripherals,
+//! };
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut pmu    = p.PMU.split();
+//!
+//! let mut monitor = SafetyMonitor::start(
+//!     p.WWDT,
+//!     Config {
+//!         timeout_ms: 1000,
+//!         window_ms: None,
+//!         bod_reset: Some(BodLevel::Level2),
+//!     },
+//!     &syscon.bod,
+//!     &mut syscon.handle,
+//!     &mut pmu.handle,
+//! )
+//! .unwrap();
+//!
+//! if monitor.reset_cause().watchdog {
+//!     // recover from watchdog reset
+//! }
+//!
+//! loop {
+//!     // do work
+//!     monitor.feed();
+//! }
+//! ```
+//!
+//! [`SafetyMonitor`]: struct.SafetyMonitor.html
+
+use cortex_m::interrupt;
+
+use crate::{pac, pmu, syscon};
+
+/// Frequency of the WWDT counter, in ticks per millisecond
+///
+/// The watchdog oscillator is configured to 600 kHz, which is divided by 2 in
+/// the oscillator and by 4 in the WWDT.
+const TICKS_PER_MS: u32 = 75;
+
+/// Index of the PMU general-purpose register used for the crash counter
+const CRASH_COUNTER: usize = 3;
+
+/// Safety monitor
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct SafetyMonitor {
+    wwdt: pac::WWDT,
+    reset_cause: ResetCause,
+    crash_count: u32,
+}
+
+impl SafetyMonitor {
+    /// Start the safety monitor
+    ///
+    /// Reads and clears the reset cause, updates the crash counter, configures
+    /// brown-out detection, and starts the watchdog. Once started, the
+    /// watchdog can't be stopped, except by a reset.
+    ///
+    /// The crash counter is reset to zero after a power-on or brown-out reset,
+    /// and incremented after a watchdog or system reset.
+    pub fn start(
+        wwdt: pac::WWDT,
+        config: Config,
+        bod: &syscon::BOD,
+        syscon: &mut syscon::Handle,
+        pmu: &mut pmu::Handle,
+    ) -> Result<Self, InvalidTimeout> {
+        let timeout = config
+            .timeout_ms
+            .checked_mul(TICKS_PER_MS)
+            .filter(|ticks| (0xff..=0xff_ffff).contains(ticks))
+            .ok_or(InvalidTimeout)?;
+        let window = match config.window_ms {
+            Some(window_ms) => window_ms
+                .checked_mul(TICKS_PER_MS)
+                .and_then(|ticks| timeout.checked_sub(ticks))
+                .ok_or(InvalidTimeout)?,
+            None => 0xff_ffff,
+        };
+
+        let reset_cause = ResetCause::read(syscon);
+
+        let crash_count = if reset_cause.power_on || reset_cause.brown_out {
+            0
+        } else if reset_cause.watchdog || reset_cause.system {
+            pmu.pmu.gpreg[CRASH_COUNTER].read().bits().saturating_add(1)
+        } else {
+            pmu.pmu.gpreg[CRASH_COUNTER].read().bits()
+        };
+        pmu.pmu.gpreg[CRASH_COUNTER].write(|w| unsafe { w.bits(crash_count) });
+
+        // See user manual, section 5.6.
+        if config.bod_reset.is_some() {
+            syscon.power_up(bod);
+        }
+        syscon.bodctrl.write(|w| {
+            let level = config.bod_reset.map(|level| level as u8).unwrap_or(0);
+
+            w.bodrstena().bit(config.bod_reset.is_some());
+            // Sound, as `BodLevel` only has valid values.
+            unsafe { w.bodrstlev().bits(level) }
+        });
+
+        // Select 600 kHz, divided by 2.
+        syscon.wdtoscctrl.write(|w| {
+            // Sound, as 1 is a valid value for FREQSEL, and 0 is a valid value
+            // for DIVSEL.
+            unsafe {
+                w.freqsel().bits(0x1);
+                w.divsel().bits(0)
+            }
+        });
+        syscon.power_up(&wwdt);
+        syscon.enable_clock(&wwdt);
+
+        wwdt.tc.write(|w| unsafe { w.bits(timeout) });
+        wwdt.window.write(|w| unsafe { w.bits(window) });
+
+        // Once set, neither of these can be cleared by software.
+        wwdt.mod_.write(|w| {
+            w.wden().set_bit();
+            w.wdreset().set_bit()
+        });
+
+        let mut monitor = Self {
+            wwdt,
+            reset_cause,
+            crash_count,
+        };
+
+        // The watchdog only starts running after the first feed.
+        monitor.feed();
+
+        Ok(monitor)
+    }
+
+    /// Feed the watchdog
+    ///
+    /// Reloads the watchdog timer with the configured timeout. If a window was
+    /// configured, feeding the watchdog too early causes a reset too.
+    pub fn feed(&mut self) {
+        // The two writes must not be interrupted by another access to the
+        // WWDT registers. See user manual, section 16.6.3.
+        interrupt::free(|_| {
+            self.wwdt.feed.write(|w| unsafe { w.bits(0xaa) });
+            self.wwdt.feed.write(|w| unsafe { w.bits(0x55) });
+        });
+    }
+
+    /// Returns the cause of the last reset
+    ///
+    /// The cause is read when the monitor is started. The flags may be
+    /// combined, if multiple reset causes occurred since they were last
+    /// cleared.
+    pub fn reset_cause(&self) -> ResetCause {
+        self.reset_cause
+    }
+
+    /// Returns the number of watchdog or system resets since power-on
+    pub fn crash_count(&self) -> u32 {
+        self.crash_count
+    }
+
+    /// Reset the crash counter to zero
+    pub fn clear_crash_count(&mut self, pmu: &mut pmu::Handle) {
+        self.crash_count = 0;
+        pmu.pmu.gpreg[CRASH_COUNTER].write(|w| unsafe { w.bits(0) });
+    }
+}
+
+/// Configuration for [`SafetyMonitor`]
+///
+/// [`SafetyMonitor`]: struct.SafetyMonitor.html
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    /// Time until the watchdog resets the microcontroller, if it isn't fed
+    ///
+    /// Must result in a watchdog timer value in the range 255 to 2^24 - 1,
+    /// which is about 4 ms to 223 s.
+    pub timeout_ms: u32,
+
+    /// Minimum time between two feeds
+    ///
+    /// If this is set, feeding the watchdog earlier than this after the
+    /// previous feed causes a reset. Must be smaller than `timeout_ms`.
+    pub window_ms: Option<u32>,
+
+    /// Voltage level at which a brown-out reset is triggered
+    ///
+    /// Brown-out reset is disabled, if this is `None`.
+    pub bod_reset: Option<BodLevel>,
+}
+
+/// Brown-out detection voltage level
+///
+/// Please refer to the data sheet of your part for the voltages that these
+/// levels correspond to. Higher levels correspond to higher voltages.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BodLevel {
+    /// Level 1
+    Level1 = 1,
+
+    /// Level 2
+    Level2 = 2,
+
+    /// Level 3
+    Level3 = 3,
+}
+
+/// The cause of the last reset
+///
+/// Multiple flags can be set, if more than one reset occurred since the flags
+/// were last cleared.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ResetCause {
+    /// Power-on reset
+    pub power_on: bool,
+
+    /// External reset via the RESET pin
+    pub external: bool,
+
+    /// Watchdog reset
+    pub watchdog: bool,
+
+    /// Brown-out reset
+    pub brown_out: bool,
+
+    /// System reset, requested by software
+    pub system: bool,
+}
+
+impl ResetCause {
+    fn read(syscon: &mut syscon::Handle) -> Self {
+        // See user manual, section 5.6.
+        let stat = syscon.sysrststat.read();
+
+        let cause = Self {
+            power_on: stat.por().bit_is_set(),
+            external: stat.extrst().bit_is_set(),
+            watchdog: stat.wdt().bit_is_set(),
+            brown_out: stat.bod().bit_is_set(),
+            system: stat.sysrst().bit_is_set(),
+        };
+
+        // The flags are cleared by writing 1.
+        syscon.sysrststat.write(|w| {
+            w.por().bit(cause.power_on);
+            w.extrst().bit(cause.external);
+            w.wdt().bit(cause.watchdog);
+            w.bod().bit(cause.brown_out);
+            w.sysrst().bit(cause.system)
+        });
+
+        cause
+    }
+}
+
+/// Indicates that the watchdog timeout or window is out of range
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidTimeout;
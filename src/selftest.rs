@@ -0,0 +1,322 @@
+//! Peripheral self-test routines
+//!
+//! Provides tests that can be run at startup, for production test or power-on
+//! diagnostics:
+//!
+//! - [`usart`] and [`spi`] send a test pattern through the hardware loopback
+//!   of the respective peripheral and check that it is received unchanged.
+//! - [`i2c_bus`] checks that the I2C bus is idle and probes a slave address.
+//!
+//! None of these tests are required for normal operation. All of them poll
+//! the hardware a limited number of times, so they fail with
+//! [`Failure::Timeout`] instead of blocking forever, if the hardware is stuck.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{prelude::*, selftest, spi, Peripherals};
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut swm    = p.SWM.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! #[cfg(feature = "82x")]
+//! let spi_clock = spi::Clock::new(&(), 0);
+//! #[cfg(feature = "845")]
+//! let spi_clock = spi::Clock::new(&syscon.iosc, 0);
+//!
+//! let (spi0_sck, _) = swm.movable_functions.spi0_sck.assign(
+//!     p.pins.pio0_13.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (spi0_mosi, _) = swm.movable_functions.spi0_mosi.assign(
+//!     p.pins.pio0_14.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (spi0_miso, _) = swm.movable_functions.spi0_miso.assign(
+//!     p.pins.pio0_15.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//!
+//! let mut spi = p.SPI0.enable_as_master(
+//!     &spi_clock,
+//!     &mut syscon.handle,
+//!     spi::MODE_0,
+//!     spi0_sck,
+//!     spi0_mosi,
+//!     spi0_miso,
+//! );
+//!
+//! if let Err(failure) = selftest::spi(&mut spi) {
+//!     // report failure
+//! }
+//! ```
+//!
+//! [`usart`]: fn.usart.html
+//! [`spi`]: fn.spi.html
+//! [`i2c_bus`]: fn.i2c_bus.html
+//! [`Failure::Timeout`]: enum.Failure.html#variant.Timeout
+
+use core::{convert::TryInto as _, marker::PhantomData};
+
+use embedded_hal::{
+    serial::{Read, Write},
+    spi::FullDuplex,
+};
+
+use crate::{
+    i2c::{self, master},
+    init_state::Enabled,
+    spi,
+    usart::{self, state},
+};
+
+/// The bytes sent by the loopback tests
+///
+/// Includes alternating bit patterns, to detect bits that are stuck or
+/// shorted to their neighbors.
+const PATTERN: [u8; 4] = [0x55, 0xaa, 0x00, 0xff];
+
+/// How often the hardware is polled, before a test gives up
+const MAX_POLLS: u32 = 100_000;
+
+/// Run a loopback test on a USART instance
+///
+/// Enables loopback mode, which internally connects the transmitter output to
+/// the receiver input, sends a test pattern, and checks that it is received
+/// unchanged. Unless loopback mode was already enabled via
+/// [`Settings::loopback_enabled`], it is disabled again, before this function
+/// returns.
+///
+/// While the test is running, the test pattern is also sent on the TXD pin.
+/// Any data that was received before the test is discarded.
+///
+/// [`Settings::loopback_enabled`]: ../usart/struct.Settings.html#method.loopback_enabled
+pub fn usart<I, Mode>(
+    usart: &mut usart::USART<I, state::Enabled<u8, Mode>>,
+) -> Result<(), Failure>
+where
+    I: usart::Instance,
+{
+    let loop_was_enabled = usart.set_loopback(true);
+
+    // Discard anything that was received before.
+    while usart.read().is_ok() {}
+
+    let result = PATTERN.iter().try_for_each(|&sent| {
+        poll(|| usart.write(sent))?;
+        let received = poll(|| usart.read())?;
+
+        if received != sent {
+            return Err(Failure::Mismatch { sent, received });
+        }
+
+        Ok(())
+    });
+
+    if !loop_was_enabled {
+        usart.set_loopback(false);
+    }
+
+    result
+}
+
+/// Run a loopback test on an SPI instance in master mode
+///
+/// Enables loopback mode, which internally connects MOSI to MISO, transfers a
+/// test pattern, and checks that it is received unchanged. Loopback mode is
+/// disabled again, before this function returns.
+///
+/// While the test is running, the test pattern is also sent on the MOSI pin,
+/// and SCK is driven.
+pub fn spi<I>(
+    spi: &mut spi::SPI<I, Enabled<spi::Master>>,
+) -> Result<(), Failure>
+where
+    I: spi::Instance,
+{
+    spi.set_loopback(true);
+
+    // Discard anything that was received before.
+    while spi.read().is_ok() {}
+
+    let result = PATTERN.iter().try_for_each(|&sent| {
+        poll(|| spi.send(sent))?;
+        let received = poll(|| spi.read())?;
+
+        if received != sent {
+            return Err(Failure::Mismatch { sent, received });
+        }
+
+        Ok(())
+    });
+
+    spi.set_loopback(false);
+
+    result
+}
+
+/// Check the health of an I2C bus
+///
+/// Checks that the I2C master is idle, then addresses the slave at `address`
+/// for writing, without writing any data. Returns whether the slave
+/// acknowledged its address.
+///
+/// A bus that is stuck (for example, because a slave holds SDA or SCL low)
+/// results in [`Failure::Timeout`] or a bus error, instead of a result. A
+/// missing slave results in [`Probe::Nack`].
+///
+/// [`Failure::Timeout`]: enum.Failure.html#variant.Timeout
+/// [`Probe::Nack`]: enum.Probe.html#variant.Nack
+pub fn i2c_bus<I, C>(
+    _master: &mut i2c::Master<I, Enabled<PhantomData<C>>, Enabled>,
+    address: u8,
+) -> Result<Probe, Failure>
+where
+    I: i2c::Instance,
+{
+    if address > 0b111_1111 {
+        return Err(Failure::I2c(i2c::Error::AddressOutOfRange));
+    }
+
+    // Sound, as we have exclusive access to the I2C master, and only access
+    // master registers.
+    let registers = unsafe { &*I::REGISTERS };
+
+    let wait = || -> Result<master::State, Failure> {
+        let mut polls = 0;
+        loop {
+            let stat = registers.stat.read();
+
+            if stat.mstarbloss().bit_is_set() {
+                registers.stat.write(|w| w.mstarbloss().set_bit());
+                return Err(Failure::I2c(i2c::Error::MasterArbitrationLoss));
+            }
+            if stat.mstststperr().bit_is_set() {
+                registers.stat.write(|w| w.mstststperr().set_bit());
+                return Err(Failure::I2c(i2c::Error::MasterStartStopError));
+            }
+
+            if !stat.mstpending().is_in_progress() {
+                let mststate = stat.mststate();
+                return mststate.variant().try_into().map_err(|()| {
+                    Failure::I2c(i2c::Error::UnexpectedState {
+                        expected: master::State::Idle,
+                        actual: Err(mststate.bits()),
+                    })
+                });
+            }
+
+            polls += 1;
+            if polls >= MAX_POLLS {
+                return Err(Failure::Timeout);
+            }
+        }
+    };
+
+    let state = wait()?;
+    if state != master::State::Idle {
+        return Err(Failure::I2c(i2c::Error::UnexpectedState {
+            expected: master::State::Idle,
+            actual: Ok(state),
+        }));
+    }
+
+    // Sound, as all 8-bit values are accepted here.
+    registers
+        .mstdat
+        .write(|w| unsafe { w.data().bits(address << 1) });
+    registers.mstctl.write(|w| w.mststart().start());
+
+    let probe = match wait()? {
+        master::State::TxReady => Probe::Ack,
+        master::State::NackAddress => Probe::Nack,
+        state => {
+            return Err(Failure::I2c(i2c::Error::UnexpectedState {
+                expected: master::State::TxReady,
+                actual: Ok(state),
+            }))
+        }
+    };
+
+    registers.mstctl.write(|w| w.mststop().stop());
+
+    Ok(probe)
+}
+
+/// Polls `f` until it doesn't return `WouldBlock` or the poll limit is hit
+fn poll<T, E>(mut f: impl FnMut() -> nb::Result<T, E>) -> Result<T, Failure>
+where
+    E: Into<Failure>,
+{
+    for _ in 0..MAX_POLLS {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(nb::Error::WouldBlock) => continue,
+            Err(nb::Error::Other(error)) => return Err(error.into()),
+        }
+    }
+
+    Err(Failure::Timeout)
+}
+
+/// Result of an I2C address probe
+///
+/// Returned by [`i2c_bus`].
+///
+/// [`i2c_bus`]: fn.i2c_bus.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Probe {
+    /// The slave acknowledged its address
+    Ack,
+
+    /// No slave acknowledged the address
+    Nack,
+}
+
+/// A self-test failed
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Failure {
+    /// The hardware didn't respond within the poll limit
+    Timeout,
+
+    /// A byte was received that differs from the one sent
+    Mismatch {
+        /// The byte that was sent
+        sent: u8,
+
+        /// The byte that was received
+        received: u8,
+    },
+
+    /// The USART reported an error
+    Usart(usart::Error<u8>),
+
+    /// The I2C master reported an error
+    I2c(i2c::Error),
+}
+
+impl From<usart::Error<u8>> for Failure {
+    fn from(error: usart::Error<u8>) -> Self {
+        Self::Usart(error)
+    }
+}
+
+impl From<void::Void> for Failure {
+    fn from(void: void::Void) -> Self {
+        void::unreachable(void)
+    }
+}
+
+impl From<core::convert::Infallible> for Failure {
+    fn from(error: core::convert::Infallible) -> Self {
+        match error {}
+    }
+}
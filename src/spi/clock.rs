@@ -5,7 +5,6 @@ use crate::syscon::{self, clock_source::PeripheralClockSelector};
 /// Contains the clock configuration for an SPI instance
 pub struct Clock<Clock> {
     pub(crate) divval: u16,
-    // The fields in the DLY register are ignored, since SSEL & EOF aren't used
     pub(crate) _clock: PhantomData<Clock>,
 }
 
@@ -20,6 +19,93 @@ where
             _clock: PhantomData,
         }
     }
+
+    /// Create the clock config for the SPI peripheral from a [`Divider`]
+    ///
+    /// Use this in combination with [`Divider::from_rate`], to compute the
+    /// configuration value at compile time.
+    pub fn from_divider(_: &C, divider: Divider) -> Self {
+        Self {
+            divval: divider.divval,
+            _clock: PhantomData,
+        }
+    }
+}
+
+/// Divider value for an SPI bit rate
+///
+/// Can be computed at compile time from a bit rate and the frequency of the
+/// SPI clock source, using [`Divider::from_rate`]. Pass it to
+/// [`Clock::from_divider`] to create the clock configuration.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::spi;
+///
+/// // Fails to compile, if the bit rate is off by 5% or more.
+/// const DIVIDER: spi::Divider =
+///     spi::Divider::from_rate(12_000_000, 1_000_000, 5);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Divider {
+    divval: u16,
+}
+
+impl Divider {
+    /// Compute the divider value for a bit rate
+    ///
+    /// `clock_hz` is the frequency of the clock source that is passed to
+    /// [`Clock::from_divider`]. The resulting bit rate is never higher than
+    /// `rate_hz`, and lower by less than `tolerance_percent`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if no value within the tolerance can be found. If this function
+    /// is used to initialize a constant, this results in a compile-time error.
+    /// Use [`Divider::try_from_rate`], if you need to handle this case without
+    /// panicking.
+    pub const fn from_rate(
+        clock_hz: u32,
+        rate_hz: u32,
+        tolerance_percent: u32,
+    ) -> Self {
+        match Self::try_from_rate(clock_hz, rate_hz, tolerance_percent) {
+            Some(divider) => divider,
+            None => panic!("No SPI divider within tolerance of bit rate"),
+        }
+    }
+
+    /// Compute the divider value for a bit rate, without panicking
+    ///
+    /// Works like [`Divider::from_rate`], but returns `None`, if no value
+    /// within the tolerance can be found.
+    pub const fn try_from_rate(
+        clock_hz: u32,
+        rate_hz: u32,
+        tolerance_percent: u32,
+    ) -> Option<Self> {
+        if rate_hz == 0 {
+            return None;
+        }
+
+        // Round the divider up, so we never exceed the requested rate.
+        let divider = (clock_hz as u64).div_ceil(rate_hz as u64);
+        if divider == 0 || divider - 1 > u16::MAX as u64 {
+            return None;
+        }
+
+        let resulting_rate = clock_hz as u64 / divider;
+        let deviation_percent =
+            (rate_hz as u64 - resulting_rate) * 100 / rate_hz as u64;
+        if deviation_percent >= tolerance_percent as u64 {
+            return None;
+        }
+
+        Some(Self {
+            divval: (divider - 1) as u16,
+        })
+    }
 }
 
 /// Implemented for SPI clock sources
@@ -0,0 +1,122 @@
+use core::convert::Infallible;
+
+use embedded_hal_1::spi::{ErrorType, Operation, SpiBus, SpiDevice};
+
+use crate::{init_state::Enabled, swm};
+
+use super::{Instance, Master, SlaveSelect, SPI};
+
+/// The highest core clock frequency supported by any LPC8xx part, in MHz
+///
+/// Used to turn delays within a transaction into a number of CPU cycles that
+/// is guaranteed to be long enough.
+const MAX_CORE_CLOCK_MHZ: u64 = 30;
+
+/// A device on the SPI bus, selected by a hardware SSEL signal
+///
+/// Owns the [`SPI`] master and one of the SPI instance's slave select
+/// functions. The slave select signal is asserted for the duration of each
+/// transaction. Use [`SharedBus`] to talk to multiple devices on the same bus.
+///
+/// # `embedded-hal` traits
+///
+/// - [`embedded_hal_1::spi::SpiDevice`]
+///
+/// [`SPI`]: struct.SPI.html
+/// [`SharedBus`]: shared/struct.SharedBus.html
+/// [`embedded_hal_1::spi::SpiDevice`]: #impl-SpiDevice%3Cu8%3E
+pub struct Device<I, Ssel, SselPin> {
+    spi: SPI<I, Enabled<Master>>,
+    ssel: swm::Function<Ssel, swm::state::Assigned<SselPin>>,
+}
+
+impl<I, Ssel, SselPin> Device<I, Ssel, SselPin>
+where
+    I: Instance,
+    Ssel: SlaveSelect<I>,
+{
+    /// Create a new `Device`
+    ///
+    /// Deasserts the slave select signal, which the SPI master otherwise
+    /// asserts during every transfer.
+    pub fn new(
+        mut spi: SPI<I, Enabled<Master>>,
+        ssel: swm::Function<Ssel, swm::state::Assigned<SselPin>>,
+    ) -> Self {
+        spi.set_slave_select(Ssel::INDEX, false);
+        Self { spi, ssel }
+    }
+
+    /// Return the SPI master and the slave select function
+    pub fn free(
+        self,
+    ) -> (
+        SPI<I, Enabled<Master>>,
+        swm::Function<Ssel, swm::state::Assigned<SselPin>>,
+    ) {
+        (self.spi, self.ssel)
+    }
+}
+
+impl<I, Ssel, SselPin> ErrorType for Device<I, Ssel, SselPin>
+where
+    I: Instance,
+{
+    type Error = Infallible;
+}
+
+impl<I, Ssel, SselPin> SpiDevice<u8> for Device<I, Ssel, SselPin>
+where
+    I: Instance,
+    Ssel: SlaveSelect<I>,
+{
+    /// Executes `operations` with the slave select signal asserted
+    ///
+    /// Waits until all data has been sent, before deasserting the slave select
+    /// signal again.
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        transaction::<I, Ssel>(&mut self.spi, operations)
+    }
+}
+
+pub(super) fn transaction<I, Ssel>(
+    spi: &mut SPI<I, Enabled<Master>>,
+    operations: &mut [Operation<'_, u8>],
+) -> Result<(), Infallible>
+where
+    I: Instance,
+    Ssel: SlaveSelect<I>,
+{
+    spi.set_slave_select(Ssel::INDEX, true);
+
+    for operation in operations {
+        match operation {
+            Operation::Read(words) => spi.read(words)?,
+            Operation::Write(words) => spi.write(words)?,
+            Operation::Transfer(read, write) => spi.transfer(read, write)?,
+            Operation::TransferInPlace(words) => {
+                spi.transfer_in_place(words)?
+            }
+            Operation::DelayNs(ns) => {
+                // The last word must have left the shift register before the
+                // delay starts.
+                spi.flush()?;
+
+                // We don't know the actual core clock frequency, so assume the
+                // highest one. Can't overflow, as the result is at most
+                // `u32::MAX * 30 / 1000`.
+                let cycles =
+                    (u64::from(*ns) * MAX_CORE_CLOCK_MHZ).div_ceil(1000);
+                cortex_m::asm::delay(cycles as u32);
+            }
+        }
+    }
+
+    spi.end_transfer();
+    spi.set_slave_select(Ssel::INDEX, false);
+
+    Ok(())
+}
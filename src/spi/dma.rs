@@ -36,7 +36,7 @@ where
         buffer: &'static mut [u8],
         rx_channel: dma::Channel<I::RxChannel, Enabled>,
         tx_channel: dma::Channel<I::TxChannel, Enabled>,
-    ) -> Self {
+    ) -> Result<Self, (dma::transfer::InvalidTransfer, Resources<I>)> {
         let ptr = buffer.as_mut_ptr();
         let len = buffer.len();
 
@@ -53,16 +53,33 @@ where
         let tx_buffer = unsafe { dma::Buffer::new(ptr, len) };
 
         let rx_transfer =
-            dma::Transfer::new(rx_channel, Rx(PhantomData), rx_buffer);
+            match dma::Transfer::new(rx_channel, Rx(PhantomData), rx_buffer) {
+                Ok(transfer) => transfer,
+                Err((error, payload)) => {
+                    return Err((
+                        error,
+                        (spi, buffer, payload.channel, tx_channel),
+                    ));
+                }
+            };
         let tx_transfer =
-            dma::Transfer::new(tx_channel, tx_buffer, Tx(PhantomData));
+            match dma::Transfer::new(tx_channel, tx_buffer, Tx(PhantomData)) {
+                Ok(transfer) => transfer,
+                Err((error, payload)) => {
+                    let rx_channel = rx_transfer.free().channel;
+                    return Err((
+                        error,
+                        (spi, buffer, rx_channel, payload.channel),
+                    ));
+                }
+            };
 
-        Self {
+        Ok(Self {
             spi,
             buffer,
             rx_transfer,
             tx_transfer,
-        }
+        })
     }
 
     /// Start the transfer
@@ -85,23 +102,26 @@ where
     /// Wait for the transfer to finish
     ///
     /// Waits until both underlying DMA transfers have finished.
-    pub fn wait(
-        self,
-    ) -> (
-        SPI<I, Enabled<Master>>,
-        &'static mut [u8],
-        dma::Channel<I::RxChannel, Enabled>,
-        dma::Channel<I::TxChannel, Enabled>,
-    ) {
+    pub fn wait(self) -> Resources<I> {
         let rx_payload = match self.rx_transfer.wait() {
             Ok(payload) => payload,
             // can't happen, as error type is `Void`
-            Err(_) => unreachable!(),
+            Err((dma::transfer::Error::Source(void), _)) => {
+                void::unreachable(void)
+            }
+            Err((dma::transfer::Error::Dest(void), _)) => {
+                void::unreachable(void)
+            }
         };
         let tx_payload = match self.tx_transfer.wait() {
             Ok(payload) => payload,
             // can't happen, as error type is `Void`
-            Err(_) => unreachable!(),
+            Err((dma::transfer::Error::Source(void), _)) => {
+                void::unreachable(void)
+            }
+            Err((dma::transfer::Error::Dest(void), _)) => {
+                void::unreachable(void)
+            }
         };
 
         (
@@ -113,6 +133,107 @@ where
     }
 }
 
+/// The resources of an SPI/DMA [`Transfer`]
+///
+/// Returned by [`Transfer::wait`], or together with an error, if the transfer
+/// could not be created.
+///
+/// [`Transfer`]: struct.Transfer.html
+/// [`Transfer::wait`]: struct.Transfer.html#method.wait
+pub type Resources<I> = (
+    SPI<I, Enabled<Master>>,
+    &'static mut [u8],
+    dma::Channel<<I as Instance>::RxChannel, Enabled>,
+    dma::Channel<<I as Instance>::TxChannel, Enabled>,
+);
+
+/// An SPI/DMA transfer that only writes
+///
+/// Sends the words from a buffer, while ignoring the received data. Created by
+/// [`SPI::write_all`].
+///
+/// [`SPI::write_all`]: struct.SPI.html#method.write_all
+pub struct WriteTransfer<State, I: Instance> {
+    spi: SPI<I, Enabled<Master>>,
+    transfer: dma::Transfer<State, I::TxChannel, &'static [u8], Tx<I>>,
+}
+
+impl<I> WriteTransfer<Ready, I>
+where
+    I: Instance,
+{
+    pub(super) fn new(
+        mut spi: SPI<I, Enabled<Master>>,
+        buffer: &'static [u8],
+        channel: dma::Channel<I::TxChannel, Enabled>,
+    ) -> Result<Self, (dma::transfer::InvalidTransfer, WriteResources<I>)> {
+        let transfer =
+            match dma::Transfer::new(channel, buffer, Tx(PhantomData)) {
+                Ok(transfer) => transfer,
+                Err((error, payload)) => {
+                    return Err((
+                        error,
+                        (spi, payload.source, payload.channel),
+                    ));
+                }
+            };
+
+        // Nobody reads the received data, so the master would stall after the
+        // first word, if it didn't ignore it.
+        spi.set_rx_ignore(true);
+
+        Ok(Self { spi, transfer })
+    }
+
+    /// Start the transfer
+    pub fn start(self) -> WriteTransfer<Started, I> {
+        WriteTransfer {
+            spi: self.spi,
+            transfer: self.transfer.start(),
+        }
+    }
+}
+
+impl<I> WriteTransfer<Started, I>
+where
+    I: Instance,
+{
+    /// Wait for the transfer to finish
+    ///
+    /// Waits until the DMA transfer has finished, and the SPI has sent the last
+    /// word.
+    pub fn wait(mut self) -> WriteResources<I> {
+        let payload = match self.transfer.wait() {
+            Ok(payload) => payload,
+            // can't happen, as error type is `Void`
+            Err((dma::transfer::Error::Source(void), _)) => {
+                void::unreachable(void)
+            }
+            Err((dma::transfer::Error::Dest(void), _)) => {
+                void::unreachable(void)
+            }
+        };
+
+        while !self.spi.is_master_idle() {}
+        self.spi.set_rx_ignore(false);
+
+        (self.spi, payload.source, payload.channel)
+    }
+}
+
+/// The resources of an SPI/DMA [`WriteTransfer`]
+///
+/// Returned by [`WriteTransfer::wait`], or together with an error, if the
+/// transfer could not be created.
+///
+/// [`WriteTransfer`]: struct.WriteTransfer.html
+/// [`WriteTransfer::wait`]: struct.WriteTransfer.html#method.wait
+pub type WriteResources<I> = (
+    SPI<I, Enabled<Master>>,
+    &'static [u8],
+    dma::Channel<<I as Instance>::TxChannel, Enabled>,
+);
+
 /// Represents the receiving portion of the DMA peripheral
 struct Rx<I>(PhantomData<I>);
 
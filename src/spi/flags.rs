@@ -0,0 +1,61 @@
+use super::Instance;
+
+/// Used to query the state of SPI flags
+///
+/// See [`SPI::is_flag_set`].
+///
+/// [`SPI::is_flag_set`]: struct.SPI.html#method.is_flag_set
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Flag {
+    /// Receiver ready
+    RXRDY,
+
+    /// Transmitter ready
+    TXRDY,
+
+    /// Receiver overrun
+    RXOV,
+
+    /// Transmitter underrun
+    TXUR,
+
+    /// Slave select asserted
+    SSA,
+
+    /// Slave select deasserted
+    SSD,
+
+    /// Stalled
+    STALLED,
+
+    /// Master idle
+    MSTIDLE,
+}
+
+impl Flag {
+    pub(super) fn is_set<I: Instance>(&self) -> bool {
+        // Sound, as besides reading, we only write to a stateless register.
+        let spi = unsafe { &*I::REGISTERS };
+
+        let (bit_pos, reset) = match self {
+            Self::RXRDY => (0, false),
+            Self::TXRDY => (1, false),
+            Self::RXOV => (2, true),
+            Self::TXUR => (3, true),
+            Self::SSA => (4, true),
+            Self::SSD => (5, true),
+            Self::STALLED => (6, false),
+            Self::MSTIDLE => (8, false),
+        };
+
+        let flag = spi.stat.read().bits() & (0x1 << bit_pos) != 0;
+
+        if reset {
+            // Sound, as we only set the bit of a write-1-to-clear flag.
+            spi.stat.write(|w| unsafe { w.bits(0x1 << bit_pos) });
+        }
+
+        flag
+    }
+}
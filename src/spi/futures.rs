@@ -0,0 +1,228 @@
+//! Async API for SPI
+//!
+//! Implements [`embedded_hal_async::spi::SpiBus`] for SPI masters, which
+//! allows awaiting transfers from an async executor, instead of busy-waiting
+//! for the SPI to become ready.
+//!
+//! This module is only available, if the `embedded-hal-1` feature is enabled.
+//!
+//! The futures are woken from the SPI interrupt. For this to work, you need to
+//! call [`on_interrupt`] from the interrupt handler of the SPI instance, and
+//! unmask the interrupt in the NVIC (see [`SPI::enable_in_nvic`]). The futures
+//! enable the RXRDY and TXRDY interrupts as required, and [`on_interrupt`]
+//! disables them again, so you shouldn't use these interrupts for anything
+//! else, while using this API.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     embedded_hal_async::spi::SpiBus as _,
+//!     init_state::Enabled,
+//!     pac, spi,
+//! };
+//!
+//! async fn send(spi: &mut spi::SPI<pac::SPI0, Enabled<spi::Master>>) {
+//!     // The error type is `Infallible`.
+//!     spi.write(b"data").await.unwrap();
+//! }
+//!
+//! // Call this from the SPI0 interrupt handler.
+//! spi::futures::on_interrupt::<pac::SPI0>();
+//! ```
+//!
+//! [`embedded_hal_async::spi::SpiBus`]: ../struct.SPI.html#impl-SpiBus%3Cu8%3E-1
+//! [`on_interrupt`]: fn.on_interrupt.html
+//! [`SPI::enable_in_nvic`]: ../struct.SPI.html#method.enable_in_nvic
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use embedded_hal::spi::FullDuplex as _;
+
+use crate::init_state::Enabled;
+
+use super::{Instance, Interrupts, Master, SPI};
+
+/// Wake the future that is waiting for SPI instance `I`
+///
+/// Must be called from the interrupt handler of SPI instance `I`. Disables the
+/// interrupts that were enabled by the futures, and wakes the future that was
+/// waiting for them.
+pub fn on_interrupt<I>()
+where
+    I: Instance,
+{
+    // Sound, as we only write to a stateless register, and the interrupts we
+    // disable are managed by this module.
+    let spi = unsafe { &*I::REGISTERS };
+
+    spi.intenclr.write(|w| {
+        w.rxrdyen().set_bit();
+        w.txrdyen().set_bit()
+    });
+
+    I::waker().wake();
+}
+
+impl<I> embedded_hal_async::spi::SpiBus<u8> for SPI<I, Enabled<Master>>
+where
+    I: Instance,
+{
+    /// Receives words, while sending `0xff` for each word
+    async fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        TransferFuture::new(self, Words::Read(words)).await;
+        Ok(())
+    }
+
+    /// Sends all words, ignoring the received data
+    async fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        TransferFuture::new(self, Words::Write(words)).await;
+        Ok(())
+    }
+
+    /// Sends `write`, while receiving into `read`
+    ///
+    /// If `read` is longer than `write`, `0xff` is sent for the remaining
+    /// words. If `write` is longer than `read`, the remaining received words
+    /// are discarded.
+    async fn transfer(
+        &mut self,
+        read: &mut [u8],
+        write: &[u8],
+    ) -> Result<(), Self::Error> {
+        TransferFuture::new(self, Words::Transfer { read, write }).await;
+        Ok(())
+    }
+
+    /// Sends all words, writing the replies back into `words`
+    async fn transfer_in_place(
+        &mut self,
+        words: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        TransferFuture::new(self, Words::InPlace(words)).await;
+        Ok(())
+    }
+
+    /// Waits until the SPI master is idle
+    ///
+    /// The other methods only resolve, once the last word has been received,
+    /// so this only ever waits for the end of the last word's frame.
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        while !self.is_master_idle() {}
+        Ok(())
+    }
+}
+
+/// The buffers of a transfer, for each of the `SpiBus` methods
+enum Words<'r> {
+    Read(&'r mut [u8]),
+    Write(&'r [u8]),
+    Transfer { read: &'r mut [u8], write: &'r [u8] },
+    InPlace(&'r mut [u8]),
+}
+
+impl Words<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Read(words) => words.len(),
+            Self::Write(words) => words.len(),
+            Self::Transfer { read, write } => read.len().max(write.len()),
+            Self::InPlace(words) => words.len(),
+        }
+    }
+
+    fn word_to_send(&self, i: usize) -> u8 {
+        let word = match self {
+            Self::Read(_) => None,
+            Self::Write(words) => words.get(i),
+            Self::Transfer { write, .. } => write.get(i),
+            Self::InPlace(words) => words.get(i),
+        };
+
+        word.copied().unwrap_or(0xff)
+    }
+
+    fn store_received(&mut self, i: usize, word: u8) {
+        let slot = match self {
+            Self::Read(words) => words.get_mut(i),
+            Self::Write(_) => None,
+            Self::Transfer { read, .. } => read.get_mut(i),
+            Self::InPlace(words) => words.get_mut(i),
+        };
+
+        if let Some(slot) = slot {
+            *slot = word;
+        }
+    }
+}
+
+struct TransferFuture<'r, I> {
+    spi: &'r mut SPI<I, Enabled<Master>>,
+    words: Words<'r>,
+    sent: usize,
+    received: usize,
+}
+
+impl<'r, I> TransferFuture<'r, I> {
+    fn new(spi: &'r mut SPI<I, Enabled<Master>>, words: Words<'r>) -> Self {
+        Self {
+            spi,
+            words,
+            sent: 0,
+            received: 0,
+        }
+    }
+}
+
+impl<I> Future for TransferFuture<'_, I>
+where
+    I: Instance,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Register first, so an interrupt right after the checks below can't
+        // get lost.
+        I::waker().register(cx.waker());
+
+        let this = self.get_mut();
+        let len = this.words.len();
+
+        loop {
+            if this.received < this.sent {
+                if let Ok(word) = this.spi.read() {
+                    this.words.store_received(this.received, word);
+                    this.received += 1;
+                    continue;
+                }
+            }
+            if this.sent < len && this.sent == this.received {
+                let word = this.words.word_to_send(this.sent);
+                if this.spi.send(word).is_ok() {
+                    this.sent += 1;
+                    continue;
+                }
+            }
+
+            break;
+        }
+
+        if this.received == len {
+            return Poll::Ready(());
+        }
+
+        // Only one word is in flight at any time, so we're either waiting for
+        // the reply, or for the transmitter to accept the next word.
+        this.spi.enable_interrupts(Interrupts {
+            rx_ready: this.received < this.sent,
+            tx_ready: this.received == this.sent,
+            ..Interrupts::default()
+        });
+
+        Poll::Pending
+    }
+}
@@ -1,10 +1,15 @@
 use core::ops::Deref;
 
 use crate::{
-    dma, pac, swm,
+    dma,
+    pac::{self, Interrupt},
+    swm,
     syscon::{self, clock_source::PeripheralClockSelector},
 };
 
+#[cfg(feature = "embedded-hal-1")]
+use crate::waker::WakerSlot;
+
 /// Implemented for all SPI instance
 pub trait Instance:
     private::Sealed
@@ -13,6 +18,9 @@ pub trait Instance:
     + syscon::ResetControl
     + PeripheralClockSelector
 {
+    /// The interrupt that is triggered for this SPI peripheral
+    const INTERRUPT: Interrupt;
+
     /// A pointer to this instance's register block
     const REGISTERS: *const pac::spi0::RegisterBlock;
 
@@ -30,20 +38,28 @@ pub trait Instance:
 
     /// The DMA channel used with this instance for transmitting
     type TxChannel: dma::channels::Instance;
+
+    /// The storage for the waker of the async API
+    #[cfg(feature = "embedded-hal-1")]
+    fn waker() -> &'static WakerSlot;
 }
 
 /// Implemented for slave select functions of a given SPI instance
-pub trait SlaveSelect<I>: private::Sealed {}
+pub trait SlaveSelect<I>: private::Sealed {
+    /// The index of this slave select function (0 for SSEL0, and so on)
+    const INDEX: u8;
+}
 
 macro_rules! instances {
     (
         $(
             $instance:ident,
             $clock_num:expr,
+            $interrupt:ident,
             $sck:ident,
             $mosi:ident,
             $miso:ident,
-            [$($ssel:ident),*],
+            [$($ssel:ident: $ssel_index:expr),*],
             $rx_channel:ident,
             $tx_channel:ident;
         )*
@@ -52,6 +68,7 @@ macro_rules! instances {
             impl private::Sealed for pac::$instance {}
 
             impl Instance for pac::$instance {
+                const INTERRUPT: Interrupt = Interrupt::$interrupt;
                 const REGISTERS: *const pac::spi0::RegisterBlock =
                     pac::$instance::ptr();
 
@@ -61,6 +78,12 @@ macro_rules! instances {
 
                 type RxChannel = dma::$rx_channel;
                 type TxChannel = dma::$tx_channel;
+
+                #[cfg(feature = "embedded-hal-1")]
+                fn waker() -> &'static WakerSlot {
+                    static WAKER: WakerSlot = WakerSlot::new();
+                    &WAKER
+                }
             }
 
             impl PeripheralClockSelector for pac::$instance {
@@ -70,7 +93,9 @@ macro_rules! instances {
             $(
                 impl private::Sealed for swm::$ssel {}
 
-                impl SlaveSelect<pac::$instance> for swm::$ssel {}
+                impl SlaveSelect<pac::$instance> for swm::$ssel {
+                    const INDEX: u8 = $ssel_index;
+                }
             )*
         )*
     };
@@ -78,25 +103,25 @@ macro_rules! instances {
 
 #[cfg(feature = "82x")]
 instances!(
-    SPI0, 9,
+    SPI0, 9, SPI0,
         SPI0_SCK, SPI0_MOSI, SPI0_MISO,
-        [SPI0_SSEL0, SPI0_SSEL1, SPI0_SSEL2, SPI0_SSEL3],
+        [SPI0_SSEL0: 0, SPI0_SSEL1: 1, SPI0_SSEL2: 2, SPI0_SSEL3: 3],
         Channel6, Channel7;
-    SPI1, 10,
+    SPI1, 10, SPI1,
         SPI1_SCK, SPI1_MOSI, SPI1_MISO,
-        [SPI1_SSEL0, SPI1_SSEL1],
+        [SPI1_SSEL0: 0, SPI1_SSEL1: 1],
         Channel8, Channel9;
 );
 
 #[cfg(feature = "845")]
 instances!(
-    SPI0, 9,
+    SPI0, 9, SPI0,
         SPI0_SCK, SPI0_MOSI, SPI0_MISO,
-        [SPI0_SSEL0, SPI0_SSEL1, SPI0_SSEL2, SPI0_SSEL3],
+        [SPI0_SSEL0: 0, SPI0_SSEL1: 1, SPI0_SSEL2: 2, SPI0_SSEL3: 3],
         Channel10, Channel11;
-    SPI1, 10,
+    SPI1, 10, SPI1,
         SPI1_SCK, SPI1_MOSI, SPI1_MISO,
-        [SPI1_SSEL0, SPI1_SSEL1],
+        [SPI1_SSEL0: 0, SPI1_SSEL1: 1],
         Channel12, Channel13;
 );
 
@@ -57,25 +57,109 @@
 //!     .expect("Transfer shouldn't fail");
 //! ```
 //!
+//! # Slave select
+//!
+//! Each SPI instance has multiple hardware slave select functions (SSEL0 to
+//! SSEL3 for SPI0, SSEL0 and SSEL1 for SPI1). Each of them can be assigned to a
+//! pin, and passed to [`Device::new`], together with the SPI master. The SWM
+//! function's type state makes sure only assigned functions can be used. The
+//! [`Device`] then selects its slave for the duration of each transaction.
+//!
+//! To talk to multiple devices on the same bus, use the [`shared`] module
+//! instead.
+//!
+//! ``` no_run
+//! # use lpc8xx_hal::{prelude::*, Peripherals, spi};
+//! #
+//! # let mut p  = Peripherals::take().unwrap();
+//! # let mut swm = p.SWM.split();
+//! # let mut syscon = p.SYSCON.split();
+//! #
+//! # #[cfg(feature = "82x")]
+//! # let mut swm_handle = swm.handle;
+//! # #[cfg(feature = "845")]
+//! # let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//! #
+//! # let (spi0_sck, _) = swm.movable_functions.spi0_sck.assign(
+//! #     p.pins.pio0_13.into_swm_pin(),
+//! #     &mut swm_handle,
+//! # );
+//! # let (spi0_mosi, _) = swm
+//! #     .movable_functions
+//! #     .spi0_mosi
+//! #     .assign(p.pins.pio0_14.into_swm_pin(), &mut swm_handle);
+//! # let (spi0_miso, _) = swm
+//! #     .movable_functions
+//! #     .spi0_miso
+//! #     .assign(p.pins.pio0_15.into_swm_pin(), &mut swm_handle);
+//! #
+//! # #[cfg(feature = "82x")]
+//! # let spi_clock = spi::Clock::new(&(), 0);
+//! # #[cfg(feature = "845")]
+//! # let spi_clock = spi::Clock::new(&syscon.iosc, 0);
+//! #
+//! # let spi = p.SPI0.enable_as_master(
+//! #     &spi_clock,
+//! #     &mut syscon.handle,
+//! #     embedded_hal::spi::MODE_0,
+//! #     spi0_sck,
+//! #     spi0_mosi,
+//! #     spi0_miso,
+//! # );
+//! #
+//! use embedded_hal_1::spi::SpiDevice;
+//!
+//! let (spi0_ssel0, _) = swm
+//!     .movable_functions
+//!     .spi0_ssel0
+//!     .assign(p.pins.pio0_16.into_swm_pin(), &mut swm_handle);
+//!
+//! let mut flash = spi::Device::new(spi, spi0_ssel0);
+//!
+//! flash.write(&[0x9f, 0x00, 0x00])
+//!     .expect("Write shouldn't fail");
+//! ```
+//!
+//! The [`Device`] and the [`shared`] module are only available, if the
+//! `embedded-hal-1` feature is enabled.
+//!
 //! Please refer to the [examples in the repository] for more example code.
 //!
 //! [`SPI`]: struct.SPI.html
+//! [`Device`]: struct.Device.html
+//! [`Device::new`]: struct.Device.html#method.new
+//! [`shared`]: shared/index.html
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
 mod clock;
+#[cfg(feature = "embedded-hal-1")]
+mod device;
 mod dma;
+mod flags;
 mod instances;
 mod interrupts;
 mod peripheral;
 
+#[cfg(feature = "embedded-hal-1")]
+pub mod futures;
+#[cfg(feature = "embedded-hal-1")]
+pub mod shared;
+
 pub use self::{
-    clock::{Clock, ClockSource},
-    dma::Transfer,
+    clock::{Clock, ClockSource, Divider},
+    dma::{Resources, Transfer, WriteResources, WriteTransfer},
+    flags::Flag,
     instances::{Instance, SlaveSelect},
     interrupts::Interrupts,
-    peripheral::{Master, Slave, SPI},
+    peripheral::{
+        BitOrder, Delays, Master, RxOverrunError, Slave, SlaveSelectPolarity,
+        TxUnderrunError, WriteControl, SPI,
+    },
 };
 
+#[cfg(feature = "embedded-hal-1")]
+pub use self::device::Device;
+
 pub use crate::embedded_hal::spi::{
     Mode, Phase, Polarity, MODE_0, MODE_1, MODE_2, MODE_3,
 };
@@ -5,11 +5,17 @@ use embedded_hal::spi::{FullDuplex, Mode, Phase, Polarity};
 use crate::{
     dma::{self, transfer::state::Ready},
     init_state::{Disabled, Enabled},
-    pac::spi0::cfg::MASTER_A,
+    pac::{
+        spi0::cfg::{self, MASTER_A},
+        NVIC,
+    },
     swm, syscon,
 };
 
-use super::{Clock, ClockSource, Instance, Interrupts, SlaveSelect, Transfer};
+use super::{
+    Clock, ClockSource, Flag, Instance, Interrupts, Resources, SlaveSelect,
+    Transfer, WriteResources, WriteTransfer,
+};
 
 /// Interface to a SPI peripheral
 ///
@@ -24,11 +30,20 @@ use super::{Clock, ClockSource, Instance, Interrupts, SlaveSelect, Transfer};
 /// - [`embedded_hal::blocking::spi::Transfer`] for synchronous transfers
 /// - [`embedded_hal::blocking::spi::Write`] for synchronous writes
 ///
+/// From embedded-hal 1.0, [`embedded_hal_1::spi::SpiBus`] is implemented for
+/// synchronous transfers, and [`embedded_hal_nb::spi::FullDuplex`] for
+/// non-blocking ones. [`embedded_hal_async::spi::SpiBus`] is implemented for
+/// async transfers (see the [`futures`] module).
+///
 /// [`Peripherals`]: ../struct.Peripherals.html
 /// [module documentation]: index.html
 /// [`embedded_hal::spi::FullDuplex`]: #impl-FullDuplex%3Cu8%3E
 /// [`embedded_hal::blocking::spi::Transfer`]: #impl-Transfer%3CW%3E
 /// [`embedded_hal::blocking::spi::Write`]: #impl-Write%3CW%3E
+/// [`embedded_hal_1::spi::SpiBus`]: #impl-SpiBus%3Cu8%3E
+/// [`embedded_hal_nb::spi::FullDuplex`]: #impl-FullDuplex%3Cu8%3E-1
+/// [`embedded_hal_async::spi::SpiBus`]: #impl-SpiBus%3Cu8%3E-1
+/// [`futures`]: futures/index.html
 pub struct SPI<I, State> {
     spi: I,
     _state: State,
@@ -73,18 +88,54 @@ where
     where
         CLOCK: ClockSource,
     {
-        self.enable::<CLOCK>(syscon);
-
-        self.spi
-            .div
-            .write(|w| unsafe { w.divval().bits(clock.divval) });
+        self.enable_master(clock, syscon, mode)
+    }
 
-        self.configure(mode, MASTER_A::MASTER_MODE);
+    /// Enable the SPI peripheral in master mode, without a MISO pin
+    ///
+    /// Works like [`enable_as_master`], except that no MISO pin is required.
+    /// This is intended for write-only slaves, like many displays. Use the
+    /// blocking or async `write` method, or [`write_all`] to send data. Any
+    /// received data is meaningless.
+    ///
+    /// [`enable_as_master`]: #method.enable_as_master
+    /// [`write_all`]: #method.write_all
+    pub fn enable_as_master_write_only<SckPin, MosiPin, CLOCK>(
+        self,
+        clock: &Clock<CLOCK>,
+        syscon: &mut syscon::Handle,
+        mode: Mode,
+        _sck: swm::Function<I::Sck, swm::state::Assigned<SckPin>>,
+        _mosi: swm::Function<I::Mosi, swm::state::Assigned<MosiPin>>,
+    ) -> SPI<I, Enabled<Master>>
+    where
+        CLOCK: ClockSource,
+    {
+        self.enable_master(clock, syscon, mode)
+    }
 
-        SPI {
-            spi: self.spi,
-            _state: Enabled(Master),
-        }
+    /// Enable the SPI peripheral in master mode, without a MOSI pin
+    ///
+    /// Works like [`enable_as_master`], except that no MOSI pin is required.
+    /// This is intended for read-only slaves, like some sensors. Use
+    /// [`read_into`] or [`read_all`] to receive data. The data that is sent is
+    /// not output on any pin.
+    ///
+    /// [`enable_as_master`]: #method.enable_as_master
+    /// [`read_into`]: #method.read_into
+    /// [`read_all`]: #method.read_all
+    pub fn enable_as_master_read_only<SckPin, MisoPin, CLOCK>(
+        self,
+        clock: &Clock<CLOCK>,
+        syscon: &mut syscon::Handle,
+        mode: Mode,
+        _sck: swm::Function<I::Sck, swm::state::Assigned<SckPin>>,
+        _miso: swm::Function<I::Miso, swm::state::Assigned<MisoPin>>,
+    ) -> SPI<I, Enabled<Master>>
+    where
+        CLOCK: ClockSource,
+    {
+        self.enable_master(clock, syscon, mode)
     }
 
     /// Enable the SPI peripheral in slave mode
@@ -121,6 +172,29 @@ where
         }
     }
 
+    fn enable_master<CLOCK>(
+        self,
+        clock: &Clock<CLOCK>,
+        syscon: &mut syscon::Handle,
+        mode: Mode,
+    ) -> SPI<I, Enabled<Master>>
+    where
+        CLOCK: ClockSource,
+    {
+        self.enable::<CLOCK>(syscon);
+
+        self.spi
+            .div
+            .write(|w| unsafe { w.divval().bits(clock.divval) });
+
+        self.configure(mode, MASTER_A::MASTER_MODE);
+
+        SPI {
+            spi: self.spi,
+            _state: Enabled(Master),
+        }
+    }
+
     fn enable<C>(&self, syscon: &mut syscon::Handle)
     where
         C: ClockSource,
@@ -131,22 +205,7 @@ where
 
     fn configure(&self, mode: Mode, master: MASTER_A) {
         self.spi.cfg.write(|w| {
-            match mode.polarity {
-                Polarity::IdleHigh => {
-                    w.cpol().high();
-                }
-                Polarity::IdleLow => {
-                    w.cpol().low();
-                }
-            }
-            match mode.phase {
-                Phase::CaptureOnFirstTransition => {
-                    w.cpha().clear_bit();
-                }
-                Phase::CaptureOnSecondTransition => {
-                    w.cpha().set_bit();
-                }
-            }
+            apply_mode(w, mode);
             w.master().variant(master);
             w.enable().enabled();
             w
@@ -167,7 +226,7 @@ where
     }
 }
 
-impl<I, Mode> SPI<I, Enabled<Mode>>
+impl<I, MasterOrSlave> SPI<I, Enabled<MasterOrSlave>>
 where
     I: Instance,
 {
@@ -187,6 +246,39 @@ where
         interrupts.disable(&self.spi);
     }
 
+    /// Query whether the provided flag is set
+    ///
+    /// Flags that need to be reset by software will be reset by this operation.
+    pub fn is_flag_set(&self, flag: Flag) -> bool {
+        flag.is_set::<I>()
+    }
+
+    /// Enable interrupts for this instance in the NVIC
+    ///
+    /// This only enables the interrupts in the NVIC. It doesn't enable any
+    /// specific interrupt in this SPI instance.
+    pub fn enable_in_nvic(&mut self) {
+        // Safe, because there's no critical section here that this could
+        // interfere with.
+        unsafe { NVIC::unmask(I::INTERRUPT) };
+    }
+
+    /// Disable interrupts for this instance in the NVIC
+    ///
+    /// This only disables the interrupts in the NVIC. It doesn't change
+    /// anything about the interrupt configuration within this SPI instance.
+    pub fn disable_in_nvic(&mut self) {
+        NVIC::mask(I::INTERRUPT);
+    }
+
+    /// Clear's this instance's interrupt pending flag in the NVIC
+    ///
+    /// This only clears the interrupt's pending flag in the NVIC. It does not
+    /// affect any of the interrupt-related flags in the peripheral.
+    pub fn clear_nvic_pending(&mut self) {
+        NVIC::unpend(I::INTERRUPT);
+    }
+
     /// Indicates whether the SPI instance is ready to receive
     ///
     /// Corresponds to the RXRDY flag in the STAT register.
@@ -232,6 +324,86 @@ where
         self.spi.stat.read().mstidle().bit_is_set()
     }
 
+    /// Change the SPI mode
+    ///
+    /// Changes clock polarity and phase. This allows talking to slaves that
+    /// use different modes on the same bus.
+    ///
+    /// Should only be called while no transfer is ongoing. Does nothing, if
+    /// `mode` is already configured.
+    pub fn set_mode(&mut self, mode: Mode) {
+        if read_mode(&self.spi.cfg.read()) == mode {
+            return;
+        }
+
+        // CFG must only be changed while the peripheral is disabled.
+        self.spi.cfg.modify(|_, w| w.enable().disabled());
+        self.spi.cfg.modify(|_, w| {
+            apply_mode(w, mode);
+            w
+        });
+        self.spi.cfg.modify(|_, w| w.enable().enabled());
+    }
+
+    /// Select the bit order
+    ///
+    /// Data is sent and received most significant bit first by default. Some
+    /// slaves expect the least significant bit first instead.
+    ///
+    /// Should only be called while no transfer is ongoing.
+    pub fn set_bit_order(&mut self, order: BitOrder) {
+        // CFG must only be changed while the peripheral is disabled.
+        self.spi.cfg.modify(|_, w| w.enable().disabled());
+        self.spi.cfg.modify(|_, w| match order {
+            BitOrder::MsbFirst => w.lsbf().clear_bit(),
+            BitOrder::LsbFirst => w.lsbf().set_bit(),
+        });
+        self.spi.cfg.modify(|_, w| w.enable().enabled());
+    }
+
+    /// Enable or disable loopback mode
+    ///
+    /// In loopback mode, MOSI is internally connected to MISO. Should only be
+    /// called while no transfer is ongoing.
+    pub(crate) fn set_loopback(&mut self, loopback: bool) {
+        // CFG must only be changed while the peripheral is disabled.
+        self.spi.cfg.modify(|_, w| w.enable().disabled());
+        self.spi.cfg.modify(|_, w| w.loop_().bit(loopback));
+        self.spi.cfg.modify(|_, w| w.enable().enabled());
+    }
+
+    /// Select the polarity of a slave select signal
+    ///
+    /// Slave select signals are active low by default. Some slaves, like
+    /// latching shift registers, require an active-high slave select signal.
+    ///
+    /// `Ssel` selects the slave select signal, for example
+    /// `swm::SPI0_SSEL1`.
+    ///
+    /// Should only be called while no transfer is ongoing.
+    pub fn set_slave_select_polarity<Ssel>(
+        &mut self,
+        polarity: SlaveSelectPolarity,
+    ) where
+        Ssel: SlaveSelect<I>,
+    {
+        let active_high = match polarity {
+            SlaveSelectPolarity::ActiveLow => false,
+            SlaveSelectPolarity::ActiveHigh => true,
+        };
+
+        // CFG must only be changed while the peripheral is disabled.
+        self.spi.cfg.modify(|_, w| w.enable().disabled());
+        // `Ssel::INDEX` is between 0 and 3.
+        self.spi.cfg.modify(|_, w| match Ssel::INDEX {
+            0 => w.spol0().bit(active_high),
+            1 => w.spol1().bit(active_high),
+            2 => w.spol2().bit(active_high),
+            _ => w.spol3().bit(active_high),
+        });
+        self.spi.cfg.modify(|_, w| w.enable().enabled());
+    }
+
     /// Disable the SPI peripheral
     ///
     /// This method is only available, if `SPI` is in the [`Enabled`] state.
@@ -262,17 +434,269 @@ where
     /// Sends all words in the provided buffer, writing the replies back into
     /// it.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    /// Returns an error, if the length of `buffer` is 0 or larger than 1024.
+    /// The error is returned together with all resources that were passed to
+    /// this method.
     pub fn transfer_all(
         self,
         buffer: &'static mut [u8],
         rx_channel: dma::Channel<I::RxChannel, Enabled>,
         tx_channel: dma::Channel<I::TxChannel, Enabled>,
-    ) -> Transfer<Ready, I> {
+    ) -> Result<
+        Transfer<Ready, I>,
+        (dma::transfer::InvalidTransfer, Resources<I>),
+    > {
         Transfer::new(self, buffer, rx_channel, tx_channel)
     }
+
+    /// Start an SPI write using DMA
+    ///
+    /// Sends all words in the provided buffer. Received data is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if the length of `buffer` is 0 or larger than 1024.
+    /// The error is returned together with all resources that were passed to
+    /// this method.
+    pub fn write_all(
+        self,
+        buffer: &'static [u8],
+        channel: dma::Channel<I::TxChannel, Enabled>,
+    ) -> Result<
+        WriteTransfer<Ready, I>,
+        (dma::transfer::InvalidTransfer, WriteResources<I>),
+    > {
+        WriteTransfer::new(self, buffer, channel)
+    }
+
+    /// Start an SPI read using DMA
+    ///
+    /// Fills the provided buffer with received words. Sends `0xff` while
+    /// receiving, which is done by overwriting `buffer` with that value, before
+    /// starting a regular transfer (see [`transfer_all`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if the length of `buffer` is 0 or larger than 1024.
+    /// The error is returned together with all resources that were passed to
+    /// this method.
+    ///
+    /// [`transfer_all`]: #method.transfer_all
+    pub fn read_all(
+        self,
+        buffer: &'static mut [u8],
+        rx_channel: dma::Channel<I::RxChannel, Enabled>,
+        tx_channel: dma::Channel<I::TxChannel, Enabled>,
+    ) -> Result<
+        Transfer<Ready, I>,
+        (dma::transfer::InvalidTransfer, Resources<I>),
+    > {
+        for word in buffer.iter_mut() {
+            *word = 0xff;
+        }
+
+        self.transfer_all(buffer, rx_channel, tx_channel)
+    }
+
+    /// Change the clock configuration of the enabled SPI master
+    ///
+    /// Waits until the master is idle, then selects the clock source and
+    /// updates the clock divider. This allows talking to slaves with different
+    /// maximum clock rates on the same bus.
+    pub fn set_clock<C>(
+        &mut self,
+        clock: &Clock<C>,
+        syscon: &mut syscon::Handle,
+    ) where
+        C: ClockSource,
+    {
+        while !self.is_master_idle() {}
+
+        C::select(&self.spi, syscon);
+        self.spi
+            .div
+            .write(|w| unsafe { w.divval().bits(clock.divval) });
+    }
+
+    /// Configure the delays around slave select and between words
+    ///
+    /// Some slaves require guaranteed setup and hold times around the slave
+    /// select signal. See [`Delays`] for the available delays. All delays are
+    /// 0 by default.
+    ///
+    /// Should only be called while no transfer is ongoing.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any delay is larger than 15.
+    ///
+    /// [`Delays`]: struct.Delays.html
+    pub fn set_delays(&mut self, delays: Delays) {
+        assert!(delays.pre_delay <= 15);
+        assert!(delays.post_delay <= 15);
+        assert!(delays.frame_delay <= 15);
+        assert!(delays.transfer_delay <= 15);
+
+        self.spi.dly.write(|w| unsafe {
+            // Sound, as we've checked that all values fit into their fields.
+            w.pre_delay().bits(delays.pre_delay);
+            w.post_delay().bits(delays.post_delay);
+            w.frame_delay().bits(delays.frame_delay);
+            w.transfer_delay().bits(delays.transfer_delay)
+        });
+    }
+
+    /// Assert a slave select signal
+    ///
+    /// By default, the SPI master asserts all slave select signals during each
+    /// transfer. After calling this method, the slave select signal `Ssel`
+    /// stays asserted across all following transfers, until [`deselect`] is
+    /// called. This is required by protocols that need the slave select signal
+    /// held across multiple logical transfers.
+    ///
+    /// `Ssel` selects the slave select signal, for example
+    /// `swm::SPI0_SSEL1`.
+    ///
+    /// Please note that the SPI master only drives slave select signals that
+    /// are assigned to a pin.
+    ///
+    /// [`deselect`]: #method.deselect
+    pub fn select<Ssel>(&mut self)
+    where
+        Ssel: SlaveSelect<I>,
+    {
+        self.set_slave_select(Ssel::INDEX, true);
+    }
+
+    /// Deassert a slave select signal
+    ///
+    /// Waits until all data has been sent, ends the current transfer, then
+    /// deasserts the slave select signal `Ssel`. See [`select`] for more
+    /// information.
+    ///
+    /// [`select`]: #method.select
+    pub fn deselect<Ssel>(&mut self)
+    where
+        Ssel: SlaveSelect<I>,
+    {
+        self.end_transfer();
+        self.set_slave_select(Ssel::INDEX, false);
+    }
+
+    /// Receive words, while sending dummy data
+    ///
+    /// Fills `words` with received data, sending `0xff` for each word. The
+    /// next dummy word is queued while the current one is still being
+    /// transferred, so there's no gap between words.
+    pub fn read_into(&mut self, words: &mut [u8]) {
+        let mut sent = 0;
+        let mut received = 0;
+
+        while received < words.len() {
+            // Keep at most two words in flight: one in the shift register, one
+            // waiting in TXDAT.
+            if sent < words.len()
+                && sent - received < 2
+                && self.is_ready_to_transmit()
+            {
+                self.spi.txdat.write(|w| unsafe { w.data().bits(0xff) });
+                sent += 1;
+            }
+
+            if self.is_ready_to_receive() {
+                words[received] = self.spi.rxdat.read().rxdat().bits() as u8;
+                received += 1;
+            }
+        }
+    }
+
+    /// Send words, controlling what happens after the last one
+    ///
+    /// Sends `words`, while ignoring received data, and applies `control` to
+    /// the last word. This allows controlling exactly when the slave select
+    /// signal is deasserted, and when the frame delay is inserted, which is
+    /// required by slaves that interpret slave select edges as command
+    /// boundaries.
+    ///
+    /// Returns, once all words have been sent.
+    pub fn write_with_control(&mut self, words: &[u8], control: WriteControl) {
+        let (&last, words) = match words.split_last() {
+            Some(split) => split,
+            None => return,
+        };
+
+        self.set_rx_ignore(true);
+
+        for &word in words {
+            while !self.is_ready_to_transmit() {}
+            self.spi
+                .txdat
+                .write(|w| unsafe { w.data().bits(word as u16) });
+        }
+
+        while !self.is_ready_to_transmit() {}
+
+        // TXDATCTL has the same layout as TXCTL, besides the data field, so we
+        // start from the current control bits, to keep RXIGNORE, slave
+        // select, and data length.
+        let txctl = self.spi.txctl.read().bits();
+        self.spi.txdatctl.write(|w| {
+            // Sound, as the value comes from TXCTL, and the data field is
+            // overwritten below.
+            unsafe { w.bits(txctl) };
+
+            // Sound, as all `u8` values are valid for the data field.
+            unsafe { w.txdat().bits(last as u16) };
+            w.eot().bit(control.end_of_transfer);
+            w.eof().bit(control.end_of_frame)
+        });
+
+        while !self.is_ready_to_transmit() {}
+        while !self.is_master_idle() {}
+
+        // Writing TXDATCTL also updates TXCTL. Clear EOT and EOF again, or
+        // they would apply to every word written to TXDAT from now on.
+        self.spi
+            .txctl
+            .modify(|_, w| w.eot().clear_bit().eof().clear_bit());
+
+        self.set_rx_ignore(false);
+    }
+
+    fn write_ignoring_rx(&mut self, words: &[u8]) {
+        self.write_with_control(words, WriteControl::default());
+    }
+
+    pub(super) fn set_rx_ignore(&mut self, ignore: bool) {
+        self.spi.txctl.modify(|_, w| w.rxignore().bit(ignore));
+    }
+
+    pub(super) fn set_slave_select(&mut self, index: u8, asserted: bool) {
+        // TXSSEL0 to TXSSEL3 are active low.
+        let not_asserted = !asserted;
+
+        // `index` comes from `SlaveSelect::INDEX`, which is between 0 and 3.
+        self.spi.txctl.modify(|_, w| match index {
+            0 => w.txssel0_n().bit(not_asserted),
+            1 => w.txssel1_n().bit(not_asserted),
+            2 => w.txssel2_n().bit(not_asserted),
+            _ => w.txssel3_n().bit(not_asserted),
+        });
+    }
+
+    pub(super) fn end_transfer(&mut self) {
+        // Wait until the last word has been moved into the shift register,
+        // then end the transfer after it has been sent.
+        while !self.is_ready_to_transmit() {}
+
+        // All other writable bits are write-1-to-clear, so this only ends the
+        // transfer.
+        self.spi.stat.write(|w| w.endtransfer().set_bit());
+
+        while !self.is_master_idle() {}
+    }
 }
 
 impl<I> SPI<I, Enabled<Slave>>
@@ -363,9 +787,128 @@ impl<I: Instance> embedded_hal::blocking::spi::transfer::Default<u8>
 {
 }
 
-impl<I: Instance> embedded_hal::blocking::spi::write::Default<u8>
+impl<I: Instance> embedded_hal::blocking::spi::Write<u8>
+    for SPI<I, Enabled<Master>>
+{
+    type Error = Infallible;
+
+    /// Sends all words, ignoring the received data
+    ///
+    /// Uses RXIGNORE, so the master doesn't need to wait for each reply.
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.write_ignoring_rx(words);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<I: Instance> embedded_hal_1::spi::ErrorType for SPI<I, Enabled<Master>> {
+    type Error = Infallible;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<I: Instance> embedded_hal_1::spi::SpiBus<u8> for SPI<I, Enabled<Master>> {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.read_into(words);
+        Ok(())
+    }
+
+    /// Sends all words, ignoring the received data
+    ///
+    /// Uses RXIGNORE, so the master doesn't need to wait for each reply.
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.write_ignoring_rx(words);
+        Ok(())
+    }
+
+    /// Sends `write`, while receiving into `read`
+    ///
+    /// If `read` is longer than `write`, `0xff` is sent for the remaining
+    /// words. If `write` is longer than `read`, the remaining received words
+    /// are discarded.
+    fn transfer(
+        &mut self,
+        read: &mut [u8],
+        write: &[u8],
+    ) -> Result<(), Self::Error> {
+        for i in 0..read.len().max(write.len()) {
+            let word = write.get(i).copied().unwrap_or(0xff);
+            nb::block!(FullDuplex::send(self, word))?;
+
+            let word = nb::block!(FullDuplex::read(self))?;
+            if let Some(slot) = read.get_mut(i) {
+                *slot = word;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transfer_in_place(
+        &mut self,
+        words: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        for word in words {
+            nb::block!(FullDuplex::send(self, *word))?;
+            *word = nb::block!(FullDuplex::read(self))?;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        while !self.is_master_idle() {}
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<I: Instance> embedded_hal_nb::spi::FullDuplex<u8>
     for SPI<I, Enabled<Master>>
 {
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        FullDuplex::read(self)
+    }
+
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        FullDuplex::send(self, word)
+    }
+}
+
+fn apply_mode(w: &mut cfg::W, mode: Mode) {
+    match mode.polarity {
+        Polarity::IdleHigh => {
+            w.cpol().high();
+        }
+        Polarity::IdleLow => {
+            w.cpol().low();
+        }
+    }
+    match mode.phase {
+        Phase::CaptureOnFirstTransition => {
+            w.cpha().clear_bit();
+        }
+        Phase::CaptureOnSecondTransition => {
+            w.cpha().set_bit();
+        }
+    }
+}
+
+fn read_mode(r: &cfg::R) -> Mode {
+    let polarity = if r.cpol().is_high() {
+        Polarity::IdleHigh
+    } else {
+        Polarity::IdleLow
+    };
+    let phase = if r.cpha().bit_is_set() {
+        Phase::CaptureOnSecondTransition
+    } else {
+        Phase::CaptureOnFirstTransition
+    };
+
+    Mode { polarity, phase }
 }
 
 /// Indicates that SPI is in master mode
@@ -382,6 +925,76 @@ pub struct Master;
 /// [`SPI`]: struct.SPI.html
 pub struct Slave;
 
+/// The order in which the bits of a word are transferred
+///
+/// Used by [`SPI::set_bit_order`].
+///
+/// [`SPI::set_bit_order`]: struct.SPI.html#method.set_bit_order
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BitOrder {
+    /// Most significant bit first (the default)
+    MsbFirst,
+
+    /// Least significant bit first
+    LsbFirst,
+}
+
+/// Controls what happens after the last word of a write
+///
+/// Used by [`SPI::write_with_control`].
+///
+/// [`SPI::write_with_control`]: struct.SPI.html#method.write_with_control
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WriteControl {
+    /// End the transfer after the last word
+    ///
+    /// Deasserts the slave select signal after the last word has been sent.
+    pub end_of_transfer: bool,
+
+    /// End the frame after the last word
+    ///
+    /// Inserts the frame delay after the last word. See [`Delays`].
+    ///
+    /// [`Delays`]: struct.Delays.html
+    pub end_of_frame: bool,
+}
+
+/// The polarity of a slave select signal
+///
+/// Used by [`SPI::set_slave_select_polarity`].
+///
+/// [`SPI::set_slave_select_polarity`]: struct.SPI.html#method.set_slave_select_polarity
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlaveSelectPolarity {
+    /// The signal is low while asserted (the default)
+    ActiveLow,
+
+    /// The signal is high while asserted
+    ActiveHigh,
+}
+
+/// Delays inserted by the SPI master
+///
+/// Each delay is specified as a number of SPI clock cycles, from 0 to 15.
+///
+/// Used by [`SPI::set_delays`].
+///
+/// [`SPI::set_delays`]: struct.SPI.html#method.set_delays
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Delays {
+    /// Delay between the assertion of slave select and the start of data
+    pub pre_delay: u8,
+
+    /// Delay between the end of data and the deassertion of slave select
+    pub post_delay: u8,
+
+    /// Delay between two words that are sent without deasserting slave select
+    pub frame_delay: u8,
+
+    /// Minimum time slave select is deasserted between two transfers
+    pub transfer_delay: u8,
+}
+
 /// Receiver Overrun Error
 #[derive(Debug)]
 pub struct RxOverrunError;
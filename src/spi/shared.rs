@@ -0,0 +1,201 @@
+//! Sharing one SPI master between multiple device drivers
+//!
+//! [`SharedBus`] wraps an enabled SPI master, so multiple [`SharedDevice`]
+//! handles can be created for it. Each handle owns one of the SPI instance's
+//! slave select functions and the SPI mode its device uses, and can be handed
+//! to a separate driver.
+//!
+//! All access to the bus happens within a critical section, so handles can
+//! also be used from interrupt handlers. Please note that this means that
+//! interrupts are disabled for the duration of each transaction.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use embedded_hal_1::spi::SpiDevice;
+//! use lpc8xx_hal::{
+//!     prelude::*,
+//!     spi::{self, shared::SharedBus},
+//!     Peripherals,
+//! };
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut swm    = p.SWM.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! let (spi0_sck, _) = swm.movable_functions.spi0_sck.assign(
+//!     p.pins.pio0_13.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (spi0_mosi, _) = swm.movable_functions.spi0_mosi.assign(
+//!     p.pins.pio0_14.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (spi0_miso, _) = swm.movable_functions.spi0_miso.assign(
+//!     p.pins.pio0_15.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (spi0_ssel0, _) = swm.movable_functions.spi0_ssel0.assign(
+//!     p.pins.pio0_16.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (spi0_ssel1, _) = swm.movable_functions.spi0_ssel1.assign(
+//!     p.pins.pio0_17.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//!
+//! #[cfg(feature = "82x")]
+//! let spi_clock = spi::Clock::new(&(), 0);
+//! #[cfg(feature = "845")]
+//! let spi_clock = spi::Clock::new(&syscon.iosc, 0);
+//!
+//! let spi = p.SPI0.enable_as_master(
+//!     &spi_clock,
+//!     &mut syscon.handle,
+//!     embedded_hal::spi::MODE_0,
+//!     spi0_sck,
+//!     spi0_mosi,
+//!     spi0_miso,
+//! );
+//!
+//! let bus = SharedBus::new(spi);
+//!
+//! let mut flash = bus.device(spi0_ssel0, embedded_hal::spi::MODE_0);
+//! let mut sensor = bus.device(spi0_ssel1, embedded_hal::spi::MODE_3);
+//!
+//! flash
+//!     .write(&[0x9f, 0x00, 0x00])
+//!     .expect("Write shouldn't fail");
+//! sensor.write(&[0x01]).expect("Write shouldn't fail");
+//! ```
+//!
+//! [`SharedBus`]: struct.SharedBus.html
+//! [`SharedDevice`]: struct.SharedDevice.html
+
+use core::{cell::RefCell, convert::Infallible};
+
+use cortex_m::interrupt::{self, Mutex};
+use embedded_hal::spi::Mode;
+use embedded_hal_1::spi::{ErrorType, Operation, SpiDevice};
+
+use crate::{init_state::Enabled, swm};
+
+use super::{device, Instance, Master, SlaveSelect, SPI};
+
+/// An SPI master that can be shared between multiple devices
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct SharedBus<I>(Mutex<RefCell<Option<SPI<I, Enabled<Master>>>>>);
+
+impl<I> SharedBus<I>
+where
+    I: Instance,
+{
+    /// Create a new `SharedBus`
+    pub fn new(spi: SPI<I, Enabled<Master>>) -> Self {
+        Self(Mutex::new(RefCell::new(Some(spi))))
+    }
+
+    /// Create a handle for a device on this bus
+    ///
+    /// The device is selected using `ssel`, and the bus is switched to `mode`
+    /// at the start of each of its transactions.
+    pub fn device<Ssel, SselPin>(
+        &self,
+        ssel: swm::Function<Ssel, swm::state::Assigned<SselPin>>,
+        mode: Mode,
+    ) -> SharedDevice<'_, I, Ssel, SselPin>
+    where
+        Ssel: SlaveSelect<I>,
+    {
+        // The SPI master asserts the slave select signal during every transfer,
+        // unless told otherwise.
+        self.lock(|spi| spi.set_slave_select(Ssel::INDEX, false));
+
+        SharedDevice {
+            bus: self,
+            ssel,
+            mode,
+        }
+    }
+
+    /// Return the SPI master
+    ///
+    /// As all [`SharedDevice`] handles borrow the bus, this can only be called
+    /// once all of them have been dropped.
+    ///
+    /// [`SharedDevice`]: struct.SharedDevice.html
+    pub fn free(self) -> SPI<I, Enabled<Master>> {
+        interrupt::free(|cs| self.0.borrow(cs).take())
+            .expect("SPI master is only taken by `free`")
+    }
+
+    fn lock<R>(&self, f: impl FnOnce(&mut SPI<I, Enabled<Master>>) -> R) -> R {
+        interrupt::free(|cs| {
+            let mut spi = self.0.borrow(cs).borrow_mut();
+            f(spi.as_mut().expect("SPI master is only taken by `free`"))
+        })
+    }
+}
+
+/// A handle for a device on a [`SharedBus`]
+///
+/// Created by [`SharedBus::device`].
+///
+/// # `embedded-hal` traits
+///
+/// - [`embedded_hal_1::spi::SpiDevice`]
+///
+/// [`SharedBus`]: struct.SharedBus.html
+/// [`SharedBus::device`]: struct.SharedBus.html#method.device
+/// [`embedded_hal_1::spi::SpiDevice`]: #impl-SpiDevice%3Cu8%3E
+pub struct SharedDevice<'bus, I, Ssel, SselPin> {
+    bus: &'bus SharedBus<I>,
+    ssel: swm::Function<Ssel, swm::state::Assigned<SselPin>>,
+    mode: Mode,
+}
+
+impl<I, Ssel, SselPin> SharedDevice<'_, I, Ssel, SselPin> {
+    /// Return the slave select function
+    pub fn free(self) -> swm::Function<Ssel, swm::state::Assigned<SselPin>> {
+        self.ssel
+    }
+}
+
+impl<I, Ssel, SselPin> ErrorType for SharedDevice<'_, I, Ssel, SselPin>
+where
+    I: Instance,
+{
+    type Error = Infallible;
+}
+
+impl<I, Ssel, SselPin> SpiDevice<u8> for SharedDevice<'_, I, Ssel, SselPin>
+where
+    I: Instance,
+    Ssel: SlaveSelect<I>,
+{
+    /// Executes `operations` with this device selected
+    ///
+    /// Switches the bus to this device's SPI mode, then asserts the slave
+    /// select signal for the duration of the operations. The whole transaction
+    /// is executed within a critical section.
+    fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        let mode = self.mode;
+
+        self.bus.lock(|spi| {
+            spi.set_mode(mode);
+            device::transaction::<I, Ssel>(spi, operations)
+        })
+    }
+}
@@ -41,6 +41,58 @@ pub trait UnassignFunction<Function, Kind> {
     fn unassign(self) -> Self::Unassigned;
 }
 
+/// Internal trait used to assign functions to pins at runtime
+///
+/// This trait is an internal implementation detail and should neither be
+/// implemented nor used outside of LPC8xx HAL. Any changes to this trait won't
+/// be considered breaking changes.
+///
+/// Please refer to [`Function::assign_dynamic`] for the public API that uses
+/// this trait.
+///
+/// [`Function::assign_dynamic`]: crate::swm::Function::assign_dynamic
+pub trait AssignDynamic {
+    /// Internal method for assigning a function to a pin
+    ///
+    /// Returns `false`, if the function can't be assigned.
+    fn assign(state: &mut pins::state::DynamicSwm) -> bool;
+
+    /// Internal method for unassigning a function from a pin
+    fn unassign(state: &mut pins::state::DynamicSwm);
+}
+
+impl AssignDynamic for Input {
+    fn assign(state: &mut pins::state::DynamicSwm) -> bool {
+        match state.inputs.checked_add(1) {
+            Some(inputs) => {
+                state.inputs = inputs;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn unassign(state: &mut pins::state::DynamicSwm) {
+        state.inputs = state.inputs.saturating_sub(1);
+    }
+}
+
+impl AssignDynamic for Output {
+    fn assign(state: &mut pins::state::DynamicSwm) -> bool {
+        // Only one output function can be assigned to a pin.
+        if state.output {
+            return false;
+        }
+
+        state.output = true;
+        true
+    }
+
+    fn unassign(state: &mut pins::state::DynamicSwm) {
+        state.output = false;
+    }
+}
+
 impl<T, F, O, Is> AssignFunction<F, Input> for Pin<T, pins::state::Swm<O, Is>>
 where
     T: pins::Trait,
@@ -1,9 +1,9 @@
 use core::marker::PhantomData;
 
-use crate::pins::{self, Pin};
+use crate::pins::{self, GenericPin, Pin};
 
 use super::{
-    assignment::{AssignFunction, UnassignFunction},
+    assignment::{AssignDynamic, AssignFunction, UnassignFunction},
     function_kind::FunctionKind,
     handle::Handle,
     state::{Assigned, State, Unassigned},
@@ -198,6 +198,175 @@ impl<T, P> Function<T, Assigned<P>> {
 
         (function, pin.unassign())
     }
+
+    /// Move this function from one pin to another
+    ///
+    /// This method is only available, if the function could be unassigned from
+    /// `from` using [`unassign`], and then assigned to `to` using [`assign`].
+    /// Code attempting to call this method while these requirements are not
+    /// met, will not compile.
+    ///
+    /// Unlike calling [`unassign`] and [`assign`] one after the other, the
+    /// function is moved to the new pin with a single register write, so there
+    /// is no point in time where the function isn't assigned to any pin.
+    ///
+    /// Consumes this instance of `Function`, as well as the provided [`Pin`]s,
+    /// and returns new instances, with their states updated accordingly.
+    ///
+    /// # Examples
+    ///
+    /// ``` no_run
+    /// # use lpc8xx_hal::Peripherals;
+    /// #
+    /// # let p = Peripherals::take().unwrap();
+    /// #
+    /// # let mut swm = p.SWM.split();
+    /// # let mut syscon = p.SYSCON.split();
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let mut swm_handle = swm.handle;
+    /// # #[cfg(feature = "845")]
+    /// # let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    /// #
+    /// let (u0_txd, pio0_0) = swm.movable_functions.u0_txd.assign(
+    ///     p.pins.pio0_0.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// let (u0_txd, pio0_0, pio0_4) = u0_txd.reassign(
+    ///     pio0_0,
+    ///     p.pins.pio0_4.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    /// ```
+    ///
+    /// [`unassign`]: #method.unassign
+    /// [`assign`]: #method.assign
+    /// [`Pin`]: ../pins/struct.Pin.html
+    pub fn reassign<S, P2, S2>(
+        mut self,
+        from: Pin<P, S>,
+        mut to: Pin<P2, S2>,
+        swm: &mut Handle,
+    ) -> (
+        Function<T, Assigned<P2>>,
+        <Pin<P, S> as UnassignFunction<T, <T as FunctionTrait<P>>::Kind>>::Unassigned,
+        <Pin<P2, S2> as AssignFunction<T, <T as FunctionTrait<P2>>::Kind>>::Assigned,
+    )
+    where
+        T: FunctionTrait<P> + FunctionTrait<P2>,
+        P: pins::Trait,
+        S: pins::State,
+        P2: pins::Trait,
+        S2: pins::State,
+        Pin<P, S>: UnassignFunction<T, <T as FunctionTrait<P>>::Kind>,
+        Pin<P2, S2>: AssignFunction<T, <T as FunctionTrait<P2>>::Kind>,
+    {
+        // Assigning the function to the new pin overwrites the previous
+        // assignment, so we don't need to unassign it first.
+        FunctionTrait::<P2>::assign(&mut self.ty, &mut to.ty, swm);
+
+        let function = Function {
+            ty: self.ty,
+            _state: Assigned(PhantomData),
+        };
+
+        (function, from.unassign(), to.assign())
+    }
+}
+
+impl<T> Function<T, Unassigned>
+where
+    T: FunctionTrait<GenericPin> + DynamicFunction,
+    T::Kind: AssignDynamic,
+{
+    /// Assign this movable function to a pin that is selected at runtime
+    ///
+    /// Works like [`assign`], but checks at runtime whether the function can be
+    /// assigned to the pin, instead of at compile time. This allows choosing
+    /// the pin at runtime, for example based on a configuration value. Pins
+    /// can be prepared for this using [`Pin::into_dynamic_swm_pin`].
+    ///
+    /// Returns the function, if the pin already has an output function
+    /// assigned, as only one output function can be assigned to a pin. Also
+    /// returns the function, if the pin's count of input functions would
+    /// overflow.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::Peripherals;
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    /// let mut swm = p.SWM.split();
+    ///
+    /// #[cfg(feature = "82x")]
+    /// let mut swm_handle = swm.handle;
+    /// #[cfg(feature = "845")]
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// let mut pins = [
+    ///     p.pins.pio0_0.into_swm_pin().into_dynamic_swm_pin(),
+    ///     p.pins.pio0_4.into_swm_pin().into_dynamic_swm_pin(),
+    /// ];
+    ///
+    /// // Select the pin at runtime
+    /// let index = 1;
+    ///
+    /// let u0_txd = swm.movable_functions.u0_txd
+    ///     .assign_dynamic(&mut pins[index], &mut swm_handle);
+    /// ```
+    ///
+    /// [`assign`]: #method.assign
+    /// [`Pin::into_dynamic_swm_pin`]: ../pins/struct.Pin.html#method.into_dynamic_swm_pin
+    pub fn assign_dynamic(
+        mut self,
+        pin: &mut Pin<GenericPin, pins::state::DynamicSwm>,
+        swm: &mut Handle,
+    ) -> Result<Function<T, Assigned<GenericPin>>, Self> {
+        if !T::Kind::assign(&mut pin._state) {
+            return Err(self);
+        }
+
+        self.ty.assign(&mut pin.ty, swm);
+
+        Ok(Function {
+            ty: self.ty,
+            _state: Assigned(PhantomData),
+        })
+    }
+}
+
+impl<T> Function<T, Assigned<GenericPin>>
+where
+    T: FunctionTrait<GenericPin> + DynamicFunction,
+    T::Kind: AssignDynamic,
+{
+    /// Unassign this movable function from a pin that was selected at runtime
+    ///
+    /// Counterpart to [`assign_dynamic`]. Returns the function, if it is not
+    /// assigned to the provided pin.
+    ///
+    /// [`assign_dynamic`]: #method.assign_dynamic
+    pub fn unassign_dynamic(
+        mut self,
+        pin: &mut Pin<GenericPin, pins::state::DynamicSwm>,
+        swm: &mut Handle,
+    ) -> Result<Function<T, Unassigned>, Self> {
+        if !self.ty.is_assigned_to(&pin.ty, swm) {
+            return Err(self);
+        }
+
+        T::Kind::unassign(&mut pin._state);
+        self.ty.unassign(&mut pin.ty, swm);
+
+        Ok(Function {
+            ty: self.ty,
+            _state: Unassigned,
+        })
+    }
 }
 
 /// Implemented for all fixed and movable functions
@@ -225,5 +394,20 @@ pub trait FunctionTrait<P: pins::Trait> {
     fn unassign(&mut self, pin: &mut P, swm: &mut Handle);
 }
 
+/// Implemented for all movable functions
+///
+/// This trait is an internal implementation detail and should neither be
+/// implemented nor used outside of LPC8xx HAL. Any changes to this trait won't
+/// be considered breaking changes.
+///
+/// Please refer [`Function::unassign_dynamic`] for the public API that uses
+/// this trait.
+///
+/// [`Function::unassign_dynamic`]: struct.Function.html#method.unassign_dynamic
+pub trait DynamicFunction {
+    /// Internal method to check which pin a function is assigned to
+    fn is_assigned_to(&self, pin: &GenericPin, swm: &Handle) -> bool;
+}
+
 /// Used as a placeholder, to indicate that an SWM function is not available
 pub enum NotAvailable {}
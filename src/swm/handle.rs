@@ -16,7 +16,7 @@ use crate::{init_state, pac, syscon};
 /// [`swm::Parts`]: struct.Parts.html
 /// [module documentation]: index.html
 pub struct Handle<State = init_state::Enabled> {
-    pub(super) swm: pac::SWM0,
+    pub(crate) swm: pac::SWM0,
     _state: PhantomData<State>,
 }
 
@@ -20,10 +20,10 @@ mod movable_functions;
 mod peripheral;
 
 pub use self::{
-    assignment::{AssignFunction, UnassignFunction},
+    assignment::{AssignDynamic, AssignFunction, UnassignFunction},
     fixed_functions::*,
     function_kind::{Analog, FunctionKind, Input, Output},
-    functions::{Function, FunctionTrait, NotAvailable},
+    functions::{DynamicFunction, Function, FunctionTrait, NotAvailable},
     handle::Handle,
     movable_functions::*,
     peripheral::{Parts, SWM},
@@ -2,7 +2,7 @@ use crate::pins::{self, Trait as _};
 
 use super::{
     function_kind::{Input, Output},
-    functions::{Function, FunctionTrait},
+    functions::{DynamicFunction, Function, FunctionTrait},
     handle::Handle,
     state::Unassigned,
 };
@@ -51,8 +51,8 @@ macro_rules! movable_functions {
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_3 );
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_4 );
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_5 );
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_6 );
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_7 );
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_6 );
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_7 );
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_8 );
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_9 );
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_10);
@@ -61,44 +61,56 @@ macro_rules! movable_functions {
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_13);
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_14);
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_15);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_16);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_16);
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_17);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_18);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_19);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_20);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_21);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_22);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_18);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_19);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_20);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_21);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_22);
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_23);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_24);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_25);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_26);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_27);
-            impl_function!($type, $kind, $reg_name, $reg_field, PIO0_28);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_29);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_30);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_31);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_0 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_1 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_2 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_3 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_4 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_5 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_6 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_7 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_8 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_9 );
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_10);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_11);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_12);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_13);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_14);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_15);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_16);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_17);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_18);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_19);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_20);
-            #[cfg(feature = "845")] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_21);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_24);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_25);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_26);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_27);
+            #[cfg(not(feature = "20"))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_28);
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_29);
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_30);
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO0_31);
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_0 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_1 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_2 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_3 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_4 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_5 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_6 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_7 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_8 );
+            #[cfg(all(feature = "845", not(feature = "33")))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_9 );
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_10);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_11);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_12);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_13);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_14);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_15);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_16);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_17);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_18);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_19);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_20);
+            #[cfg(all(feature = "845", not(any(feature = "33", feature = "48"))))] impl_function!($type, $kind, $reg_name, $reg_field, PIO1_21);
+            impl_function!($type, $kind, $reg_name, $reg_field, GenericPin);
+
+            impl DynamicFunction for $type {
+                fn is_assigned_to(
+                    &self,
+                    pin: &pins::GenericPin,
+                    swm: &Handle,
+                ) -> bool {
+                    swm.swm.$reg_name.read().$reg_field().bits()
+                        == pin.id() | pin.port() << 5
+                }
+            }
         )*
     }
 }
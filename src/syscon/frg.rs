@@ -1,6 +1,7 @@
 //! The fractional generator (FRG), available on LPC845
 
 use crate::{
+    init_state,
     pac::{
         self,
         syscon::frg::{FRGCLKSEL, FRGDIV, FRGMULT},
@@ -16,13 +17,22 @@ pub use crate::pac::syscon::frg::frgclksel::SEL_A as Clock;
 /// Fractional generator
 ///
 /// Can be used as a clock source for serial peripherals.
-pub struct FRG<I: Instance> {
+///
+/// After reset, no clock is selected as the input for the FRG, so it doesn't
+/// output a clock. For this reason, the FRG is initially in the [`Disabled`]
+/// state and can only be used as a clock source for other peripherals, once it
+/// has been enabled using [`FRG::enable`], which selects its input clock.
+///
+/// [`Disabled`]: ../../init_state/struct.Disabled.html
+/// [`FRG::enable`]: #method.enable
+pub struct FRG<I: Instance, State = init_state::Enabled> {
     div: RegProxy<I::Div>,
     mult: RegProxy<I::Mult>,
     clksel: RegProxy<I::Clksel>,
+    _state: State,
 }
 
-impl<I> FRG<I>
+impl<I> FRG<I, init_state::Disabled>
 where
     I: Instance,
 {
@@ -31,14 +41,52 @@ where
             div: RegProxy::new(),
             mult: RegProxy::new(),
             clksel: RegProxy::new(),
+            _state: init_state::Disabled,
+        }
+    }
+
+    /// Enable the FRG by selecting its clock source
+    ///
+    /// This method is only available, if `FRG` is in the [`Disabled`] state.
+    /// Code that attempts to call this method when the FRG is already enabled
+    /// will not compile.
+    ///
+    /// Consumes this instance of `FRG` and returns another instance that has
+    /// its `State` type parameter set to [`Enabled`]. Only enabled FRGs can be
+    /// used as the clock source for other peripherals.
+    ///
+    /// [`Disabled`]: ../../init_state/struct.Disabled.html
+    /// [`Enabled`]: ../../init_state/struct.Enabled.html
+    pub fn enable(self, clock: Clock) -> FRG<I, init_state::Enabled> {
+        self.clksel.write(|w| w.sel().variant(clock));
+
+        FRG {
+            div: self.div,
+            mult: self.mult,
+            clksel: self.clksel,
+            _state: init_state::Enabled(()),
         }
     }
+}
 
+impl<I> FRG<I, init_state::Enabled>
+where
+    I: Instance,
+{
     /// Select clock source for FRG
+    ///
+    /// Overwrites the clock source that was selected by [`FRG::enable`].
+    ///
+    /// [`FRG::enable`]: #method.enable
     pub fn select_clock(&mut self, clock: Clock) {
         self.clksel.write(|w| w.sel().variant(clock));
     }
+}
 
+impl<I, State> FRG<I, State>
+where
+    I: Instance,
+{
     /// Set the fractional generator divider value
     pub fn set_div(&mut self, div: u8) {
         // Safe, as all `u8` values are valid.
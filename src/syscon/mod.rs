@@ -20,18 +20,22 @@ pub mod clock_source;
 #[cfg(feature = "82x")]
 use crate::pac::syscon::{
     pdruncfg, presetctrl as presetctrl0, starterp1,
-    sysahbclkctrl as sysahbclkctrl0, PDRUNCFG, PRESETCTRL as PRESETCTRL0,
-    STARTERP1, SYSAHBCLKCTRL as SYSAHBCLKCTRL0, UARTCLKDIV, UARTFRGDIV,
-    UARTFRGMULT,
+    sysahbclkctrl as sysahbclkctrl0, BODCTRL, IOCONCLKDIV0, IOCONCLKDIV1,
+    IOCONCLKDIV2, IOCONCLKDIV3, IOCONCLKDIV4, IOCONCLKDIV5, IOCONCLKDIV6,
+    PDAWAKECFG, PDRUNCFG, PDSLEEPCFG, PRESETCTRL as PRESETCTRL0, STARTERP0,
+    STARTERP1, SYSAHBCLKCTRL as SYSAHBCLKCTRL0, SYSRSTSTAT, UARTCLKDIV,
+    UARTFRGDIV, UARTFRGMULT, WDTOSCCTRL,
 };
 
 #[cfg(feature = "845")]
 use crate::pac::syscon::{
-    pdruncfg, presetctrl0, starterp1, sysahbclkctrl0, FCLKSEL, PDRUNCFG,
-    PRESETCTRL0, STARTERP1, SYSAHBCLKCTRL0,
+    pdruncfg, presetctrl0, starterp1, sysahbclkctrl0, BODCTRL, FCLKSEL,
+    IOCONCLKDIV0, IOCONCLKDIV1, IOCONCLKDIV2, IOCONCLKDIV3, IOCONCLKDIV4,
+    IOCONCLKDIV5, IOCONCLKDIV6, PDAWAKECFG, PDRUNCFG, PDSLEEPCFG, PRESETCTRL0,
+    STARTERP0, STARTERP1, SYSAHBCLKCTRL0, SYSRSTSTAT, WDTOSCCTRL,
 };
 
-use crate::{clock, init_state, pac, reg_proxy::RegProxy};
+use crate::{clock, init_state, pac, pins::FilterClock, reg_proxy::RegProxy};
 
 /// Entry point to the SYSCON API
 ///
@@ -67,10 +71,23 @@ impl SYSCON {
     pub fn split(self) -> Parts {
         Parts {
             handle: Handle {
+                bodctrl: RegProxy::new(),
+                ioconclkdiv0: RegProxy::new(),
+                ioconclkdiv1: RegProxy::new(),
+                ioconclkdiv2: RegProxy::new(),
+                ioconclkdiv3: RegProxy::new(),
+                ioconclkdiv4: RegProxy::new(),
+                ioconclkdiv5: RegProxy::new(),
+                ioconclkdiv6: RegProxy::new(),
+                pdawakecfg: RegProxy::new(),
                 pdruncfg: RegProxy::new(),
+                pdsleepcfg: RegProxy::new(),
                 presetctrl0: RegProxy::new(),
+                starterp0: RegProxy::new(),
                 starterp1: RegProxy::new(),
                 sysahbclkctrl: RegProxy::new(),
+                sysrststat: RegProxy::new(),
+                wdtoscctrl: RegProxy::new(),
                 #[cfg(feature = "845")]
                 fclksel: RegProxy::new(),
             },
@@ -163,11 +180,11 @@ pub struct Parts {
 
     #[cfg(feature = "845")]
     /// Fractional Baud Rate Generator 0
-    pub frg0: FRG<frg::FRG0>,
+    pub frg0: FRG<frg::FRG0, init_state::Disabled>,
 
     #[cfg(feature = "845")]
     /// Fractional Baud Rate Generator 1
-    pub frg1: FRG<frg::FRG1>,
+    pub frg1: FRG<frg::FRG1, init_state::Disabled>,
 }
 
 /// Handle to the SYSCON peripheral
@@ -181,10 +198,23 @@ pub struct Parts {
 ///
 /// [module documentation]: index.html
 pub struct Handle {
-    pdruncfg: RegProxy<PDRUNCFG>,
+    pub(crate) bodctrl: RegProxy<BODCTRL>,
+    ioconclkdiv0: RegProxy<IOCONCLKDIV0>,
+    ioconclkdiv1: RegProxy<IOCONCLKDIV1>,
+    ioconclkdiv2: RegProxy<IOCONCLKDIV2>,
+    ioconclkdiv3: RegProxy<IOCONCLKDIV3>,
+    ioconclkdiv4: RegProxy<IOCONCLKDIV4>,
+    ioconclkdiv5: RegProxy<IOCONCLKDIV5>,
+    ioconclkdiv6: RegProxy<IOCONCLKDIV6>,
+    pub(crate) pdawakecfg: RegProxy<PDAWAKECFG>,
+    pub(crate) pdruncfg: RegProxy<PDRUNCFG>,
+    pub(crate) pdsleepcfg: RegProxy<PDSLEEPCFG>,
     presetctrl0: RegProxy<PRESETCTRL0>,
-    starterp1: RegProxy<STARTERP1>,
+    pub(crate) starterp0: RegProxy<STARTERP0>,
+    pub(crate) starterp1: RegProxy<STARTERP1>,
     sysahbclkctrl: RegProxy<SYSAHBCLKCTRL0>,
+    pub(crate) sysrststat: RegProxy<SYSRSTSTAT>,
+    pub(crate) wdtoscctrl: RegProxy<WDTOSCCTRL>,
     #[cfg(feature = "845")]
     pub(crate) fclksel: RegProxy<FCLKSEL>,
 }
@@ -253,6 +283,43 @@ impl Handle {
     {
         self.starterp1.modify(|_, w| I::disable(w));
     }
+
+    /// Set one of the IOCON glitch filter clock dividers
+    ///
+    /// `clock` selects the divider, from IOCONCLKDIV0 to IOCONCLKDIV6. The
+    /// resulting filter clock is the main clock divided by `div`. A `div` of 0
+    /// disables the filter clock, which is the reset configuration.
+    ///
+    /// The filter clocks are used by the digital input filters of the pins
+    /// (see [`Pin::set_filter`]).
+    ///
+    /// [`Pin::set_filter`]: ../pins/struct.Pin.html#method.set_filter
+    pub fn set_iocon_clock_divider(&mut self, clock: FilterClock, div: u8) {
+        // Sound, as all `u8` values are valid for DIV.
+        match clock {
+            FilterClock::Div0 => {
+                self.ioconclkdiv0.write(|w| unsafe { w.div().bits(div) })
+            }
+            FilterClock::Div1 => {
+                self.ioconclkdiv1.write(|w| unsafe { w.div().bits(div) })
+            }
+            FilterClock::Div2 => {
+                self.ioconclkdiv2.write(|w| unsafe { w.div().bits(div) })
+            }
+            FilterClock::Div3 => {
+                self.ioconclkdiv3.write(|w| unsafe { w.div().bits(div) })
+            }
+            FilterClock::Div4 => {
+                self.ioconclkdiv4.write(|w| unsafe { w.div().bits(div) })
+            }
+            FilterClock::Div5 => {
+                self.ioconclkdiv5.write(|w| unsafe { w.div().bits(div) })
+            }
+            FilterClock::Div6 => {
+                self.ioconclkdiv6.write(|w| unsafe { w.div().bits(div) })
+            }
+        }
+    }
 }
 
 /// Brown-out detection
@@ -670,6 +737,12 @@ impl IoscDerivedClock<init_state::Disabled> {
     }
 }
 
+impl clock::Frequency for IOSC {
+    fn hz(&self) -> u32 {
+        12_000_000
+    }
+}
+
 impl<State> clock::Frequency for IoscDerivedClock<State> {
     fn hz(&self) -> u32 {
         750_000
@@ -696,10 +769,13 @@ pub trait WakeUpInterrupt {
 
     /// Internal method to configure interrupt wakeup behavior
     fn disable(w: &mut starterp1::W) -> &mut starterp1::W;
+
+    /// The interrupt's bit mask in STARTERP1
+    const MASK: u32;
 }
 
 macro_rules! wakeup_interrupt {
-    ($name:ident, $field:ident) => {
+    ($name:ident, $field:ident, $bit:expr) => {
         /// Can be used to enable/disable interrupt wake-up behavior
         ///
         /// See [`syscon::Handle::enable_interrupt_wakeup`] and
@@ -717,33 +793,48 @@ macro_rules! wakeup_interrupt {
             fn disable(w: &mut starterp1::W) -> &mut starterp1::W {
                 w.$field().disabled()
             }
+
+            const MASK: u32 = 0x1 << $bit;
         }
     };
 }
 
-wakeup_interrupt!(Spi0Wakeup, spi0);
-wakeup_interrupt!(Spi1Wakeup, spi1);
-wakeup_interrupt!(Usart0Wakeup, usart0);
-wakeup_interrupt!(Usart1Wakeup, usart1);
-wakeup_interrupt!(Usart2Wakeup, usart2);
-wakeup_interrupt!(I2c1Wakeup, i2c1);
-wakeup_interrupt!(I2c0Wakeup, i2c0);
-wakeup_interrupt!(WwdtWakeup, wwdt);
-wakeup_interrupt!(BodWakeup, bod);
-wakeup_interrupt!(WktWakeup, wkt);
-wakeup_interrupt!(I2c2Wakeup, i2c2);
-wakeup_interrupt!(I2c3Wakeup, i2c3);
-
+wakeup_interrupt!(Spi0Wakeup, spi0, 0);
+wakeup_interrupt!(Spi1Wakeup, spi1, 1);
+wakeup_interrupt!(Usart0Wakeup, usart0, 3);
+wakeup_interrupt!(Usart1Wakeup, usart1, 4);
+wakeup_interrupt!(Usart2Wakeup, usart2, 5);
+wakeup_interrupt!(I2c1Wakeup, i2c1, 7);
+wakeup_interrupt!(I2c0Wakeup, i2c0, 8);
+wakeup_interrupt!(WwdtWakeup, wwdt, 12);
+wakeup_interrupt!(BodWakeup, bod, 13);
+wakeup_interrupt!(WktWakeup, wkt, 15);
+wakeup_interrupt!(I2c2Wakeup, i2c2, 21);
+wakeup_interrupt!(I2c3Wakeup, i2c3, 22);
+
+reg!(BODCTRL, BODCTRL, pac::SYSCON, bodctrl);
+reg!(IOCONCLKDIV0, IOCONCLKDIV0, pac::SYSCON, ioconclkdiv0);
+reg!(IOCONCLKDIV1, IOCONCLKDIV1, pac::SYSCON, ioconclkdiv1);
+reg!(IOCONCLKDIV2, IOCONCLKDIV2, pac::SYSCON, ioconclkdiv2);
+reg!(IOCONCLKDIV3, IOCONCLKDIV3, pac::SYSCON, ioconclkdiv3);
+reg!(IOCONCLKDIV4, IOCONCLKDIV4, pac::SYSCON, ioconclkdiv4);
+reg!(IOCONCLKDIV5, IOCONCLKDIV5, pac::SYSCON, ioconclkdiv5);
+reg!(IOCONCLKDIV6, IOCONCLKDIV6, pac::SYSCON, ioconclkdiv6);
+reg!(PDAWAKECFG, PDAWAKECFG, pac::SYSCON, pdawakecfg);
 reg!(PDRUNCFG, PDRUNCFG, pac::SYSCON, pdruncfg);
+reg!(PDSLEEPCFG, PDSLEEPCFG, pac::SYSCON, pdsleepcfg);
 #[cfg(feature = "82x")]
 reg!(PRESETCTRL0, PRESETCTRL0, pac::SYSCON, presetctrl);
 #[cfg(feature = "845")]
 reg!(PRESETCTRL0, PRESETCTRL0, pac::SYSCON, presetctrl0);
+reg!(STARTERP0, STARTERP0, pac::SYSCON, starterp0);
 reg!(STARTERP1, STARTERP1, pac::SYSCON, starterp1);
 #[cfg(feature = "82x")]
 reg!(SYSAHBCLKCTRL0, SYSAHBCLKCTRL0, pac::SYSCON, sysahbclkctrl);
 #[cfg(feature = "845")]
 reg!(SYSAHBCLKCTRL0, SYSAHBCLKCTRL0, pac::SYSCON, sysahbclkctrl0);
+reg!(SYSRSTSTAT, SYSRSTSTAT, pac::SYSCON, sysrststat);
+reg!(WDTOSCCTRL, WDTOSCCTRL, pac::SYSCON, wdtoscctrl);
 #[cfg(feature = "845")]
 reg!(FCLKSEL, [FCLKSEL; 11], pac::SYSCON, fclksel);
 
@@ -0,0 +1,190 @@
+//! Units for frequencies, durations, and duty cycles
+//!
+//! Provides the [`U32Ext`] extension trait, which makes it possible to write
+//! configuration values with their unit, like `115_200.bps()`, `400.khz()`,
+//! `10.ms()`, or `50.percent()`. The trait is part of the [prelude].
+//!
+//! Frequencies and durations are represented by types from the
+//! [embedded-time] crate, which are re-exported here. Duty cycles are
+//! represented by [`Percent`].
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{prelude::*, units::Percent};
+//!
+//! let baud_rate = 115_200.bps();
+//! let frequency = 400.khz();
+//! let timeout = 10.ms();
+//! let duty_cycle: Percent = 50.percent();
+//! ```
+//!
+//! [`U32Ext`]: trait.U32Ext.html
+//! [prelude]: ../prelude/index.html
+//! [embedded-time]: https://crates.io/crates/embedded-time
+//! [`Percent`]: struct.Percent.html
+
+pub use embedded_time::{
+    duration::{Microseconds, Milliseconds, Seconds},
+    rate::{Baud, Hertz},
+};
+
+/// Extension trait that adds unit constructors to `u32`
+///
+/// This trait is part of the [prelude], so you don't need to import it
+/// separately, if you're using the prelude.
+///
+/// [prelude]: ../prelude/index.html
+pub trait U32Ext: private::Sealed {
+    /// Interpret this value as a frequency in Hz
+    fn hz(self) -> Hertz;
+
+    /// Interpret this value as a frequency in kHz
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the frequency in Hz doesn't fit into a `u32`, meaning the
+    /// value is larger than 4_294_967.
+    fn khz(self) -> Hertz;
+
+    /// Interpret this value as a frequency in MHz
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the frequency in Hz doesn't fit into a `u32`, meaning the
+    /// value is larger than 4_294.
+    fn mhz(self) -> Hertz;
+
+    /// Interpret this value as a bit rate in bits per second
+    fn bps(self) -> Baud;
+
+    /// Interpret this value as a duration in microseconds
+    fn us(self) -> Microseconds;
+
+    /// Interpret this value as a duration in milliseconds
+    fn ms(self) -> Milliseconds;
+
+    /// Interpret this value as a duration in seconds
+    fn s(self) -> Seconds;
+
+    /// Interpret this value as a percentage
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the value is larger than 100. Use [`Percent::new`], if you
+    /// need to handle this case without panicking.
+    ///
+    /// [`Percent::new`]: struct.Percent.html#method.new
+    fn percent(self) -> Percent;
+}
+
+impl U32Ext for u32 {
+    fn hz(self) -> Hertz {
+        Hertz(self)
+    }
+
+    fn khz(self) -> Hertz {
+        match self.checked_mul(1_000) {
+            Some(hz) => Hertz(hz),
+            None => panic!("Frequency in kHz is too large"),
+        }
+    }
+
+    fn mhz(self) -> Hertz {
+        match self.checked_mul(1_000_000) {
+            Some(hz) => Hertz(hz),
+            None => panic!("Frequency in MHz is too large"),
+        }
+    }
+
+    fn bps(self) -> Baud {
+        Baud(self)
+    }
+
+    fn us(self) -> Microseconds {
+        Microseconds(self)
+    }
+
+    fn ms(self) -> Milliseconds {
+        Milliseconds(self)
+    }
+
+    fn s(self) -> Seconds {
+        Seconds(self)
+    }
+
+    fn percent(self) -> Percent {
+        match Percent::new(self) {
+            Some(percent) => percent,
+            None => panic!("Percentage must not be larger than 100"),
+        }
+    }
+}
+
+/// A percentage between 0 and 100, used for duty cycles
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Percent(u8);
+
+impl Percent {
+    /// Create a new `Percent`
+    ///
+    /// Returns `None`, if `value` is larger than 100.
+    pub const fn new(value: u32) -> Option<Self> {
+        if value > 100 {
+            return None;
+        }
+
+        Some(Self(value as u8))
+    }
+
+    /// Returns the percentage as a number between 0 and 100
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Returns this percentage of `max`, rounded down
+    pub fn of(self, max: u32) -> u32 {
+        // Can't overflow, as the result is never larger than `max`.
+        (u64::from(max) * u64::from(self.0) / 100) as u32
+    }
+}
+
+/// Implemented for types that represent a frequency or bit rate
+///
+/// Allows APIs to accept plain `u32` values (in Hz or bits per second), as
+/// well as [`Hertz`] and [`Baud`].
+///
+/// [`Hertz`]: struct.Hertz.html
+/// [`Baud`]: struct.Baud.html
+pub trait Rate: private::Sealed {
+    /// Returns the rate in Hz or bits per second
+    fn to_hz(self) -> u32;
+}
+
+impl Rate for u32 {
+    fn to_hz(self) -> u32 {
+        self
+    }
+}
+
+impl Rate for Hertz {
+    fn to_hz(self) -> u32 {
+        self.0
+    }
+}
+
+impl Rate for Baud {
+    fn to_hz(self) -> u32 {
+        self.0
+    }
+}
+
+mod private {
+    use super::{Baud, Hertz};
+
+    pub trait Sealed {}
+
+    impl Sealed for u32 {}
+    impl Sealed for Hertz {}
+    impl Sealed for Baud {}
+}
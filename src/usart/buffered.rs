@@ -0,0 +1,424 @@
+//! Interrupt-driven, buffered USART
+//!
+//! Provides [`Buffered`], which wraps an enabled [`USART`] and moves data
+//! between the USART and two ring buffers from the USART interrupt. This
+//! allows the application to read and write without blocking, and without
+//! losing received data while it is busy with other things, as long as the
+//! buffers don't run full.
+//!
+//! To use it, create a `Buffered` instance using [`Buffered::new`], and call
+//! [`on_interrupt`] from the interrupt handler of the same USART instance.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{cortex_m, pac, prelude::*, usart, Peripherals};
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut swm    = p.SWM.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! #[cfg(feature = "82x")]
+//! let clock_config = {
+//!     syscon.uartfrg.set_clkdiv(6);
+//!     syscon.uartfrg.set_frgmult(22);
+//!     syscon.uartfrg.set_frgdiv(0xff);
+//!     usart::Clock::new(&syscon.uartfrg, 0, 16)
+//! };
+//! #[cfg(feature = "845")]
+//! let clock_config = usart::Clock::new_with_baudrate(115200);
+//!
+//! let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+//!     p.pins.pio0_0.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+//!     p.pins.pio0_4.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//!
+//! let serial = p.USART0.enable_async(
+//!     &clock_config,
+//!     &mut syscon.handle,
+//!     u0_rxd,
+//!     u0_txd,
+//!     usart::Settings::default(),
+//! );
+//!
+//! let rx_buffer = cortex_m::singleton!(: [u8; 64] = [0; 64]).unwrap();
+//! let tx_buffer = cortex_m::singleton!(: [u8; 64] = [0; 64]).unwrap();
+//!
+//! let mut serial = usart::Buffered::new(serial, rx_buffer, tx_buffer);
+//!
+//! // Only blocks, if there's not enough room in the transmit buffer.
+//! serial.bwrite_all(b"Hello, world!");
+//!
+//! // In the USART0 interrupt handler:
+//! usart::buffered::on_interrupt::<pac::USART0>();
+//! ```
+//!
+//! [`Buffered`]: struct.Buffered.html
+//! [`USART`]: ../struct.USART.html
+//! [`Buffered::new`]: struct.Buffered.html#method.new
+//! [`on_interrupt`]: fn.on_interrupt.html
+
+use core::{cell::RefCell, fmt, ptr, slice};
+
+use cortex_m::{
+    interrupt::{self, Mutex},
+    peripheral::NVIC,
+};
+use embedded_hal::{
+    blocking::serial::write::Default as BlockingWriteDefault,
+    serial::{Read, Write},
+};
+use nb::block;
+use void::Void;
+
+use super::{
+    flags::Interrupts,
+    instances::Instance,
+    peripheral::USART,
+    rx::{Error, Rx},
+    state::{AsyncMode, Enabled},
+    tx::Tx,
+};
+
+/// Move data between the USART and the buffers
+///
+/// Must be called from the interrupt handler of USART instance `I`, while a
+/// [`Buffered`] instance for it exists. Moves all received data into the
+/// receive buffer, and as much data from the transmit buffer into the USART,
+/// as it can accept.
+///
+/// [`Buffered`]: struct.Buffered.html
+pub fn on_interrupt<I>()
+where
+    I: Instance,
+{
+    // The mode doesn't matter for reading and writing words.
+    let mut rx = Rx::<I, Enabled<u8, AsyncMode>>::new();
+    let mut tx = Tx::<I, Enabled<u8, AsyncMode>, _>::new();
+
+    interrupt::free(|cs| {
+        let mut buffers = I::buffers().0.borrow(cs).borrow_mut();
+
+        loop {
+            match rx.read() {
+                Ok(word) => {
+                    if !buffers.rx.push(word) {
+                        buffers.error = Some(Error::Overrun(word));
+                    }
+                }
+                Err(nb::Error::Other(error)) => buffers.error = Some(error),
+                Err(nb::Error::WouldBlock) => break,
+            }
+        }
+
+        while let Some(word) = buffers.tx.peek() {
+            match tx.write(word) {
+                Ok(()) => {
+                    buffers.tx.pop();
+                }
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(void)) => void::unreachable(void),
+            }
+        }
+
+        if buffers.tx.is_empty() {
+            tx.disable_interrupts(Interrupts {
+                TXRDY: true,
+                ..Interrupts::default()
+            });
+        }
+    });
+}
+
+/// A USART instance with interrupt-driven receive and transmit buffers
+///
+/// Please refer to the [module documentation] for more information.
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::serial::Read`] for non-blocking reads
+/// - [`embedded_hal::serial::Write`] for non-blocking writes
+/// - [`embedded_hal::blocking::serial::Write`] for blocking writes
+///
+/// [module documentation]: index.html
+/// [`embedded_hal::serial::Read`]: #impl-Read<u8>
+/// [`embedded_hal::serial::Write`]: #impl-Write<u8>
+/// [`embedded_hal::blocking::serial::Write`]: #impl-Write<Word>
+pub struct Buffered<I, Mode> {
+    usart: USART<I, Enabled<u8, Mode>>,
+}
+
+impl<I, Mode> Buffered<I, Mode>
+where
+    I: Instance,
+{
+    /// Start buffering data for the provided USART instance
+    ///
+    /// Enables the RXRDY interrupt and unmasks the USART interrupt in the
+    /// NVIC. The TXRDY interrupt is enabled and disabled as required. Don't
+    /// change the configuration of these interrupts, while the `Buffered`
+    /// instance exists.
+    ///
+    /// Neither buffer may have a length of 0. This is checked at compile time.
+    pub fn new<const RX: usize, const TX: usize>(
+        mut usart: USART<I, Enabled<u8, Mode>>,
+        rx_buffer: &'static mut [u8; RX],
+        tx_buffer: &'static mut [u8; TX],
+    ) -> Self {
+        let () = NonEmpty::<RX>::CHECK;
+        let () = NonEmpty::<TX>::CHECK;
+
+        let rx = Ring::new(rx_buffer);
+        let tx = Ring::new(tx_buffer);
+
+        interrupt::free(move |cs| {
+            let mut buffers = I::buffers().0.borrow(cs).borrow_mut();
+            buffers.rx = rx;
+            buffers.tx = tx;
+            buffers.error = None;
+        });
+
+        usart.enable_interrupts(Interrupts {
+            RXRDY: true,
+            ..Interrupts::default()
+        });
+
+        // Sound, as the interrupt handler only accesses the buffers within a
+        // critical section.
+        unsafe { NVIC::unmask(I::INTERRUPT) };
+
+        Self { usart }
+    }
+
+    /// Returns the number of received bytes that haven't been read yet
+    pub fn available(&self) -> usize {
+        interrupt::free(|cs| I::buffers().0.borrow(cs).borrow().rx.len)
+    }
+
+    /// Returns the last receive error, if any, and resets it
+    ///
+    /// Words that were received with an error are not put into the receive
+    /// buffer. If the receive buffer is full, received words are discarded
+    /// and [`Error::Overrun`] is reported.
+    ///
+    /// [`Error::Overrun`]: ../enum.Error.html#variant.Overrun
+    pub fn take_error(&mut self) -> Option<Error<u8>> {
+        interrupt::free(|cs| {
+            I::buffers().0.borrow(cs).borrow_mut().error.take()
+        })
+    }
+
+    /// Stop buffering and return the USART instance and the buffers
+    ///
+    /// Disables the RXRDY and TXRDY interrupts. Any data that is left in the
+    /// buffers is discarded.
+    pub fn free(
+        mut self,
+    ) -> (
+        USART<I, Enabled<u8, Mode>>,
+        &'static mut [u8],
+        &'static mut [u8],
+    ) {
+        self.usart.disable_interrupts(Interrupts {
+            RXRDY: true,
+            TXRDY: true,
+            ..Interrupts::default()
+        });
+
+        let (rx_buffer, tx_buffer) = interrupt::free(|cs| {
+            let mut buffers = I::buffers().0.borrow(cs).borrow_mut();
+            let rx = core::mem::replace(&mut buffers.rx, Ring::empty());
+            let tx = core::mem::replace(&mut buffers.tx, Ring::empty());
+            (rx.free(), tx.free())
+        });
+
+        (self.usart, rx_buffer, tx_buffer)
+    }
+}
+
+impl<I, Mode> Read<u8> for Buffered<I, Mode>
+where
+    I: Instance,
+{
+    type Error = Void;
+
+    /// Reads a single word from the receive buffer
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        interrupt::free(|cs| I::buffers().0.borrow(cs).borrow_mut().rx.pop())
+            .ok_or(nb::Error::WouldBlock)
+    }
+}
+
+impl<I, Mode> Write<u8> for Buffered<I, Mode>
+where
+    I: Instance,
+{
+    type Error = Void;
+
+    /// Writes a single word to the transmit buffer
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        let pushed = interrupt::free(|cs| {
+            I::buffers().0.borrow(cs).borrow_mut().tx.push(word)
+        });
+
+        // Enable the interrupt even if the buffer was full, in case it hasn't
+        // been enabled yet.
+        self.usart.enable_interrupts(Interrupts {
+            TXRDY: true,
+            ..Interrupts::default()
+        });
+
+        if pushed {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Waits until the transmit buffer is empty and all words have been sent
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        let empty = interrupt::free(|cs| {
+            I::buffers().0.borrow(cs).borrow().tx.is_empty()
+        });
+
+        if !empty {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        self.usart.flush()
+    }
+}
+
+impl<I, Mode> BlockingWriteDefault<u8> for Buffered<I, Mode> where I: Instance {}
+
+impl<I, Mode> fmt::Write for Buffered<I, Mode>
+where
+    I: Instance,
+{
+    /// Writes a string slice into the transmit buffer
+    ///
+    /// Blocks while the transmit buffer is full.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            block!(self.write(byte)).map_err(|_| fmt::Error)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Storage for the buffers of a USART instance
+///
+/// This type is an internal implementation detail.
+pub struct Buffers(Mutex<RefCell<Inner>>);
+
+impl Buffers {
+    pub(super) const fn new() -> Self {
+        Self(Mutex::new(RefCell::new(Inner {
+            rx: Ring::empty(),
+            tx: Ring::empty(),
+            error: None,
+        })))
+    }
+}
+
+/// Checks at compile time that a buffer of length `N` is not empty
+struct NonEmpty<const N: usize>;
+
+impl<const N: usize> NonEmpty<N> {
+    const CHECK: () = assert!(N > 0, "USART buffers must not be empty");
+}
+
+struct Inner {
+    rx: Ring,
+    tx: Ring,
+    error: Option<Error<u8>>,
+}
+
+struct Ring {
+    data: *mut u8,
+    capacity: usize,
+    tail: usize,
+    len: usize,
+}
+
+impl Ring {
+    const fn empty() -> Self {
+        Self {
+            data: ptr::null_mut(),
+            capacity: 0,
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    fn new(buffer: &'static mut [u8]) -> Self {
+        Self {
+            data: buffer.as_mut_ptr(),
+            capacity: buffer.len(),
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    fn free(self) -> &'static mut [u8] {
+        if self.data.is_null() {
+            return &mut [];
+        }
+
+        // Sound, as the pointer and capacity were taken from a
+        // `&'static mut [u8]` in `new`, and the ring was the only user of it.
+        unsafe { slice::from_raw_parts_mut(self.data, self.capacity) }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `false`, if the ring is full
+    fn push(&mut self, byte: u8) -> bool {
+        if self.len == self.capacity {
+            return false;
+        }
+
+        let head = (self.tail + self.len) % self.capacity;
+
+        // Sound, as `head` is within the bounds of the buffer.
+        unsafe { *self.data.add(head) = byte };
+        self.len += 1;
+
+        true
+    }
+
+    fn peek(&self) -> Option<u8> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // Sound, as `tail` is within the bounds of the buffer.
+        Some(unsafe { *self.data.add(self.tail) })
+    }
+
+    fn pop(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+
+        self.tail = (self.tail + 1) % self.capacity;
+        self.len -= 1;
+
+        Some(byte)
+    }
+}
+
+// `Ring` contains a raw pointer, therefore `Send` is not derived
+// automatically. It is only ever accessed from within a critical section, so
+// it is fine to share it with the interrupt handler.
+unsafe impl Send for Ring {}
@@ -26,19 +26,235 @@ where
     ///
     /// The `osrval` argument has to be between 5-16. It will be ignored in
     /// synchronous mode.
-    pub fn new(_: &T, brgval: u16, osrval: u8) -> Self {
-        let osrval = osrval - 1;
-        assert!(osrval > 3 && osrval < 0x10);
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `osrval` is out of range. Use [`Clock::try_new`], if you
+    /// need to handle this case without panicking.
+    pub fn new(clock: &T, brgval: u16, osrval: u8) -> Self {
+        match Self::try_new(clock, brgval, osrval) {
+            Ok(clock) => clock,
+            Err(_) => panic!("`osrval` must be between 5 and 16"),
+        }
+    }
 
-        Self {
+    /// Create the clock configuration for the USART, without panicking
+    ///
+    /// Works like [`Clock::new`], but returns an error, if `osrval` is not
+    /// between 5-16.
+    pub fn try_new(
+        _: &T,
+        brgval: u16,
+        osrval: u8,
+    ) -> Result<Self, InvalidClockConfig> {
+        if !(5..=16).contains(&osrval) {
+            return Err(InvalidClockConfig);
+        }
+
+        Ok(Self {
             brgval,
-            osrval,
+            osrval: osrval - 1,
+            _clock: PhantomData,
+            _mode: PhantomData,
+        })
+    }
+
+    /// Create the clock configuration for the USART from a [`Divider`]
+    ///
+    /// Use this in combination with [`Divider::from_baudrate`], to compute the
+    /// configuration values at compile time.
+    pub fn from_divider(_: &T, divider: Divider) -> Self {
+        Self {
+            brgval: divider.brgval,
+            osrval: divider.osrval,
             _clock: PhantomData,
             _mode: PhantomData,
         }
     }
 }
 
+/// Divider values for a USART baud rate
+///
+/// Can be computed at compile time from a baud rate and the frequency of the
+/// USART clock source, using [`Divider::from_baudrate`]. Pass it to
+/// [`Clock::from_divider`] to create the clock configuration.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::usart;
+///
+/// // Fails to compile, if the baud rate is off by 2% or more.
+/// const DIVIDER: usart::Divider =
+///     usart::Divider::from_baudrate(12_000_000, 115_200, 2);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Divider {
+    brgval: u16,
+    osrval: u8,
+}
+
+impl Divider {
+    /// Compute the divider values for a baud rate
+    ///
+    /// `clock_hz` is the frequency of the clock source that is passed to
+    /// [`Clock::from_divider`]. The resulting baud rate deviates from
+    /// `baudrate` by less than `tolerance_percent`.
+    ///
+    /// Chooses the highest oversampling value that achieves this accuracy.
+    /// The oversampling value only has meaning in asynchronous mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if no values within the tolerance can be found. If this
+    /// function is used to initialize a constant, this results in a
+    /// compile-time error. Use [`Divider::try_from_baudrate`], if you need to
+    /// handle this case without panicking.
+    pub const fn from_baudrate(
+        clock_hz: u32,
+        baudrate: u32,
+        tolerance_percent: u32,
+    ) -> Self {
+        match Self::try_from_baudrate(clock_hz, baudrate, tolerance_percent) {
+            Some(divider) => divider,
+            None => panic!("No USART divider within tolerance of baud rate"),
+        }
+    }
+
+    /// Compute the divider values for a baud rate, without panicking
+    ///
+    /// Works like [`Divider::from_baudrate`], but returns `None`, if no
+    /// values within the tolerance can be found.
+    pub const fn try_from_baudrate(
+        clock_hz: u32,
+        baudrate: u32,
+        tolerance_percent: u32,
+    ) -> Option<Self> {
+        if baudrate == 0 {
+            return None;
+        }
+
+        // Look for the highest `osrval` that will give us the requested
+        // accuracy.
+        let mut osrval = 0xf;
+        while osrval >= 0x4 {
+            if let Some(divider) = Self::try_with_osrval(
+                clock_hz,
+                baudrate,
+                osrval,
+                tolerance_percent,
+            ) {
+                return Some(divider);
+            }
+
+            osrval -= 1;
+        }
+
+        None
+    }
+
+    /// Compute the divider values for a baud rate and oversampling value
+    ///
+    /// Works like [`Divider::from_baudrate`], but uses the provided
+    /// `oversampling` value, instead of choosing one. A higher oversampling
+    /// value improves noise immunity, while a lower one makes higher baud rates
+    /// achievable.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `oversampling` is not between 5 and 16, or if no values
+    /// within the tolerance can be found. If this function is used to
+    /// initialize a constant, this results in a compile-time error. Use
+    /// [`Divider::try_from_baudrate_with_oversampling`], if you need to
+    /// handle this case without panicking.
+    pub const fn from_baudrate_with_oversampling(
+        clock_hz: u32,
+        baudrate: u32,
+        oversampling: u8,
+        tolerance_percent: u32,
+    ) -> Self {
+        match Self::try_from_baudrate_with_oversampling(
+            clock_hz,
+            baudrate,
+            oversampling,
+            tolerance_percent,
+        ) {
+            Some(divider) => divider,
+            None => panic!("No USART divider within tolerance of baud rate"),
+        }
+    }
+
+    /// Compute the divider values for a baud rate and oversampling value,
+    /// without panicking
+    ///
+    /// Works like [`Divider::from_baudrate_with_oversampling`], but returns
+    /// `None`, if `oversampling` is not between 5 and 16, or if no values
+    /// within the tolerance can be found.
+    pub const fn try_from_baudrate_with_oversampling(
+        clock_hz: u32,
+        baudrate: u32,
+        oversampling: u8,
+        tolerance_percent: u32,
+    ) -> Option<Self> {
+        if baudrate == 0 || oversampling < 5 || oversampling > 16 {
+            return None;
+        }
+
+        Self::try_with_osrval(
+            clock_hz,
+            baudrate,
+            oversampling - 1,
+            tolerance_percent,
+        )
+    }
+
+    /// Returns the oversampling value, between 5 and 16
+    pub const fn oversampling(&self) -> u8 {
+        self.osrval + 1
+    }
+
+    const fn try_with_osrval(
+        clock_hz: u32,
+        baudrate: u32,
+        osrval: u8,
+        tolerance_percent: u32,
+    ) -> Option<Self> {
+        let divider = match baudrate.checked_mul(osrval as u32 + 1) {
+            Some(divider) => divider,
+            None => return None,
+        };
+        let brgval = clock_hz / divider;
+
+        if brgval == 0 || brgval - 1 > u16::MAX as u32 {
+            return None;
+        }
+
+        let resulting_baudrate = clock_hz / brgval / (osrval as u32 + 1);
+
+        // Due to rounding, the resulting baud rate is never lower than the
+        // desired one.
+        let deviation_percent =
+            (resulting_baudrate - baudrate) as u64 * 100 / baudrate as u64;
+
+        if deviation_percent < tolerance_percent as u64 {
+            Some(Self {
+                brgval: (brgval - 1) as u16,
+                osrval,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Indicates that a USART clock configuration is invalid
+///
+/// Returned by [`Clock::try_new`], if the configuration values are out of
+/// range, or by [`Clock::try_new_with_baudrate`] (LPC845 only), if no
+/// configuration could be found for the requested baud rate.
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidClockConfig;
+
 /// Implemented for USART clock sources
 pub trait ClockSource: private::Sealed {
     /// Select the clock source
@@ -78,17 +294,19 @@ mod target {
             self,
             clock_source::{PeripheralClock, PeripheralClockSelector},
         },
+        units::Rate,
         usart::state::AsyncMode,
     };
 
-    use super::{Clock, ClockSource};
+    use super::{Clock, ClockSource, Divider, InvalidClockConfig};
 
     impl Clock<syscon::IOSC, AsyncMode> {
         /// Create a new configuration with a specified baudrate
         ///
         /// Searches for configuration values that lead to a baud rate that is
         /// within 5% accuracy of the desired baudrate. Panics, if it can't find
-        /// such parameters.
+        /// such parameters. Use [`Clock::try_new_with_baudrate`], if you need
+        /// to handle this case without panicking.
         ///
         /// Chooses the highest possibly oversampling value that will still give
         /// the desired accuracy. Please note that if the oversampling value
@@ -100,52 +318,92 @@ mod target {
         /// it during initialization, with known baud rates. If you need more
         /// control, please use [`Clock::new`] in combination with an FRG.
         ///
+        /// `baudrate` can be a `u32` or a [`Baud`] value, like `115_200.bps()`.
+        ///
         /// Assumes the internal oscillator runs at 12 MHz.
-        pub fn new_with_baudrate(baudrate: u32) -> Self {
-            fn calculate_brgval(
-                desired_baudrate: u32,
-                osrval: u8,
-            ) -> (u16, u8) {
-                let iosc_frequency = 12_000_000;
-
-                let brgval = iosc_frequency
-                    / (desired_baudrate * (osrval + 1) as u32)
-                    - 1;
-                let resulting_baudrate =
-                    iosc_frequency / (brgval + 1) / (osrval as u32 + 1);
-
-                // This subtraction should never overflow. Due to rounding, the
-                // resulting baud rate is always going to be higher than the
-                // desired one.
-                let deviation_percent = (resulting_baudrate - desired_baudrate)
-                    * 100
-                    / desired_baudrate;
-
-                (brgval as u16, deviation_percent as u8)
-            }
-            fn search_parameters(baudrate: u32) -> (u16, u8) {
-                // Look for the highest `osrval` that will give us an accuracy
-                // within 5%.
-                for osrval in (0x4..=0xf).rev() {
-                    let (brgval, deviation_percent) =
-                        calculate_brgval(baudrate, osrval);
-                    if deviation_percent < 5 {
-                        return (brgval, osrval);
-                    }
-                }
-
-                panic!("Could not find parameters that are accurate within 5%");
+        ///
+        /// [`Baud`]: ../units/struct.Baud.html
+        pub fn new_with_baudrate(baudrate: impl Rate) -> Self {
+            match Self::try_new_with_baudrate(baudrate) {
+                Ok(clock) => clock,
+                Err(_) => panic!(
+                    "Could not find parameters that are accurate within 5%"
+                ),
             }
+        }
 
-            let (brgval, osrval) = search_parameters(baudrate);
+        /// Create a new configuration with a specified baudrate, without
+        /// panicking
+        ///
+        /// Works like [`Clock::new_with_baudrate`], but returns an error, if no
+        /// parameters can be found that are accurate within 5%.
+        pub fn try_new_with_baudrate(
+            baudrate: impl Rate,
+        ) -> Result<Self, InvalidClockConfig> {
+            let divider =
+                Divider::try_from_baudrate(12_000_000, baudrate.to_hz(), 5)
+                    .ok_or(InvalidClockConfig)?;
 
-            Self {
-                brgval,
-                osrval,
+            Ok(Self {
+                brgval: divider.brgval,
+                osrval: divider.osrval,
                 _clock: PhantomData,
                 _mode: PhantomData,
+            })
+        }
+    }
+
+    impl Clock<syscon::IOSC, AsyncMode> {
+        /// Create a new configuration with a specified baudrate and
+        /// oversampling value
+        ///
+        /// Works like [`Clock::new_with_baudrate`], but uses the provided
+        /// `oversampling` value (between 5 and 16), instead of choosing the
+        /// highest one possible. Panics, if `oversampling` is out of range, or
+        /// no parameters can be found that are accurate within 5%. Use
+        /// [`Clock::try_new_with_baudrate_and_oversampling`], if you need to
+        /// handle these cases without panicking.
+        pub fn new_with_baudrate_and_oversampling(
+            baudrate: impl Rate,
+            oversampling: u8,
+        ) -> Self {
+            match Self::try_new_with_baudrate_and_oversampling(
+                baudrate,
+                oversampling,
+            ) {
+                Ok(clock) => clock,
+                Err(_) => panic!(
+                    "Invalid oversampling value, or could not find parameters \
+                    that are accurate within 5%"
+                ),
             }
         }
+
+        /// Create a new configuration with a specified baudrate and
+        /// oversampling value, without panicking
+        ///
+        /// Works like [`Clock::new_with_baudrate_and_oversampling`], but
+        /// returns an error, if `oversampling` is not between 5 and 16, or if
+        /// no parameters can be found that are accurate within 5%.
+        pub fn try_new_with_baudrate_and_oversampling(
+            baudrate: impl Rate,
+            oversampling: u8,
+        ) -> Result<Self, InvalidClockConfig> {
+            let divider = Divider::try_from_baudrate_with_oversampling(
+                12_000_000,
+                baudrate.to_hz(),
+                oversampling,
+                5,
+            )
+            .ok_or(InvalidClockConfig)?;
+
+            Ok(Self {
+                brgval: divider.brgval,
+                osrval: divider.osrval,
+                _clock: PhantomData,
+                _mode: PhantomData,
+            })
+        }
     }
 
     impl<T> super::private::Sealed for T where T: PeripheralClock {}
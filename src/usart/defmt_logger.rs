@@ -0,0 +1,239 @@
+//! defmt global logger that transmits over USART
+//!
+//! This module is only available, if the `defmt-usart` feature is enabled. It
+//! provides a [defmt] global logger, which encodes log frames using the
+//! encoding selected in the defmt crate (rzcobs by default) and transmits them
+//! over a USART instance of your choice. This is useful for boards that don't
+//! have a debugger attached all the time, which would be required for RTT.
+//!
+//! Log frames are written into a buffer, which is drained from the USART
+//! interrupt. This means logging doesn't block, unless the buffer is full.
+//!
+//! To use it, pass a USART transmitter to [`init`], and call [`on_interrupt`]
+//! from the interrupt handler of the same USART instance.
+//!
+//! Since this module defines the global logger, it can't be used together
+//! with another global logger, like `defmt-rtt`.
+//!
+//! [defmt]: https://crates.io/crates/defmt
+//! [`init`]: fn.init.html
+//! [`on_interrupt`]: fn.on_interrupt.html
+
+use core::{
+    cell::RefCell,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicPtr, Ordering},
+};
+
+use cortex_m::{
+    interrupt::{self, Mutex},
+    peripheral::NVIC,
+    register::primask,
+};
+
+use crate::pac::usart0::RegisterBlock;
+
+use super::{instances::Instance, state::Enabled, Tx};
+
+/// Size of the buffer that holds encoded frames until they are sent
+const BUFFER_SIZE: usize = 256;
+
+static REGISTERS: AtomicPtr<RegisterBlock> = AtomicPtr::new(ptr::null_mut());
+static BUFFER: Mutex<RefCell<Buffer>> = Mutex::new(RefCell::new(Buffer::new()));
+static ENCODER: Mutex<RefCell<defmt::Encoder>> =
+    Mutex::new(RefCell::new(defmt::Encoder::new()));
+
+static TAKEN: AtomicBool = AtomicBool::new(false);
+static INTERRUPTS_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Start transmitting log frames over the given USART transmitter
+///
+/// Consumes the transmitter, as it is exclusively used by the logger from now
+/// on. Also unmasks the USART interrupt in the NVIC.
+///
+/// Frames that were logged before this function was called are kept in the
+/// buffer (as far as it has room for them) and are sent after it was called.
+pub fn init<I, Mode, Throttle>(_: Tx<I, Enabled<u8, Mode>, Throttle>)
+where
+    I: Instance,
+{
+    REGISTERS.store(I::REGISTERS as *mut _, Ordering::SeqCst);
+
+    interrupt::free(|cs| {
+        if !BUFFER.borrow(cs).borrow().is_empty() {
+            enable_txrdy_interrupt();
+        }
+    });
+
+    // Sound, as the interrupt handler only accesses the buffer within a
+    // critical section.
+    unsafe { NVIC::unmask(I::INTERRUPT) };
+}
+
+/// Drain the buffer
+///
+/// Must be called from the interrupt handler of the USART instance that was
+/// passed to [`init`].
+///
+/// [`init`]: fn.init.html
+pub fn on_interrupt() {
+    interrupt::free(|cs| {
+        let mut buffer = BUFFER.borrow(cs).borrow_mut();
+        drain(&mut buffer, false);
+    });
+}
+
+/// Moves bytes from the buffer into the USART
+///
+/// If `block` is `true`, waits until the buffer is empty. Otherwise, only
+/// writes as many bytes as the USART can accept right now.
+fn drain(buffer: &mut Buffer, block: bool) {
+    let usart = REGISTERS.load(Ordering::SeqCst);
+    if usart.is_null() {
+        return;
+    }
+
+    // Sound, as the pointer was set from `Instance::REGISTERS`, and the
+    // transmitter is exclusively used by this module.
+    let usart = unsafe { &*usart };
+
+    loop {
+        if usart.stat.read().txrdy().bit_is_clear() {
+            if block {
+                continue;
+            }
+            break;
+        }
+
+        match buffer.pop() {
+            Some(byte) => {
+                // Sound, as all `u8` values are valid here.
+                usart
+                    .txdat
+                    .write(|w| unsafe { w.txdat().bits(byte.into()) });
+            }
+            None => {
+                // Writing 0 to the other bits of INTENCLR has no effect.
+                usart.intenclr.write(|w| w.txrdyclr().set_bit());
+                break;
+            }
+        }
+    }
+}
+
+fn enable_txrdy_interrupt() {
+    let usart = REGISTERS.load(Ordering::SeqCst);
+    if usart.is_null() {
+        return;
+    }
+
+    // Sound, as the pointer was set from `Instance::REGISTERS`, and writing 0
+    // to the other bits of INTENSET has no effect.
+    let usart = unsafe { &*usart };
+    usart.intenset.write(|w| w.txrdyen().set_bit());
+}
+
+fn write(bytes: &[u8]) {
+    interrupt::free(|cs| {
+        let mut buffer = BUFFER.borrow(cs).borrow_mut();
+
+        for &byte in bytes {
+            if buffer.is_full() {
+                // We're in a critical section, so the interrupt can't drain the
+                // buffer. Do it here instead.
+                drain(&mut buffer, true);
+            }
+            buffer.push(byte);
+        }
+    });
+
+    enable_txrdy_interrupt();
+}
+
+#[defmt::global_logger]
+struct Logger;
+
+unsafe impl defmt::Logger for Logger {
+    fn acquire() {
+        let active = primask::read().is_active();
+        interrupt::disable();
+
+        if TAKEN.load(Ordering::Relaxed) {
+            panic!("defmt logger taken reentrantly")
+        }
+        TAKEN.store(true, Ordering::Relaxed);
+        INTERRUPTS_ACTIVE.store(active, Ordering::Relaxed);
+
+        interrupt::free(|cs| {
+            ENCODER.borrow(cs).borrow_mut().start_frame(write)
+        });
+    }
+
+    unsafe fn flush() {
+        interrupt::free(|cs| {
+            let mut buffer = BUFFER.borrow(cs).borrow_mut();
+            drain(&mut buffer, true);
+        });
+    }
+
+    unsafe fn release() {
+        interrupt::free(|cs| ENCODER.borrow(cs).borrow_mut().end_frame(write));
+        TAKEN.store(false, Ordering::Relaxed);
+
+        if INTERRUPTS_ACTIVE.load(Ordering::Relaxed) {
+            interrupt::enable();
+        }
+    }
+
+    unsafe fn write(bytes: &[u8]) {
+        interrupt::free(|cs| {
+            ENCODER.borrow(cs).borrow_mut().write(bytes, write)
+        });
+    }
+}
+
+struct Buffer {
+    data: [u8; BUFFER_SIZE],
+    tail: usize,
+    len: usize,
+}
+
+impl Buffer {
+    const fn new() -> Self {
+        Self {
+            data: [0; BUFFER_SIZE],
+            tail: 0,
+            len: 0,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == BUFFER_SIZE
+    }
+
+    fn push(&mut self, byte: u8) {
+        if self.is_full() {
+            return;
+        }
+
+        let head = (self.tail + self.len) % BUFFER_SIZE;
+        self.data[head] = byte;
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<u8> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let byte = self.data[self.tail];
+        self.tail = (self.tail + 1) % BUFFER_SIZE;
+        self.len -= 1;
+
+        Some(byte)
+    }
+}
@@ -0,0 +1,94 @@
+use super::{flags::Interrupts, instances::Instance};
+
+/// A change of the line status
+///
+/// Events can be enabled as interrupts using [`USART::enable_event`], and
+/// queried using [`USART::pending_events`].
+///
+/// [`USART::enable_event`]: struct.USART.html#method.enable_event
+/// [`USART::pending_events`]: struct.USART.html#method.pending_events
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Event {
+    /// The CTS signal has changed
+    CtsChanged,
+
+    /// A break condition has started or ended on the receive line
+    BreakChanged,
+
+    /// A start bit has been detected on the receive line
+    StartDetected,
+}
+
+impl Event {
+    fn interrupts(&self) -> Interrupts {
+        let mut interrupts = Interrupts::default();
+
+        match self {
+            Self::CtsChanged => interrupts.DELTACTS = true,
+            Self::BreakChanged => interrupts.DELTARXBRK = true,
+            Self::StartDetected => interrupts.START = true,
+        }
+
+        interrupts
+    }
+
+    pub(super) fn enable<I: Instance>(&self) {
+        self.interrupts().enable::<I>();
+    }
+
+    pub(super) fn disable<I: Instance>(&self) {
+        self.interrupts().disable::<I>();
+    }
+}
+
+/// The events that have occurred since they were last queried
+///
+/// Returned by [`USART::pending_events`].
+///
+/// [`USART::pending_events`]: struct.USART.html#method.pending_events
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PendingEvents {
+    cts_changed: bool,
+    break_changed: bool,
+    start_detected: bool,
+}
+
+impl PendingEvents {
+    /// Indicates whether the provided event has occurred
+    pub fn contains(&self, event: Event) -> bool {
+        match event {
+            Event::CtsChanged => self.cts_changed,
+            Event::BreakChanged => self.break_changed,
+            Event::StartDetected => self.start_detected,
+        }
+    }
+
+    /// Indicates whether no event has occurred
+    pub fn is_empty(&self) -> bool {
+        !(self.cts_changed || self.break_changed || self.start_detected)
+    }
+
+    pub(super) fn take<I: Instance>() -> Self {
+        // Sound, as besides reading, we only write to a stateless register.
+        let usart = unsafe { &*I::REGISTERS };
+
+        let stat = usart.stat.read();
+        let pending = Self {
+            cts_changed: stat.deltacts().bit_is_set(),
+            break_changed: stat.deltarxbrk().bit_is_set(),
+            start_detected: stat.start().bit_is_set(),
+        };
+
+        // These flags are cleared by writing 1. Only clear the ones we've
+        // seen, so events that occur in between aren't lost.
+        usart.stat.write(|w| {
+            w.deltacts().bit(pending.cts_changed);
+            w.deltarxbrk().bit(pending.break_changed);
+            w.start().bit(pending.start_detected)
+        });
+
+        pending
+    }
+}
@@ -18,6 +18,7 @@ macro_rules! flags {
         /// [`USART::is_flag_set`]: struct.USART.html#method.is_flag_set
         /// [`usart::Tx::is_flag_set`]: struct.Tx.html#method.is_flag_set
         /// [`usart::Rx::is_flag_set`]: struct.Rx.html#method.is_flag_set
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub enum Flag {
             $(
                 #[doc = $description]
@@ -160,6 +161,7 @@ macro_rules! flags {
         /// [`usart::Rx::enable_interrupts`]: struct.Rx.html#method.enable_interrupts
         /// [`usart::Rx::disable_interrupts`]: struct.Rx.html#method.disable_interrupts
         #[allow(non_snake_case)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Interrupts {
             $($output_ty)*
         }
@@ -0,0 +1,341 @@
+//! Async API for USART
+//!
+//! Provides futures for reading from a [`Rx`] and writing to a [`Tx`], which
+//! can be awaited from an async executor, instead of busy-waiting for the
+//! USART to become ready. The futures are created by [`Rx::read_async`],
+//! [`Tx::write_async`], and [`Tx::flush_async`], which work with all word
+//! sizes.
+//!
+//! This module is only available, if the `embedded-hal-1` feature is enabled.
+//!
+//! For 8-bit words, [`Rx`] and [`Tx`] also implement the `Read` and `Write`
+//! traits from [embedded-io-async], which are built on these futures. Since
+//! the USART doesn't have a FIFO, each `read` and `write` call transfers a
+//! single byte.
+//!
+//! The futures are woken from the USART interrupt. For this to work, you need
+//! to call [`on_interrupt`] from the interrupt handler of the USART instance,
+//! and unmask the interrupt in the NVIC (see [`USART::enable_in_nvic`]). The
+//! futures enable the RXRDY, TXRDY, and TXIDLE interrupts as required, and
+//! [`on_interrupt`] disables them again, so you shouldn't use these
+//! interrupts for anything else, while using this API.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     embedded_io_async::Write as _,
+//!     pac,
+//!     usart::{
+//!         self,
+//!         state::{AsyncMode, Enabled, NoThrottle},
+//!     },
+//! };
+//!
+//! async fn greet(
+//!     tx: &mut usart::Tx<pac::USART0, Enabled<u8, AsyncMode>, NoThrottle>,
+//! ) {
+//!     // The error type is `Infallible`.
+//!     tx.write_all(b"Hello, world!").await.unwrap();
+//! }
+//!
+//! // Call this from the USART0 interrupt handler.
+//! usart::futures::on_interrupt::<pac::USART0>();
+//! ```
+//!
+//! [`Rx`]: ../struct.Rx.html
+//! [`Tx`]: ../struct.Tx.html
+//! [`Rx::read_async`]: ../struct.Rx.html#method.read_async
+//! [`Tx::write_async`]: ../struct.Tx.html#method.write_async
+//! [`Tx::flush_async`]: ../struct.Tx.html#method.flush_async
+//! [embedded-io-async]: https://crates.io/crates/embedded-io-async
+//! [`on_interrupt`]: fn.on_interrupt.html
+//! [`USART::enable_in_nvic`]: ../struct.USART.html#method.enable_in_nvic
+
+use core::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use embedded_hal::serial::{Read as _, Write as _};
+use void::Void;
+
+use crate::waker::WakerSlot;
+
+use super::{
+    flags::Interrupts,
+    instances::Instance,
+    rx::{Error, Rx},
+    state::{Enabled, Word},
+    tx::Tx,
+};
+
+/// Wake the futures that are waiting for USART instance `I`
+///
+/// Must be called from the interrupt handler of USART instance `I`. Disables
+/// the interrupts that were enabled by the futures, and wakes the futures
+/// that were waiting for them.
+pub fn on_interrupt<I>()
+where
+    I: Instance,
+{
+    // Sound, as we're only reading from a register, and the interrupts we
+    // disable are managed by this module.
+    let usart = unsafe { &*I::REGISTERS };
+
+    let intstat = usart.intstat.read();
+
+    if intstat.rxrdy().bit_is_set() {
+        Interrupts {
+            RXRDY: true,
+            ..Interrupts::default()
+        }
+        .disable::<I>();
+        I::wakers().rx.wake();
+    }
+    if intstat.txrdy().bit_is_set() || intstat.txidle().bit_is_set() {
+        Interrupts {
+            TXRDY: true,
+            TXIDLE: true,
+            ..Interrupts::default()
+        }
+        .disable::<I>();
+        I::wakers().tx.wake();
+    }
+}
+
+impl<I, W, Mode> Rx<I, Enabled<W, Mode>>
+where
+    I: Instance,
+    W: Word,
+{
+    /// Read a single word asynchronously
+    ///
+    /// Returns a future that resolves, once a word has been received. Please
+    /// refer to the [module documentation] for the requirements.
+    ///
+    /// [module documentation]: futures/index.html
+    pub fn read_async(&mut self) -> ReadFuture<'_, I, W, Mode> {
+        ReadFuture { rx: self }
+    }
+}
+
+impl<I, W, Mode, Throttle> Tx<I, Enabled<W, Mode>, Throttle>
+where
+    I: Instance,
+    W: Word,
+{
+    /// Write a single word asynchronously
+    ///
+    /// Returns a future that resolves, once the word has been written to the
+    /// transmitter. Please refer to the [module documentation] for the
+    /// requirements.
+    ///
+    /// [module documentation]: futures/index.html
+    pub fn write_async(
+        &mut self,
+        word: W,
+    ) -> WriteFuture<'_, I, W, Mode, Throttle> {
+        WriteFuture {
+            tx: self,
+            word: word.into(),
+        }
+    }
+
+    /// Wait asynchronously until all written words have been sent
+    ///
+    /// Returns a future that resolves, once the transmitter is idle. Please
+    /// refer to the [module documentation] for the requirements.
+    ///
+    /// [module documentation]: futures/index.html
+    pub fn flush_async(&mut self) -> FlushFuture<'_, I, W, Mode, Throttle> {
+        FlushFuture { tx: self }
+    }
+}
+
+impl<I, Mode> embedded_io_async::ErrorType for Rx<I, Enabled<u8, Mode>>
+where
+    I: Instance,
+{
+    type Error = Error<u8>;
+}
+
+impl<I, Mode> embedded_io_async::Read for Rx<I, Enabled<u8, Mode>>
+where
+    I: Instance,
+{
+    /// Reads a single byte into `buf`
+    ///
+    /// Waits until a byte has been received, unless `buf` is empty.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        match buf.first_mut() {
+            Some(slot) => {
+                *slot = self.read_async().await?;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+impl<I, Mode, Throttle> embedded_io_async::ErrorType
+    for Tx<I, Enabled<u8, Mode>, Throttle>
+where
+    I: Instance,
+{
+    type Error = Infallible;
+}
+
+impl<I, Mode, Throttle> embedded_io_async::Write
+    for Tx<I, Enabled<u8, Mode>, Throttle>
+where
+    I: Instance,
+{
+    /// Writes a single byte from `buf`
+    ///
+    /// Waits until the transmitter is ready, unless `buf` is empty.
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        match buf.first() {
+            Some(&word) => {
+                if let Err(void) = self.write_async(word).await {
+                    void::unreachable(void)
+                }
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Waits until all written bytes have been sent
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        if let Err(void) = self.flush_async().await {
+            void::unreachable(void)
+        }
+        Ok(())
+    }
+}
+
+/// Future returned by [`Rx::read_async`]
+///
+/// [`Rx::read_async`]: ../struct.Rx.html#method.read_async
+pub struct ReadFuture<'r, I, W, Mode>
+where
+    W: Word,
+{
+    rx: &'r mut Rx<I, Enabled<W, Mode>>,
+}
+
+impl<I, W, Mode> Future for ReadFuture<'_, I, W, Mode>
+where
+    I: Instance,
+    W: Word,
+{
+    type Output = Result<W, Error<W>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        // Register first, so a word received right after the read below can't
+        // get lost.
+        I::wakers().rx.register(cx.waker());
+
+        match self.rx.read() {
+            Ok(word) => Poll::Ready(Ok(word)),
+            Err(nb::Error::Other(error)) => Poll::Ready(Err(error)),
+            Err(nb::Error::WouldBlock) => {
+                self.rx.enable_interrupts(Interrupts {
+                    RXRDY: true,
+                    ..Interrupts::default()
+                });
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Future returned by [`Tx::write_async`]
+///
+/// [`Tx::write_async`]: ../struct.Tx.html#method.write_async
+pub struct WriteFuture<'r, I, W, Mode, Throttle>
+where
+    W: Word,
+{
+    tx: &'r mut Tx<I, Enabled<W, Mode>, Throttle>,
+    word: u16,
+}
+
+impl<I, W, Mode, Throttle> Future for WriteFuture<'_, I, W, Mode, Throttle>
+where
+    I: Instance,
+    W: Word,
+{
+    type Output = Result<(), Void>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        I::wakers().tx.register(cx.waker());
+
+        let word = W::from_u16(self.word);
+        match self.tx.write(word) {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(nb::Error::Other(error)) => Poll::Ready(Err(error)),
+            Err(nb::Error::WouldBlock) => {
+                self.tx.enable_interrupts(Interrupts {
+                    TXRDY: true,
+                    ..Interrupts::default()
+                });
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Future returned by [`Tx::flush_async`]
+///
+/// [`Tx::flush_async`]: ../struct.Tx.html#method.flush_async
+pub struct FlushFuture<'r, I, W, Mode, Throttle>
+where
+    W: Word,
+{
+    tx: &'r mut Tx<I, Enabled<W, Mode>, Throttle>,
+}
+
+impl<I, W, Mode, Throttle> Future for FlushFuture<'_, I, W, Mode, Throttle>
+where
+    I: Instance,
+    W: Word,
+{
+    type Output = Result<(), Void>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        I::wakers().tx.register(cx.waker());
+
+        match self.tx.flush() {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(nb::Error::Other(error)) => Poll::Ready(Err(error)),
+            Err(nb::Error::WouldBlock) => {
+                self.tx.enable_interrupts(Interrupts {
+                    TXIDLE: true,
+                    ..Interrupts::default()
+                });
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Storage for the wakers of a USART instance
+///
+/// This type is an internal implementation detail.
+pub struct Wakers {
+    rx: WakerSlot,
+    tx: WakerSlot,
+}
+
+impl Wakers {
+    pub(super) const fn new() -> Self {
+        Self {
+            rx: WakerSlot::new(),
+            tx: WakerSlot::new(),
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Idle-line framing for received data
+//!
+//! Some protocols, Modbus RTU in particular, don't use delimiters to frame
+//! packets. Instead, a packet ends, once the line has been idle for a given
+//! time. [`Receiver`] collects the received bytes into a buffer, and reports a
+//! complete frame once that gap has been detected.
+//!
+//! The USART's RXIDLE flag only indicates that no character is being received
+//! right now, which is also the case between two characters of the same frame.
+//! That's why the gap is measured with a timer, which is restarted whenever a
+//! byte is received. Any timer that implements
+//! `embedded_hal::timer::CountDown` can be used, for example an MRT channel.
+//!
+//! [`Receiver::poll_frame`] needs to be called regularly, at least once per
+//! received byte, to not lose any data, and once more after the gap, to detect
+//! the end of the frame. This can be done from the main loop, or by calling it
+//! from both the USART interrupt (with RXRDY enabled) and the timer interrupt.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use core::convert::TryFrom;
+//!
+//! use lpc8xx_hal::{mrt, usart, Peripherals};
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut swm    = p.SWM.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! #[cfg(feature = "82x")]
+//! let clock_config = {
+//!     syscon.uartfrg.set_clkdiv(6);
+//!     syscon.uartfrg.set_frgmult(22);
+//!     syscon.uartfrg.set_frgdiv(0xff);
+//!     usart::Clock::new(&syscon.uartfrg, 0, 16)
+//! };
+//! #[cfg(feature = "845")]
+//! let clock_config = usart::Clock::new_with_baudrate(115200);
+//!
+//! let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+//!     p.pins.pio0_0.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//! let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+//!     p.pins.pio0_4.into_swm_pin(),
+//!     &mut swm_handle,
+//! );
+//!
+//! let serial = p.USART0.enable_async(
+//!     &clock_config,
+//!     &mut syscon.handle,
+//!     u0_rxd,
+//!     u0_txd,
+//!     usart::Settings::default(),
+//! );
+//!
+//! let timer = p.MRT0.split(&mut syscon.handle).mrt0;
+//!
+//! static mut BUFFER: [u8; 256] = [0; 256];
+//!
+//! // At 115200 baud, 3.5 characters take about 300 µs, which is 3600 ticks
+//! // at the default system clock of 12 MHz.
+//! let gap = mrt::Ticks::try_from(3600u32).unwrap();
+//!
+//! let mut receiver = usart::idle::Receiver::new(
+//!     serial.rx,
+//!     unsafe { &mut BUFFER },
+//!     timer,
+//!     gap,
+//! );
+//!
+//! loop {
+//!     if let Ok(frame) = receiver.poll_frame() {
+//!         // process frame
+//!     }
+//! }
+//! ```
+//!
+//! [`Receiver`]: struct.Receiver.html
+//! [`Receiver::poll_frame`]: struct.Receiver.html#method.poll_frame
+
+use embedded_hal::{serial::Read, timer::CountDown};
+
+use super::{instances::Instance, rx::Rx, state::Enabled};
+
+/// Receives frames that are delimited by an idle line
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct Receiver<I, Mode, T>
+where
+    T: CountDown,
+{
+    rx: Rx<I, Enabled<u8, Mode>>,
+    buffer: &'static mut [u8],
+    timer: T,
+    gap: T::Time,
+    len: usize,
+    overflow: bool,
+}
+
+impl<I, Mode, T> Receiver<I, Mode, T>
+where
+    I: Instance,
+    T: CountDown,
+    T::Time: Clone,
+{
+    /// Create a new `Receiver`
+    ///
+    /// `buffer` must be large enough for the largest expected frame. `gap` is
+    /// the time the line needs to be idle, for a frame to be considered
+    /// complete.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `buffer` has a length of 0.
+    pub fn new(
+        rx: Rx<I, Enabled<u8, Mode>>,
+        buffer: &'static mut [u8],
+        timer: T,
+        gap: T::Time,
+    ) -> Self {
+        assert!(!buffer.is_empty());
+
+        Self {
+            rx,
+            buffer,
+            timer,
+            gap,
+            len: 0,
+            overflow: false,
+        }
+    }
+
+    /// Poll for a received frame
+    ///
+    /// Reads all bytes that are currently available from the USART. Returns
+    /// the frame, once the line has been idle for the configured gap after
+    /// receiving it, and `WouldBlock`, if no complete frame is available yet.
+    ///
+    /// If a receive error occurs, the frame that is currently being received
+    /// is discarded.
+    pub fn poll_frame(&mut self) -> nb::Result<&[u8], Error> {
+        loop {
+            match self.rx.read() {
+                Ok(byte) => {
+                    if self.len < self.buffer.len() {
+                        self.buffer[self.len] = byte;
+                        self.len += 1;
+                    } else {
+                        self.overflow = true;
+                    }
+
+                    self.timer.start(self.gap.clone());
+                }
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(error)) => {
+                    self.discard();
+                    return Err(nb::Error::Other(Error::Usart(error)));
+                }
+            }
+        }
+
+        if self.len == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        // The timer is restarted with every received byte, so it only expires
+        // once the line has been idle for the full gap.
+        if self.timer.wait().is_err() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        let len = self.len;
+        let overflow = self.overflow;
+        self.discard();
+
+        if overflow {
+            return Err(nb::Error::Other(Error::FrameTooLong));
+        }
+
+        Ok(&self.buffer[..len])
+    }
+
+    /// Return the receiver, buffer, and timer
+    pub fn free(self) -> (Rx<I, Enabled<u8, Mode>>, &'static mut [u8], T) {
+        (self.rx, self.buffer, self.timer)
+    }
+
+    fn discard(&mut self) {
+        self.len = 0;
+        self.overflow = false;
+    }
+}
+
+/// An idle-line framing error
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A received frame didn't fit into the buffer
+    FrameTooLong,
+
+    /// The USART reported an error
+    Usart(super::Error<u8>),
+}
@@ -7,6 +7,11 @@ use crate::{
     syscon::{self, clock_source::PeripheralClockSelector},
 };
 
+use super::buffered::Buffers;
+
+#[cfg(feature = "embedded-hal-1")]
+use super::futures::Wakers;
+
 /// Implemented for all USART instances
 pub trait Instance:
     private::Sealed
@@ -41,6 +46,13 @@ pub trait Instance:
 
     /// The DMA channel used with this instance for transmitting
     type TxChannel: dma::channels::Instance;
+
+    /// The storage for the wakers of the async API
+    #[cfg(feature = "embedded-hal-1")]
+    fn wakers() -> &'static Wakers;
+
+    /// The storage for the buffers of the buffered API
+    fn buffers() -> &'static Buffers;
 }
 
 macro_rules! instances {
@@ -75,6 +87,17 @@ macro_rules! instances {
 
                 type RxChannel = dma::$rx_channel;
                 type TxChannel = dma::$tx_channel;
+
+                #[cfg(feature = "embedded-hal-1")]
+                fn wakers() -> &'static Wakers {
+                    static WAKERS: Wakers = Wakers::new();
+                    &WAKERS
+                }
+
+                fn buffers() -> &'static Buffers {
+                    static BUFFERS: Buffers = Buffers::new();
+                    &BUFFERS
+                }
             }
 
             impl PeripheralClockSelector for pac::$instance {
@@ -106,6 +129,28 @@ instances!(
         Channel8, Channel9;
 );
 
+/// Implemented for USART instances that can wake up from deep-sleep
+///
+/// See [`USART::enable_wakeup_on_receive`].
+///
+/// [`USART::enable_wakeup_on_receive`]: struct.USART.html#method.enable_wakeup_on_receive
+pub trait WakeUpInstance: Instance {
+    /// The wake-up interrupt of this instance
+    type WakeUp: syscon::WakeUpInterrupt;
+}
+
+impl WakeUpInstance for pac::USART0 {
+    type WakeUp = syscon::Usart0Wakeup;
+}
+
+impl WakeUpInstance for pac::USART1 {
+    type WakeUp = syscon::Usart1Wakeup;
+}
+
+impl WakeUpInstance for pac::USART2 {
+    type WakeUp = syscon::Usart2Wakeup;
+}
+
 mod private {
     pub trait Sealed {}
 }
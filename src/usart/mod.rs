@@ -71,6 +71,7 @@
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
 mod clock;
+mod event;
 mod flags;
 mod instances;
 mod peripheral;
@@ -78,14 +79,24 @@ mod rx;
 mod settings;
 mod tx;
 
+pub mod buffered;
+#[cfg(feature = "defmt-usart")]
+pub mod defmt_logger;
+#[cfg(feature = "embedded-hal-1")]
+pub mod futures;
+pub mod idle;
+pub mod rs485;
 pub mod state;
 
 pub use self::{
-    clock::{Clock, ClockSource},
+    buffered::Buffered,
+    clock::{Clock, ClockSource, Divider, InvalidClockConfig},
+    event::{Event, PendingEvents},
     flags::{Flag, Interrupts},
-    instances::Instance,
-    peripheral::USART,
-    rx::{Error, Rx},
+    instances::{Instance, WakeUpInstance},
+    peripheral::{Token, USART},
+    rs485::Rs485Tx,
+    rx::{AutobaudError, Error, Rx, WordStatus},
     settings::Settings,
     tx::Tx,
 };
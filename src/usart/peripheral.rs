@@ -1,9 +1,10 @@
-use core::fmt;
+use core::{fmt, marker::PhantomData};
 
 use embedded_hal::{
     blocking::serial::write::Default as BlockingWriteDefault,
     serial::{Read, Write},
 };
+use nb::block;
 use void::Void;
 
 use crate::{
@@ -14,9 +15,10 @@ use crate::{
 
 use super::{
     clock::{Clock, ClockSource},
+    event::{Event, PendingEvents},
     flags::{Flag, Interrupts},
-    instances::Instance,
-    rx::{Error, Rx},
+    instances::{Instance, WakeUpInstance},
+    rx::{AutobaudError, Error, Rx},
     settings::Settings,
     state::{AsyncMode, Enabled, NoThrottle, SyncMode, Word},
     tx::Tx,
@@ -132,6 +134,49 @@ where
         }
     }
 
+    /// Enable the USART in asynchronous half-duplex mode
+    ///
+    /// Like [`enable_async`], but for single-wire buses, where transmitting and
+    /// receiving happens on the same pin. This requires the RX and TX functions
+    /// to be assigned to the same pin, which is checked at compile-time.
+    ///
+    /// Since the receiver is connected to the same pin, it receives everything
+    /// that is sent. Use [`bwrite_all_half_duplex`] to send data, which takes
+    /// care of this echo, so the receiver is ready for the response once it
+    /// returns.
+    ///
+    /// The pin must not be driven while the transmitter is idle, so that other
+    /// devices on the bus can transmit. This means it must be configured as
+    /// open-drain, with an external pull-up resistor, before any data is sent.
+    /// Use [`Pin::set_open_drain`] for this.
+    ///
+    /// This method is only available, if `USART` is in the [`Disabled`] state.
+    /// Code that attempts to call this method when the peripheral is already
+    /// enabled will not compile.
+    ///
+    /// Consumes this instance of `USART` and returns another instance that has
+    /// its `State` type parameter set to [`Enabled`].
+    ///
+    /// [`enable_async`]: #method.enable_async
+    /// [`bwrite_all_half_duplex`]: #method.bwrite_all_half_duplex
+    /// [`Pin::set_open_drain`]: ../pins/struct.Pin.html#method.set_open_drain
+    /// [`Disabled`]: ../init_state/struct.Disabled.html
+    /// [`Enabled`]: state/struct.Enabled.html
+    pub fn enable_async_half_duplex<Pin, CLOCK, W>(
+        self,
+        clock: &Clock<CLOCK, AsyncMode>,
+        syscon: &mut syscon::Handle,
+        rx: swm::Function<I::Rx, swm::state::Assigned<Pin>>,
+        tx: swm::Function<I::Tx, swm::state::Assigned<Pin>>,
+        settings: Settings<W>,
+    ) -> USART<I, Enabled<W, AsyncMode>>
+    where
+        CLOCK: ClockSource,
+        W: Word,
+    {
+        self.enable_async(clock, syscon, rx, tx, settings)
+    }
+
     /// Enable the USART in synchronous mode as master
     ///
     /// Synchronous mode works with an external clock signal. The word
@@ -170,6 +215,10 @@ where
     {
         self.configure::<C>(syscon);
 
+        self.usart
+            .ctl
+            .modify(|_, w| w.cc().bit(settings.continuous_clock));
+
         self.usart
             .brg
             .write(|w| unsafe { w.brgval().bits(clock.brgval) });
@@ -229,7 +278,70 @@ where
         W: Word,
     {
         self.configure::<C>(syscon);
+        self.configure_sync_slave(settings);
+
+        USART {
+            rx: Rx::new(), // can't use `self.rx`, due to state
+            tx: Tx::new(), // can't use `self.tx`, due to state
+            usart: self.usart,
+        }
+    }
+
+    /// Enable the USART in synchronous mode as a receive-only slave
+    ///
+    /// Works like [`enable_sync_as_slave`], except that no TX pin is required.
+    /// This is intended for receiving data streams from a device that provides
+    /// the clock, like a sensor or ASIC that continuously clocks out data.
+    ///
+    /// The transmitter is disabled, so only the receiver is returned, together
+    /// with a [`Token`] that holds the peripheral instance. Pass both to
+    /// [`USART::disable_rx_only`] to disable the USART again.
+    ///
+    /// Use [`Settings::clock_pol_falling`] or [`Settings::clock_pol_rising`]
+    /// to select the clock edge on which data is sampled. Continuous streams
+    /// can be received without interruption using [`Rx::read_all`] (for 8-bit
+    /// words) or the RXRDY interrupt.
+    ///
+    /// This method is only available, if `USART` is in the [`Disabled`] state.
+    /// Code that attempts to call this method when the peripheral is already
+    /// enabled will not compile.
+    ///
+    /// Consumes this instance of `USART` and returns a receiver that has its
+    /// `State` type parameter set to [`Enabled`].
+    ///
+    /// [`enable_sync_as_slave`]: #method.enable_sync_as_slave
+    /// [`Settings::clock_pol_falling`]: struct.Settings.html#method.clock_pol_falling
+    /// [`Settings::clock_pol_rising`]: struct.Settings.html#method.clock_pol_rising
+    /// [`Rx::read_all`]: struct.Rx.html#method.read_all
+    /// [`Token`]: struct.Token.html
+    /// [`USART::disable_rx_only`]: #method.disable_rx_only
+    /// [`Disabled`]: ../init_state/struct.Disabled.html
+    /// [`Enabled`]: state/struct.Enabled.html
+    pub fn enable_sync_as_slave_rx_only<RxPin, SclkPin, C, W>(
+        mut self,
+        _clock: &C,
+        syscon: &mut syscon::Handle,
+        _: swm::Function<I::Rx, swm::state::Assigned<RxPin>>,
+        _: swm::Function<I::Sclk, swm::state::Assigned<SclkPin>>,
+        settings: Settings<W>,
+    ) -> (Rx<I, Enabled<W, SyncMode>>, Token<I, Enabled<W, SyncMode>>)
+    where
+        C: ClockSource,
+        W: Word,
+    {
+        self.configure::<C>(syscon);
+        self.usart.ctl.modify(|_, w| w.txdis().disabled());
+        self.configure_sync_slave(settings);
 
+        let token = Token {
+            usart: self.usart,
+            state: PhantomData,
+        };
+
+        (Rx::new(), token)
+    }
+
+    fn configure_sync_slave<W>(&mut self, settings: Settings<W>) {
         // We are not allowed to send or receive data when writing to CFG. This
         // is ensured by type state, so no need to do anything here.
 
@@ -240,12 +352,6 @@ where
             settings.apply(w);
             w
         });
-
-        USART {
-            rx: Rx::new(), // can't use `self.rx`, due to state
-            tx: Tx::new(), // can't use `self.tx`, due to state
-            usart: self.usart,
-        }
     }
 
     fn configure<C>(&mut self, syscon: &mut syscon::Handle)
@@ -259,6 +365,7 @@ where
             w.txbrken().normal();
             w.addrdet().disabled();
             w.txdis().enabled();
+            // Can be overridden by `Settings` in synchronous master mode.
             w.cc().continous_clock();
             w.autobaud().disabled()
         });
@@ -271,7 +378,7 @@ where
         // Disable CTS; can be enabled by the user later.
         w.ctsen().disabled();
 
-        // No loopback mode; currently it's not supported.
+        // No loopback mode. Can be overridden by `Settings`.
         w.loop_().normal();
 
         // Enable automatic address matching. This makes no difference until we
@@ -307,6 +414,148 @@ where
         }
     }
 
+    /// Split the USART into transmitter and receiver
+    ///
+    /// Returns the transmitter and receiver, which can then be moved into
+    /// different contexts, for example an interrupt handler and the main loop.
+    /// The returned [`Token`] keeps the peripheral instance, and can be passed
+    /// to [`USART::join`] together with the transmitter and receiver, to get
+    /// back the `USART` (for example, to disable it).
+    ///
+    /// [`Token`]: struct.Token.html
+    /// [`USART::join`]: #method.join
+    pub fn split(
+        self,
+    ) -> (
+        Tx<I, Enabled<W, Mode>, NoThrottle>,
+        Rx<I, Enabled<W, Mode>>,
+        Token<I, Enabled<W, Mode>>,
+    ) {
+        let token = Token {
+            usart: self.usart,
+            state: PhantomData,
+        };
+
+        (self.tx, self.rx, token)
+    }
+
+    /// Join transmitter and receiver back into a USART
+    ///
+    /// This is the inverse of [`USART::split`]. If CTS throttling was enabled
+    /// on the transmitter, it needs to be disabled before calling this method.
+    ///
+    /// [`USART::split`]: #method.split
+    pub fn join(
+        tx: Tx<I, Enabled<W, Mode>, NoThrottle>,
+        rx: Rx<I, Enabled<W, Mode>>,
+        token: Token<I, Enabled<W, Mode>>,
+    ) -> Self {
+        USART {
+            rx,
+            tx,
+            usart: token.usart,
+        }
+    }
+
+    /// Disable a USART that has been enabled without a transmitter
+    ///
+    /// This is the counterpart to [`USART::enable_sync_as_slave_rx_only`],
+    /// which only returns the receiver and a [`Token`].
+    ///
+    /// [`USART::enable_sync_as_slave_rx_only`]: #method.enable_sync_as_slave_rx_only
+    /// [`Token`]: struct.Token.html
+    pub fn disable_rx_only(
+        _rx: Rx<I, Enabled<W, Mode>>,
+        token: Token<I, Enabled<W, Mode>>,
+        syscon: &mut syscon::Handle,
+    ) -> USART<I, Disabled> {
+        syscon.disable_clock(&token.usart);
+
+        USART {
+            rx: Rx::new(),
+            tx: Tx::new(),
+            usart: token.usart,
+        }
+    }
+
+    /// Change the settings of the enabled USART
+    ///
+    /// Waits until the transmitter is idle, then briefly disables the USART,
+    /// applies `settings`, and enables the USART again. This can be used to
+    /// change data length, parity, stop bits, polarities, and loopback mode,
+    /// without disabling the USART. The word type can't be changed, so
+    /// switching between 9-bit words and 7/8-bit words still requires
+    /// disabling the USART.
+    ///
+    /// The clock generation setting ([`Settings::clock_on_demand`]) is not
+    /// changed by this method.
+    ///
+    /// Any word that is being received while the USART is disabled is lost.
+    ///
+    /// [`Settings::clock_on_demand`]: struct.Settings.html#method.clock_on_demand
+    pub fn reconfigure(&mut self, settings: Settings<W>) {
+        if let Err(void) = block!(self.tx.flush()) {
+            void::unreachable(void)
+        }
+
+        // We are not allowed to send or receive data when writing to CFG, so
+        // disable the USART first.
+        self.usart.cfg.modify(|_, w| w.enable().disabled());
+        self.usart.cfg.modify(|_, w| {
+            settings.apply(w);
+            w
+        });
+        self.usart.cfg.modify(|_, w| w.enable().enabled());
+    }
+
+    /// Change only the loopback setting of the enabled USART
+    ///
+    /// Works like [`USART::reconfigure`], but leaves all other settings alone.
+    /// Returns whether loopback mode was enabled before.
+    ///
+    /// [`USART::reconfigure`]: #method.reconfigure
+    pub(crate) fn set_loopback(&mut self, loopback: bool) -> bool {
+        if let Err(void) = block!(self.tx.flush()) {
+            void::unreachable(void)
+        }
+
+        let was_enabled = self.usart.cfg.read().loop_().bit_is_set();
+
+        self.usart.cfg.modify(|_, w| w.enable().disabled());
+        self.usart.cfg.modify(|_, w| w.loop_().bit(loopback));
+        self.usart.cfg.modify(|_, w| w.enable().enabled());
+
+        was_enabled
+    }
+
+    /// Put the receiver into address detection mode
+    ///
+    /// Only received words that have the most significant bit set (bit 8 in
+    /// 9-bit mode, bit 7 in 8-bit mode), and that match `address`, will be
+    /// received. All other data is ignored by the hardware. This can be used
+    /// to build multi-drop networks, for example based on RS-485, where each
+    /// node only receives the frames addressed to it.
+    ///
+    /// Once the matched address has been received, call
+    /// [`USART::stop_address_detection`] to receive the rest of the frame.
+    ///
+    /// See [`Rx::start_address_detection`] for details.
+    ///
+    /// [`USART::stop_address_detection`]: #method.stop_address_detection
+    /// [`Rx::start_address_detection`]: struct.Rx.html#method.start_address_detection
+    pub fn start_address_detection(&mut self, address: u8) {
+        self.rx.start_address_detection(address);
+    }
+
+    /// Put the receiver out of address detection mode
+    ///
+    /// See [`Rx::stop_address_detection`] for details.
+    ///
+    /// [`Rx::stop_address_detection`]: struct.Rx.html#method.stop_address_detection
+    pub fn stop_address_detection(&mut self) {
+        self.rx.stop_address_detection();
+    }
+
     /// Query whether the provided flag is set
     ///
     /// Flags that need to be reset by software will be reset by this operation.
@@ -314,6 +563,32 @@ where
         flag.is_set::<I>()
     }
 
+    /// Enable the interrupt for the provided event
+    ///
+    /// Other interrupts are not affected. The interrupt still needs to be
+    /// unmasked in the NVIC (see [`enable_in_nvic`]).
+    ///
+    /// [`enable_in_nvic`]: #method.enable_in_nvic
+    pub fn enable_event(&mut self, event: Event) {
+        event.enable::<I>();
+    }
+
+    /// Disable the interrupt for the provided event
+    ///
+    /// Other interrupts are not affected.
+    pub fn disable_event(&mut self, event: Event) {
+        event.disable::<I>();
+    }
+
+    /// Returns the events that have occurred since the last call
+    ///
+    /// Resets the flags of all returned events, which also clears the
+    /// respective pending interrupts. Events are recorded, whether their
+    /// interrupt is enabled or not.
+    pub fn pending_events(&mut self) -> PendingEvents {
+        PendingEvents::take::<I>()
+    }
+
     /// Enable interrupts for this instance in the NVIC
     ///
     /// This only enables the interrupts in the NVIC. It doesn't enable any
@@ -461,6 +736,185 @@ where
     }
 }
 
+impl<I, W> USART<I, Enabled<W, AsyncMode>>
+where
+    I: Instance,
+    W: Word,
+{
+    /// Start automatic baud rate detection
+    ///
+    /// See [`Rx::start_autobaud`] for details.
+    ///
+    /// [`Rx::start_autobaud`]: struct.Rx.html#method.start_autobaud
+    pub fn start_autobaud(&mut self) {
+        self.rx.start_autobaud();
+    }
+
+    /// Check whether automatic baud rate detection has finished
+    ///
+    /// See [`Rx::autobaud_status`] for details.
+    ///
+    /// [`Rx::autobaud_status`]: struct.Rx.html#method.autobaud_status
+    pub fn autobaud_status(&mut self) -> nb::Result<(), AutobaudError> {
+        self.rx.autobaud_status()
+    }
+
+    /// Returns the current value of the baud rate generator
+    ///
+    /// See [`Rx::brgval`] for details.
+    ///
+    /// [`Rx::brgval`]: struct.Rx.html#method.brgval
+    pub fn brgval(&self) -> u16 {
+        self.rx.brgval()
+    }
+
+    /// Change the baud rate of the enabled USART
+    ///
+    /// Waits until the transmitter is idle, then briefly disables the USART,
+    /// selects the clock source, updates the baud rate generator and the
+    /// oversampling value, and enables the USART again. Pins and all other
+    /// settings remain as they are.
+    ///
+    /// Any word that is being received while the USART is disabled is lost.
+    /// Ideally, this method is only called when no data is expected, for
+    /// example after a baud rate change has been negotiated with the other
+    /// side.
+    pub fn set_baud_rate<C>(
+        &mut self,
+        clock: &Clock<C, AsyncMode>,
+        syscon: &mut syscon::Handle,
+    ) where
+        C: ClockSource,
+    {
+        if let Err(void) = block!(self.tx.flush()) {
+            void::unreachable(void)
+        }
+
+        self.usart.cfg.modify(|_, w| w.enable().disabled());
+
+        C::select(&self.usart, syscon);
+        self.usart
+            .brg
+            .write(|w| unsafe { w.brgval().bits(clock.brgval) });
+        self.usart
+            .osr
+            .write(|w| unsafe { w.osrval().bits(clock.osrval) });
+
+        self.usart.cfg.modify(|_, w| w.enable().enabled());
+    }
+}
+
+impl<I, W> USART<I, Enabled<W, AsyncMode>>
+where
+    I: WakeUpInstance,
+    W: Word,
+{
+    /// Wake up from deep-sleep or power-down mode, when data is received
+    ///
+    /// Enables the START interrupt, which fires when a start bit is detected,
+    /// even while the USART's function clock is not running. Also enables the
+    /// USART's interrupt as a wake-up source in SYSCON. The interrupt still
+    /// needs to be unmasked in the NVIC (see [`enable_in_nvic`]).
+    ///
+    /// Since the function clock needs to start up again after wake-up, the
+    /// first received word is usually lost or corrupted. Call
+    /// [`finish_wakeup_on_receive`] after waking up, to get the receiver back
+    /// into a known state.
+    ///
+    /// A USART that is only used to wake up doesn't need to be declared to
+    /// [`pmu::LowPower`].
+    ///
+    /// [`enable_in_nvic`]: #method.enable_in_nvic
+    /// [`finish_wakeup_on_receive`]: #method.finish_wakeup_on_receive
+    /// [`pmu::LowPower`]: ../pmu/struct.LowPower.html
+    pub fn enable_wakeup_on_receive(&mut self, syscon: &mut syscon::Handle) {
+        // Reset the flag, so an old start bit doesn't wake us up right away.
+        self.is_flag_set(Flag::START);
+
+        self.enable_interrupts(Interrupts {
+            START: true,
+            ..Interrupts::default()
+        });
+        syscon.enable_interrupt_wakeup::<I::WakeUp>();
+    }
+
+    /// Resynchronize the receiver after waking up
+    ///
+    /// Disables the wake-up behavior that was enabled by
+    /// [`enable_wakeup_on_receive`], and discards any words that were received
+    /// while the function clock was starting up, including any errors. After
+    /// this method returns, the receiver is ready to receive the next word.
+    ///
+    /// [`enable_wakeup_on_receive`]: #method.enable_wakeup_on_receive
+    pub fn finish_wakeup_on_receive(&mut self, syscon: &mut syscon::Handle) {
+        syscon.disable_interrupt_wakeup::<I::WakeUp>();
+        self.disable_interrupts(Interrupts {
+            START: true,
+            ..Interrupts::default()
+        });
+        self.is_flag_set(Flag::START);
+
+        loop {
+            match self.rx.read() {
+                Err(nb::Error::WouldBlock) => break,
+                _ => continue,
+            }
+        }
+    }
+}
+
+impl<I, W> USART<I, Enabled<W, AsyncMode>>
+where
+    I: Instance,
+    W: Word + Copy,
+{
+    /// Send all words in `buffer` in half-duplex mode, blocking
+    ///
+    /// Intended for use with [`enable_async_half_duplex`]. Any data that was
+    /// received before, and not read yet, is discarded. Then each word is sent,
+    /// and its echo is received and compared to the word sent. Once this
+    /// method returns, the transmission is complete and the receiver is ready
+    /// to receive the response.
+    ///
+    /// Returns [`Error::Collision`], if the echo differs from the word sent,
+    /// which means another device was transmitting at the same time. Any
+    /// other receive error is returned as-is. In both cases, the remaining
+    /// words are not sent.
+    ///
+    /// Blocks until the echo of each word is received. If the RX and TX
+    /// functions aren't assigned to the same pin, this blocks forever.
+    ///
+    /// [`enable_async_half_duplex`]: #method.enable_async_half_duplex
+    /// [`Error::Collision`]: enum.Error.html#variant.Collision
+    pub fn bwrite_all_half_duplex(
+        &mut self,
+        buffer: &[W],
+    ) -> Result<(), Error<W>> {
+        loop {
+            match self.rx.read() {
+                Err(nb::Error::WouldBlock) => break,
+                _ => continue,
+            }
+        }
+
+        for &word in buffer {
+            if let Err(void) = block!(self.tx.write(word)) {
+                void::unreachable(void)
+            }
+
+            let echo = block!(self.rx.read())?;
+
+            let sent: u16 = word.into();
+            let received: u16 = echo.into();
+            if received != sent {
+                return Err(Error::Collision(echo));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<I, State> USART<I, State>
 where
     I: Instance,
@@ -520,13 +974,74 @@ where
 {
 }
 
-impl<I, Mode> fmt::Write for USART<I, Enabled<u8, Mode>>
+#[cfg(feature = "embedded-hal-1")]
+impl<I, W, Mode> embedded_hal_nb::serial::ErrorType
+    for USART<I, Enabled<W, Mode>>
 where
-    Self: BlockingWriteDefault<u8>,
     I: Instance,
+    W: Word + Copy + fmt::Debug,
+{
+    type Error = Error<W>;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<I, W, Mode> embedded_hal_nb::serial::Read<W> for USART<I, Enabled<W, Mode>>
+where
+    I: Instance,
+    W: Word + Copy + fmt::Debug,
+{
+    /// Reads a single word from the serial interface
+    fn read(&mut self) -> nb::Result<W, Self::Error> {
+        embedded_hal::serial::Read::read(&mut self.rx)
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<I, W, Mode> embedded_hal_nb::serial::Write<W>
+    for USART<I, Enabled<W, Mode>>
+where
+    I: Instance,
+    W: Word + Copy + fmt::Debug,
+{
+    /// Writes a single word to the serial interface
+    fn write(&mut self, word: W) -> nb::Result<(), Self::Error> {
+        embedded_hal::serial::Write::write(&mut self.tx, word)
+            .map_err(|err| err.map(|void| void::unreachable(void)))
+    }
+
+    /// Ensures that none of the previously written words are still buffered
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        embedded_hal::serial::Write::flush(&mut self.tx)
+            .map_err(|err| err.map(|void| void::unreachable(void)))
+    }
+}
+
+impl<I, W, Mode> fmt::Write for USART<I, Enabled<W, Mode>>
+where
+    Self: BlockingWriteDefault<W>,
+    I: Instance,
+    W: Word,
 {
     /// Writes a string slice into this writer, returning whether the write succeeded.
     fn write_str(&mut self, s: &str) -> fmt::Result {
         self.tx.write_str(s)
     }
 }
+
+/// Proof that a USART has been split into transmitter and receiver
+///
+/// Returned by [`USART::split`], and required by [`USART::join`]. Holds the
+/// peripheral instance, while the transmitter and receiver are used
+/// separately.
+///
+/// Also returned by [`USART::enable_sync_as_slave_rx_only`], and required by
+/// [`USART::disable_rx_only`].
+///
+/// [`USART::split`]: struct.USART.html#method.split
+/// [`USART::join`]: struct.USART.html#method.join
+/// [`USART::enable_sync_as_slave_rx_only`]: struct.USART.html#method.enable_sync_as_slave_rx_only
+/// [`USART::disable_rx_only`]: struct.USART.html#method.disable_rx_only
+pub struct Token<I, State> {
+    usart: I,
+    state: PhantomData<State>,
+}
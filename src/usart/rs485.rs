@@ -0,0 +1,170 @@
+//! RS-485 driver enable handling
+//!
+//! RS-485 transceivers have a driver enable (DE) input that needs to be
+//! asserted while transmitting, and released afterwards, so other nodes on the
+//! bus can transmit. Often, the receiver enable (RE, active low) input is tied
+//! to DE.
+//!
+//! [`Rs485Tx`] pairs a [`Tx`] with a GPIO output that is connected to DE. It
+//! asserts the pin (sets it HIGH) before transmitting, and enables the TXIDLE
+//! interrupt. Once the last word has been sent completely, the interrupt fires,
+//! and [`Rs485Tx::on_interrupt`], which must be called from the interrupt
+//! handler of the USART instance, releases the pin (sets it LOW) again.
+//!
+//! The USART interrupt needs to be unmasked in the NVIC (see
+//! [`USART::enable_in_nvic`]).
+//!
+//! [`Rs485Tx`]: struct.Rs485Tx.html
+//! [`Tx`]: ../struct.Tx.html
+//! [`Rs485Tx::on_interrupt`]: struct.Rs485Tx.html#method.on_interrupt
+//! [`USART::enable_in_nvic`]: ../struct.USART.html#method.enable_in_nvic
+
+use embedded_hal::{
+    blocking::serial::write::Default as BlockingWriteDefault, serial::Write,
+};
+use void::Void;
+
+use crate::{
+    gpio::{direction, GpioPin},
+    pins,
+};
+
+use super::{
+    flags::{Flag, Interrupts},
+    instances::Instance,
+    state::{Enabled, Word},
+    tx::Tx,
+};
+
+/// A USART transmitter that controls the DE pin of an RS-485 transceiver
+///
+/// Please refer to the [module documentation] for more information.
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::serial::Write`] for non-blocking writes
+/// - [`embedded_hal::blocking::serial::Write`] for blocking writes
+///
+/// [module documentation]: index.html
+/// [`embedded_hal::serial::Write`]: #impl-Write<W>
+/// [`embedded_hal::blocking::serial::Write`]: #impl-Write<Word>
+pub struct Rs485Tx<I, W, Mode, Throttle, P>
+where
+    W: Word,
+{
+    tx: Tx<I, Enabled<W, Mode>, Throttle>,
+    de: GpioPin<P, direction::Output>,
+}
+
+impl<I, W, Mode, Throttle, P> Rs485Tx<I, W, Mode, Throttle, P>
+where
+    I: Instance,
+    W: Word,
+    P: pins::Trait,
+{
+    /// Create a new instance of `Rs485Tx`
+    ///
+    /// Releases the DE pin (sets it LOW), so the transceiver starts out in
+    /// receive mode.
+    pub fn new(
+        tx: Tx<I, Enabled<W, Mode>, Throttle>,
+        mut de: GpioPin<P, direction::Output>,
+    ) -> Self {
+        de.set_low();
+        Self { tx, de }
+    }
+
+    /// Release the DE pin, once the transmitter is idle
+    ///
+    /// Must be called from the interrupt handler of USART instance `I`. Does
+    /// nothing, if the transmitter is still busy, or if the DE pin has
+    /// already been released.
+    pub fn on_interrupt(&mut self) {
+        if self.de.is_set_low() {
+            return;
+        }
+
+        if self.tx.is_flag_set(Flag::TXIDLE) {
+            self.release();
+        }
+    }
+
+    /// Indicates whether the DE pin is currently asserted
+    pub fn is_transmitting(&self) -> bool {
+        self.de.is_set_high()
+    }
+
+    /// Return the transmitter and the DE pin
+    ///
+    /// Disables the TXIDLE interrupt, and releases the DE pin, even if a
+    /// transmission is still ongoing. Call [`flush`] first, to prevent this.
+    ///
+    /// [`flush`]: #method.flush
+    pub fn free(
+        mut self,
+    ) -> (
+        Tx<I, Enabled<W, Mode>, Throttle>,
+        GpioPin<P, direction::Output>,
+    ) {
+        self.release();
+        (self.tx, self.de)
+    }
+
+    fn release(&mut self) {
+        self.tx.disable_interrupts(Interrupts {
+            TXIDLE: true,
+            ..Interrupts::default()
+        });
+        self.de.set_low();
+    }
+}
+
+impl<I, W, Mode, Throttle, P> Write<W> for Rs485Tx<I, W, Mode, Throttle, P>
+where
+    I: Instance,
+    W: Word,
+    P: pins::Trait,
+{
+    type Error = Void;
+
+    /// Writes a single word to the serial interface
+    ///
+    /// Asserts the DE pin, if it isn't asserted already.
+    fn write(&mut self, word: W) -> nb::Result<(), Self::Error> {
+        if self.de.is_set_low() {
+            self.de.set_high();
+        }
+
+        self.tx.write(word)?;
+
+        // Writing the word has reset TXIDLE, so the interrupt won't fire
+        // before the word has been sent.
+        self.tx.enable_interrupts(Interrupts {
+            TXIDLE: true,
+            ..Interrupts::default()
+        });
+
+        Ok(())
+    }
+
+    /// Ensures that none of the previously written words are still buffered
+    ///
+    /// Releases the DE pin, once the transmitter is idle.
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.tx.flush()?;
+
+        if self.de.is_set_high() {
+            self.release();
+        }
+
+        Ok(())
+    }
+}
+
+impl<I, W, Mode, Throttle, P> BlockingWriteDefault<W>
+    for Rs485Tx<I, W, Mode, Throttle, P>
+where
+    I: Instance,
+    W: Word,
+    P: pins::Trait,
+{
+}
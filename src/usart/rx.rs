@@ -1,11 +1,14 @@
 use core::marker::PhantomData;
 
+#[cfg(feature = "embedded-hal-1")]
+use core::fmt;
+
 use cortex_m::interrupt;
 use void::Void;
 
 use crate::{
     dma::{self, transfer::state::Ready},
-    embedded_hal::serial::Read,
+    embedded_hal::{serial::Read, timer::CountDown},
     init_state,
     pac::dma0::channel::xfercfg::SRCINC_A,
 };
@@ -13,7 +16,7 @@ use crate::{
 use super::{
     flags::{Flag, Interrupts},
     instances::Instance,
-    state::{Enabled, Word},
+    state::{AsyncMode, Enabled, Word},
 };
 
 /// USART receiver
@@ -102,6 +105,39 @@ where
         });
     }
 
+    /// Recover from receive errors
+    ///
+    /// Resets all receive error flags, and discards any received data that
+    /// hasn't been read yet. After this method returns, the next call to
+    /// `read` returns the next word that is received.
+    ///
+    /// `read` resets the error flag belonging to each error it returns, so
+    /// calling this method is never required to continue receiving. It is
+    /// useful after [`Error::Overrun`] or [`Error::Framing`], which typically
+    /// indicate that the receiver has lost track of the data stream. Discarding
+    /// everything that is left lets the application resynchronize at a known
+    /// point, for example the start of the next packet.
+    ///
+    /// [`Error::Overrun`]: enum.Error.html#variant.Overrun
+    /// [`Error::Framing`]: enum.Error.html#variant.Framing
+    pub fn recover(&mut self) {
+        // Sound, as we're only reading from `rxdat` and `stat`, and writing to
+        // a stateless register.
+        let usart = unsafe { &*I::REGISTERS };
+
+        while usart.stat.read().rxrdy().bit_is_set() {
+            usart.rxdat.read();
+        }
+
+        usart.stat.write(|w| {
+            w.overrunint().set_bit();
+            w.framerrint().set_bit();
+            w.parityerrint().set_bit();
+            w.rxnoiseint().set_bit();
+            w.deltarxbrk().set_bit()
+        });
+    }
+
     /// Query whether the provided flag is set
     ///
     /// Flags that need to be reset by software will be reset by this operation.
@@ -228,6 +264,243 @@ where
     pub fn disable_interrupts(&mut self, interrupts: Interrupts) {
         interrupts.disable::<I>();
     }
+
+    /// Reads until the provided buffer is full, or the timer expires
+    ///
+    /// Blocks until `buffer` has been filled, but not longer than it takes
+    /// `timer` to expire. The timer must have been started by the caller, which
+    /// allows it to use whatever time unit the timer supports, and to share a
+    /// single deadline between multiple calls.
+    ///
+    /// Returns [`Error::TimedOut`], if the timer expires first. The words that
+    /// have been received up to that point are left in `buffer`. Any other
+    /// receive error aborts the read immediately.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use core::convert::TryFrom;
+    ///
+    /// use lpc8xx_hal::{mrt, usart};
+    ///
+    /// # use lpc8xx_hal::Peripherals;
+    /// #
+    /// # let mut p = Peripherals::take().unwrap();
+    /// #
+    /// # let mut syscon = p.SYSCON.split();
+    /// # let mut swm    = p.SWM.split();
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let mut swm_handle = swm.handle;
+    /// # #[cfg(feature = "845")]
+    /// # let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let clock_config = {
+    /// #     syscon.uartfrg.set_clkdiv(6);
+    /// #     syscon.uartfrg.set_frgmult(22);
+    /// #     syscon.uartfrg.set_frgdiv(0xff);
+    /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
+    /// # };
+    /// # #[cfg(feature = "845")]
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// #
+    /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
+    /// #     p.pins.pio0_0.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// # let (u0_txd, _) = swm.movable_functions.u0_txd.assign(
+    /// #     p.pins.pio0_4.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// #
+    /// # let mut usart = p.USART0.enable_async(
+    /// #     &clock_config,
+    /// #     &mut syscon.handle,
+    /// #     u0_rxd,
+    /// #     u0_txd,
+    /// #     usart::Settings::default(),
+    /// # );
+    /// #
+    /// let mut mrt = p.MRT0.split(&mut syscon.handle).mrt0;
+    ///
+    /// let mut buffer = [0; 4];
+    ///
+    /// // Wait at most 1_200_000 ticks (100 ms at 12 MHz) for the response.
+    /// mrt.start(mrt::Ticks::try_from(1_200_000u32).unwrap());
+    /// match usart.rx.read_exact_timeout(&mut buffer, &mut mrt) {
+    ///     Ok(()) => { /* process response */ }
+    ///     Err(usart::Error::TimedOut) => { /* no response */ }
+    ///     Err(_) => { /* receive error */ }
+    /// }
+    /// ```
+    ///
+    /// [`Error::TimedOut`]: enum.Error.html#variant.TimedOut
+    pub fn read_exact_timeout<T>(
+        &mut self,
+        buffer: &mut [W],
+        timer: &mut T,
+    ) -> Result<(), Error<W>>
+    where
+        T: CountDown,
+    {
+        for slot in buffer {
+            *slot = loop {
+                match self.read() {
+                    Ok(word) => break word,
+                    Err(nb::Error::Other(error)) => return Err(error),
+                    Err(nb::Error::WouldBlock) => {}
+                }
+
+                if timer.wait().is_ok() {
+                    return Err(Error::TimedOut);
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Reads a single word, together with its receive status
+    ///
+    /// Unlike `read`, this method doesn't turn framing, parity, or noise errors
+    /// into an error. Instead, it returns the received word, together with the
+    /// status flags that belong to exactly that word. This is useful for
+    /// protocols that can tolerate individual corrupted words.
+    ///
+    /// Overruns and breaks don't belong to a specific word, and are still
+    /// reported as [`Error::Overrun`] and [`Error::Break`] respectively.
+    ///
+    /// [`Error::Overrun`]: enum.Error.html#variant.Overrun
+    /// [`Error::Break`]: enum.Error.html#variant.Break
+    pub fn read_with_status(
+        &mut self,
+    ) -> nb::Result<(W, WordStatus), Error<W>> {
+        // Sound, as we're only reading from `stat`, and `rxdatstat` is
+        // exclusively accessed by this method and `read`.
+        let usart = unsafe { &*I::REGISTERS };
+
+        let stat = usart.stat.read();
+
+        if stat.rxbrk().bit_is_set() {
+            // Report the start of a break once, then wait for it to end.
+            if stat.deltarxbrk().bit_is_set() {
+                usart.stat.write(|w| w.deltarxbrk().set_bit());
+                return Err(nb::Error::Other(Error::Break));
+            }
+
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if stat.rxrdy().bit_is_clear() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        // It's important to read this register all at once, as reading it
+        // changes the status flags.
+        let rx_dat_stat = usart.rxdatstat.read();
+
+        let word = Word::from_u16(rx_dat_stat.rxdat().bits());
+        let status = WordStatus {
+            FRAMERR: rx_dat_stat.framerr().bit_is_set(),
+            PARITYERR: rx_dat_stat.parityerr().bit_is_set(),
+            RXNOISE: rx_dat_stat.rxnoise().bit_is_set(),
+        };
+
+        // The sticky flags in STAT have been replaced by the status returned
+        // here, so reset them.
+        usart.stat.write(|w| {
+            w.framerrint().bit(status.FRAMERR);
+            w.parityerrint().bit(status.PARITYERR);
+            w.rxnoiseint().bit(status.RXNOISE)
+        });
+
+        if stat.overrunint().bit_is_set() {
+            usart.stat.write(|w| w.overrunint().set_bit());
+            return Err(nb::Error::Other(Error::Overrun(word)));
+        }
+
+        Ok((word, status))
+    }
+}
+
+impl<I, W> Rx<I, Enabled<W, AsyncMode>>
+where
+    I: Instance,
+    W: Word,
+{
+    /// Start automatic baud rate detection
+    ///
+    /// The USART measures the start bit of the next received character and
+    /// loads the baud rate generator accordingly. The least significant bit of
+    /// that character must be `1` (as is the case for `A`, `a`, or `0x55`),
+    /// and the character itself is discarded.
+    ///
+    /// Since the transmitter uses the same baud rate generator, it will
+    /// operate at the detected baud rate too, once detection has finished.
+    ///
+    /// Use [`Rx::autobaud_status`] to find out whether detection has finished.
+    ///
+    /// [`Rx::autobaud_status`]: #method.autobaud_status
+    pub fn start_autobaud(&mut self) {
+        // Sound, as access to CTL is protected by a critical section, and we
+        // only write to a stateless register otherwise.
+        let usart = unsafe { &*I::REGISTERS };
+
+        // Clear a leftover error from a previous attempt.
+        usart.stat.write(|w| w.aberr().set_bit());
+
+        interrupt::free(|_| {
+            usart.ctl.modify(|_, w| w.autobaud().enabled());
+        });
+    }
+
+    /// Check whether automatic baud rate detection has finished
+    ///
+    /// Returns `WouldBlock`, while detection is still in progress. Use
+    /// `nb::block!` to wait for detection to finish.
+    ///
+    /// If the baud rate was too low to be measured, detection is stopped and
+    /// [`AutobaudError`] is returned. Detection can be restarted using
+    /// [`Rx::start_autobaud`].
+    ///
+    /// [`AutobaudError`]: struct.AutobaudError.html
+    /// [`Rx::start_autobaud`]: #method.start_autobaud
+    pub fn autobaud_status(&mut self) -> nb::Result<(), AutobaudError> {
+        // Sound, as access to CTL is protected by a critical section, and we
+        // only write to a stateless register otherwise.
+        let usart = unsafe { &*I::REGISTERS };
+
+        if usart.stat.read().aberr().bit_is_set() {
+            interrupt::free(|_| {
+                usart.ctl.modify(|_, w| w.autobaud().disabled());
+            });
+
+            // Writing 1 clears the flag.
+            usart.stat.write(|w| w.aberr().set_bit());
+
+            return Err(nb::Error::Other(AutobaudError));
+        }
+
+        // The hardware clears AUTOBAUD once detection has finished.
+        if usart.ctl.read().autobaud().bit_is_set() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the current value of the baud rate generator
+    ///
+    /// After automatic baud rate detection has finished, this is the value
+    /// that was detected. The baud rate is the USART function clock, divided
+    /// by `brgval + 1` and by the oversampling value.
+    pub fn brgval(&self) -> u16 {
+        // Sound, as we're only reading from a register.
+        let usart = unsafe { &*I::REGISTERS };
+
+        usart.brg.read().brgval().bits()
+    }
 }
 
 impl<I, Mode> Rx<I, Enabled<u8, Mode>>
@@ -236,16 +509,110 @@ where
 {
     /// Reads until the provided buffer is full, using DMA
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    /// Returns an error, if the length of `buffer` is 0 or larger than 1024.
+    /// The error is returned together with the receiver, buffer, and channel.
     pub fn read_all(
         self,
         buffer: &'static mut [u8],
         channel: dma::Channel<I::RxChannel, init_state::Enabled>,
-    ) -> dma::Transfer<Ready, I::RxChannel, Self, &'static mut [u8]> {
+    ) -> Result<
+        dma::Transfer<Ready, I::RxChannel, Self, &'static mut [u8]>,
+        (
+            dma::transfer::InvalidTransfer,
+            dma::Payload<I::RxChannel, Self, &'static mut [u8]>,
+        ),
+    > {
         dma::Transfer::new(channel, self, buffer)
     }
+
+    /// Continuously receive into a ring buffer, using DMA
+    ///
+    /// Starts a DMA transfer that never finishes on its own, and writes all
+    /// received data into `buffer`, wrapping around at its end. Use
+    /// [`CircularTransfer::read`] to read out the received data, and
+    /// [`CircularTransfer::stop`] to get back the receiver, channel, and
+    /// buffer.
+    ///
+    /// Since the data is moved out of the receiver by the DMA, without the CPU
+    /// being involved, this prevents overrun errors at high baud rates, even if
+    /// the CPU is busy. Please note that receive errors (like framing or parity
+    /// errors) are not reported for data that is received this way.
+    ///
+    /// Returns an error, if the length of `buffer` is odd, 0, or larger than
+    /// 2046. The error is returned together with the receiver, channel, and
+    /// buffer.
+    ///
+    /// [`CircularTransfer::read`]: ../dma/struct.CircularTransfer.html#method.read
+    /// [`CircularTransfer::stop`]: ../dma/struct.CircularTransfer.html#method.stop
+    pub fn read_circular(
+        self,
+        buffer: &'static mut [u8],
+        channel: dma::Channel<I::RxChannel, init_state::Enabled>,
+    ) -> Result<
+        dma::CircularTransfer<I::RxChannel, Self>,
+        (
+            dma::transfer::InvalidTransfer,
+            dma::Payload<I::RxChannel, Self, &'static mut [u8]>,
+        ),
+    > {
+        dma::CircularTransfer::start(channel, self, buffer)
+    }
+
+    /// Reads until the delimiter has been received, or the buffer is full
+    ///
+    /// Blocks until either `delim` has been received, or `buffer` is full.
+    /// The delimiter is written to the buffer too. Returns the number of bytes
+    /// written to the buffer.
+    ///
+    /// See [`read_until_nb`] for a non-blocking variant.
+    ///
+    /// [`read_until_nb`]: #method.read_until_nb
+    pub fn read_until(
+        &mut self,
+        delim: u8,
+        buffer: &mut [u8],
+    ) -> Result<usize, Error<u8>> {
+        let mut pos = 0;
+        nb::block!(self.read_until_nb(delim, buffer, &mut pos))
+    }
+
+    /// Reads until the delimiter has been received, or the buffer is full
+    ///
+    /// Non-blocking variant of [`read_until`]. Reads all bytes that are
+    /// currently available into `buffer`, starting at `*pos`, and advances
+    /// `*pos` accordingly. Returns `WouldBlock`, if neither `delim` has been
+    /// received, nor `buffer` is full yet. Once either is the case, returns
+    /// the total number of bytes written to the buffer, which is also the
+    /// value of `*pos`.
+    ///
+    /// `*pos` should be 0 for the first call. It needs to be reset to 0 by
+    /// the caller, before starting to read the next delimited sequence.
+    ///
+    /// If an error is returned, `*pos` still reflects the bytes that have
+    /// been received up to that point, so reading can be continued.
+    ///
+    /// [`read_until`]: #method.read_until
+    pub fn read_until_nb(
+        &mut self,
+        delim: u8,
+        buffer: &mut [u8],
+        pos: &mut usize,
+    ) -> nb::Result<usize, Error<u8>> {
+        while *pos < buffer.len() {
+            let byte = self.read()?;
+
+            buffer[*pos] = byte;
+            *pos += 1;
+
+            if byte == delim {
+                break;
+            }
+        }
+
+        Ok(*pos)
+    }
 }
 
 impl<I, W, Mode> Read<W> for Rx<I, Enabled<W, Mode>>
@@ -263,6 +630,12 @@ where
         let stat = usart.stat.read();
 
         if stat.rxbrk().bit_is_set() {
+            // Report the start of a break once, then wait for it to end.
+            if stat.deltarxbrk().bit_is_set() {
+                usart.stat.write(|w| w.deltarxbrk().set_bit());
+                return Err(nb::Error::Other(Error::Break));
+            }
+
             return Err(nb::Error::WouldBlock);
         }
 
@@ -294,6 +667,28 @@ where
     }
 }
 
+#[cfg(feature = "embedded-hal-1")]
+impl<I, W, Mode> embedded_hal_nb::serial::ErrorType for Rx<I, Enabled<W, Mode>>
+where
+    I: Instance,
+    W: Word + Copy + fmt::Debug,
+{
+    type Error = Error<W>;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<I, W, Mode> embedded_hal_nb::serial::Read<W> for Rx<I, Enabled<W, Mode>>
+where
+    I: Instance,
+    W: Word + Copy + fmt::Debug,
+{
+    /// Reads a single word from the serial interface
+    fn read(&mut self) -> nb::Result<W, Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        Read::read(self)
+    }
+}
+
 impl<I, State> crate::private::Sealed for Rx<I, State> {}
 
 impl<I, Mode> dma::Source for Rx<I, Enabled<u8, Mode>>
@@ -329,18 +724,122 @@ where
     }
 }
 
+/// The receive status of a single word
+///
+/// Returned by [`Rx::read_with_status`]. Each field is `true`, if the
+/// respective condition was detected while receiving the word.
+///
+/// [`Rx::read_with_status`]: struct.Rx.html#method.read_with_status
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[allow(non_snake_case)]
+pub struct WordStatus {
+    /// Stop bit missing at the expected location
+    pub FRAMERR: bool,
+
+    /// Parity error
+    pub PARITYERR: bool,
+
+    /// Noise detected
+    pub RXNOISE: bool,
+}
+
+impl WordStatus {
+    /// Indicates whether the word was received without any errors
+    pub fn is_ok(&self) -> bool {
+        !(self.FRAMERR || self.PARITYERR || self.RXNOISE)
+    }
+}
+
+/// Indicates that automatic baud rate detection failed
+///
+/// Returned by [`Rx::autobaud_status`]. This happens, if the start bit of the
+/// received character was too long to be measured.
+///
+/// [`Rx::autobaud_status`]: struct.Rx.html#method.autobaud_status
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AutobaudError;
+
 /// A USART error
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum Error<Word> {
     /// Character received with a stop bit missing at the expected location
+    ///
+    /// Consider calling [`Rx::recover`] to resynchronize.
+    ///
+    /// [`Rx::recover`]: struct.Rx.html#method.recover
     Framing(Word),
 
     /// Corrupted character received
     Noise(Word),
 
     /// Character received, while receive buffer was still in use
+    ///
+    /// Consider calling [`Rx::recover`] to resynchronize.
+    ///
+    /// [`Rx::recover`]: struct.Rx.html#method.recover
     Overrun(Word),
 
     /// Parity error detected in received character
     Parity(Word),
+
+    /// A break condition was detected on the receive line
+    ///
+    /// This error is returned once, when the break starts. After that, no data
+    /// is received until the break has ended.
+    Break,
+
+    /// In half-duplex mode, a character other than the one sent was received
+    ///
+    /// This indicates that another device was transmitting at the same time.
+    /// See [`USART::bwrite_all_half_duplex`].
+    ///
+    /// [`USART::bwrite_all_half_duplex`]: struct.USART.html#method.bwrite_all_half_duplex
+    Collision(Word),
+
+    /// The timer expired before the read operation completed
+    ///
+    /// See [`Rx::read_exact_timeout`].
+    ///
+    /// [`Rx::read_exact_timeout`]: struct.Rx.html#method.read_exact_timeout
+    TimedOut,
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<W> embedded_hal_nb::serial::Error for Error<W>
+where
+    W: fmt::Debug,
+{
+    fn kind(&self) -> embedded_hal_nb::serial::ErrorKind {
+        use embedded_hal_nb::serial::ErrorKind;
+
+        match self {
+            Self::Framing(_) => ErrorKind::FrameFormat,
+            Self::Noise(_) => ErrorKind::Noise,
+            Self::Overrun(_) => ErrorKind::Overrun,
+            Self::Parity(_) => ErrorKind::Parity,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<W> embedded_io_async::Error for Error<W>
+where
+    W: fmt::Debug,
+{
+    fn kind(&self) -> embedded_io_async::ErrorKind {
+        use embedded_io_async::ErrorKind;
+
+        match self {
+            Self::Framing(_) | Self::Noise(_) | Self::Parity(_) => {
+                ErrorKind::InvalidData
+            }
+            Self::TimedOut => ErrorKind::TimedOut,
+            _ => ErrorKind::Other,
+        }
+    }
 }
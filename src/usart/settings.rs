@@ -16,6 +16,8 @@ pub struct Settings<Word = u8> {
     pub(super) clock_pol: CLKPOL_A,
     pub(super) rx_pol: RXPOL_A,
     pub(super) tx_pol: TXPOL_A,
+    pub(super) loopback: bool,
+    pub(super) continuous_clock: bool,
 
     _word: PhantomData<Word>,
 }
@@ -137,6 +139,49 @@ impl<Word> Settings<Word> {
         self
     }
 
+    /// Disable loopback mode
+    ///
+    /// Overwrites the previous loopback setting. This is the default.
+    pub fn loopback_disabled(mut self) -> Self {
+        self.loopback = false;
+        self
+    }
+
+    /// Enable loopback mode
+    ///
+    /// In loopback mode, the transmitter output is internally connected to the
+    /// receiver input. This can be used to test the serial path without
+    /// external wiring. The transmitted data is still sent on the TX pin.
+    ///
+    /// Overwrites the previous loopback setting.
+    pub fn loopback_enabled(mut self) -> Self {
+        self.loopback = true;
+        self
+    }
+
+    /// Output the clock continuously
+    ///
+    /// This is only relevant in synchronous master mode. SCLK is output
+    /// continuously, whether data is being transferred or not.
+    ///
+    /// Overwrites the previous clock generation setting. This is the default.
+    pub fn clock_continuous(mut self) -> Self {
+        self.continuous_clock = true;
+        self
+    }
+
+    /// Only output the clock while data is being transferred
+    ///
+    /// This is only relevant in synchronous master mode. SCLK only cycles while
+    /// a character is being sent, or to complete a character that is being
+    /// received. Some synchronous slaves require this.
+    ///
+    /// Overwrites the previous clock generation setting.
+    pub fn clock_on_demand(mut self) -> Self {
+        self.continuous_clock = false;
+        self
+    }
+
     fn transmute<NewW>(self) -> Settings<NewW> {
         Settings {
             data_len: self.data_len,
@@ -145,6 +190,8 @@ impl<Word> Settings<Word> {
             clock_pol: self.clock_pol,
             rx_pol: self.rx_pol,
             tx_pol: self.tx_pol,
+            loopback: self.loopback,
+            continuous_clock: self.continuous_clock,
             _word: PhantomData,
         }
     }
@@ -156,6 +203,7 @@ impl<Word> Settings<Word> {
         w.clkpol().variant(self.clock_pol);
         w.rxpol().variant(self.rx_pol);
         w.txpol().variant(self.tx_pol);
+        w.loop_().bit(self.loopback);
     }
 }
 
@@ -168,7 +216,56 @@ impl Default for Settings {
             clock_pol: CLKPOL_A::FALLING_EDGE,
             rx_pol: RXPOL_A::STANDARD,
             tx_pol: TXPOL_A::STANDARD,
+            loopback: false,
+            continuous_clock: true,
             _word: PhantomData,
         }
     }
 }
+
+#[cfg(feature = "defmt")]
+impl<Word> defmt::Format for Settings<Word> {
+    fn format(&self, f: defmt::Formatter) {
+        let data_len = match self.data_len {
+            DATALEN_A::BIT_7 => 7u8,
+            DATALEN_A::BIT_8 => 8,
+            DATALEN_A::BIT_9 => 9,
+        };
+        let parity = match self.parity {
+            PARITYSEL_A::NO_PARITY => "none",
+            PARITYSEL_A::EVEN_PARITY => "even",
+            PARITYSEL_A::ODD_PARITY => "odd",
+        };
+        let stop_len = match self.stop_len {
+            STOPLEN_A::BIT_1 => 1u8,
+            STOPLEN_A::BITS_2 => 2,
+        };
+        let clock_pol = match self.clock_pol {
+            CLKPOL_A::FALLING_EDGE => "falling",
+            CLKPOL_A::RISING_EDGE => "rising",
+        };
+        let rx_inverted = match self.rx_pol {
+            RXPOL_A::STANDARD => false,
+            RXPOL_A::INVERTED => true,
+        };
+        let tx_inverted = match self.tx_pol {
+            TXPOL_A::STANDARD => false,
+            TXPOL_A::INVERTED => true,
+        };
+
+        defmt::write!(
+            f,
+            "Settings {{ data_len: {=u8}, parity: {=str}, stop_len: {=u8}, \
+            clock_pol: {=str}, rx_inverted: {=bool}, tx_inverted: {=bool}, \
+            loopback: {=bool}, continuous_clock: {=bool} }}",
+            data_len,
+            parity,
+            stop_len,
+            clock_pol,
+            rx_inverted,
+            tx_inverted,
+            self.loopback,
+            self.continuous_clock,
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use core::{fmt, marker::PhantomData};
 
+#[cfg(feature = "embedded-hal-1")]
+use core::convert::Infallible;
+
 use cortex_m::interrupt;
 use embedded_hal::{
     blocking::serial::write::Default as BlockingWriteDefault, serial::Write,
@@ -120,6 +123,87 @@ where
         function.unassign(pin, swm)
     }
 
+    /// Start sending a break
+    ///
+    /// Holds the transmit line in the break state, until [`Tx::stop_break`]
+    /// is called. Returns `WouldBlock`, if the transmitter is still sending
+    /// data, as starting a break then would corrupt the current character.
+    ///
+    /// [`Tx::stop_break`]: #method.stop_break
+    pub fn start_break(&mut self) -> nb::Result<(), Void> {
+        // Sound, as we're only reading from `stat`, and access to `ctl` is
+        // protected by a critical section.
+        let usart = unsafe { &*I::REGISTERS };
+
+        if usart.stat.read().txidle().bit_is_clear() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        interrupt::free(|_| {
+            usart.ctl.modify(|_, w| w.txbrken().set_bit());
+        });
+
+        Ok(())
+    }
+
+    /// Stop sending a break
+    ///
+    /// Returns the transmit line to the idle state. Calling this method while
+    /// no break is being sent has no effect.
+    pub fn stop_break(&mut self) {
+        // Sound, as access to `ctl` is protected by a critical section.
+        let usart = unsafe { &*I::REGISTERS };
+
+        interrupt::free(|_| {
+            usart.ctl.modify(|_, w| w.txbrken().normal());
+        });
+    }
+
+    /// Pause the transmitter
+    ///
+    /// The character currently being sent, if any, is completed. After that,
+    /// no more data is sent until [`Tx::resume`] is called. Data that is
+    /// written in the meantime stays in the transmitter. This can be used to
+    /// temporarily release a shared bus, without disabling the whole USART.
+    ///
+    /// Use [`Tx::is_paused`] to find out when the transmitter has actually
+    /// stopped.
+    ///
+    /// [`Tx::resume`]: #method.resume
+    /// [`Tx::is_paused`]: #method.is_paused
+    pub fn pause(&mut self) {
+        // Sound, as access to `ctl` is protected by a critical section.
+        let usart = unsafe { &*I::REGISTERS };
+
+        interrupt::free(|_| {
+            usart.ctl.modify(|_, w| w.txdis().disabled());
+        });
+    }
+
+    /// Resume the transmitter after it has been paused
+    ///
+    /// Calling this method while the transmitter isn't paused has no effect.
+    pub fn resume(&mut self) {
+        // Sound, as access to `ctl` is protected by a critical section.
+        let usart = unsafe { &*I::REGISTERS };
+
+        interrupt::free(|_| {
+            usart.ctl.modify(|_, w| w.txdis().enabled());
+        });
+    }
+
+    /// Indicates whether the transmitter is paused
+    ///
+    /// Returns `true` after [`Tx::pause`] has been called, once the character
+    /// that was being sent at that point has been completed. Corresponds to
+    /// [`Flag::TXDIS`].
+    ///
+    /// [`Tx::pause`]: #method.pause
+    /// [`Flag::TXDIS`]: enum.Flag.html#variant.TXDIS
+    pub fn is_paused(&self) -> bool {
+        Flag::TXDIS.is_set::<I>()
+    }
+
     /// Query whether the provided flag is set
     ///
     /// Flags that need to be reset by software will be reset by this operation.
@@ -311,6 +395,22 @@ where
             self.throttle.0,
         )
     }
+
+    /// Indicates whether the CTS signal is currently asserted
+    ///
+    /// While CTS is not asserted, the transmitter stalls, and writes will
+    /// return `WouldBlock` once the transmit buffer is full.
+    pub fn is_cts_asserted(&self) -> bool {
+        Flag::CTS.is_set::<I>()
+    }
+
+    /// Indicates whether the CTS signal has changed
+    ///
+    /// Returns `true`, if the CTS signal has changed since the last call.
+    /// Resets the DELTACTS flag, which also clears a pending interrupt.
+    pub fn cts_changed(&mut self) -> bool {
+        Flag::DELTACTS.is_set::<I>()
+    }
 }
 
 impl<I, Mode, Throttle> Tx<I, Enabled<u8, Mode>, Throttle>
@@ -319,18 +419,40 @@ where
 {
     /// Writes the provided buffer using DMA
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    /// Returns an error, if the length of `buffer` is 0 or larger than 1024.
+    /// The error is returned together with the transmitter, buffer, and
+    /// channel.
     pub fn write_all(
         self,
         buffer: &'static [u8],
         channel: dma::Channel<I::TxChannel, init_state::Enabled>,
-    ) -> dma::Transfer<Ready, I::TxChannel, &'static [u8], Self> {
+    ) -> Result<
+        dma::Transfer<Ready, I::TxChannel, &'static [u8], Self>,
+        (
+            dma::transfer::InvalidTransfer,
+            dma::Payload<I::TxChannel, &'static [u8], Self>,
+        ),
+    > {
         dma::Transfer::new(channel, buffer, self)
     }
 }
 
+impl<I, Mode, Throttle> Tx<I, Enabled<u16, Mode>, Throttle>
+where
+    I: Instance,
+{
+    /// Write an address to a multi-drop network
+    ///
+    /// Writes `address` with bit 8 set, which marks it as an address for
+    /// receivers that are in address detection mode. Only available in 9-bit
+    /// mode.
+    pub fn write_address(&mut self, address: u8) -> nb::Result<(), Void> {
+        self.write(0x100 | u16::from(address))
+    }
+}
+
 impl<I, W, Mode, Throttle> Write<W> for Tx<I, Enabled<W, Mode>, Throttle>
 where
     I: Instance,
@@ -374,15 +496,61 @@ where
 {
 }
 
-impl<I, Mode, Throttle> fmt::Write for Tx<I, Enabled<u8, Mode>, Throttle>
+#[cfg(feature = "embedded-hal-1")]
+impl<I, W, Mode, Throttle> embedded_hal_nb::serial::ErrorType
+    for Tx<I, Enabled<W, Mode>, Throttle>
+where
+    I: Instance,
+    W: Word + Copy,
+{
+    type Error = Infallible;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl<I, W, Mode, Throttle> embedded_hal_nb::serial::Write<W>
+    for Tx<I, Enabled<W, Mode>, Throttle>
+where
+    I: Instance,
+    W: Word + Copy,
+{
+    /// Writes a single word to the serial interface
+    fn write(&mut self, word: W) -> nb::Result<(), Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        Write::write(self, word)
+            .map_err(|err| err.map(|void| void::unreachable(void)))
+    }
+
+    /// Ensures that none of the previously written words are still buffered
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        // Call the embedded-hal 0.2 implementation above.
+        Write::flush(self)
+            .map_err(|err| err.map(|void| void::unreachable(void)))
+    }
+}
+
+impl<I, W, Mode, Throttle> fmt::Write for Tx<I, Enabled<W, Mode>, Throttle>
 where
-    Self: BlockingWriteDefault<u8>,
     I: Instance,
+    W: Word,
 {
+    /// Writes a string slice into this writer
+    ///
+    /// Works with all word sizes. Each byte of the string is sent as one word.
+    /// If the USART is configured for 7-bit words, only ASCII strings can be
+    /// sent, and an error is returned for any other string, before anything
+    /// is sent.
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        use crate::prelude::*;
+        // Sound, as we're only reading from a register.
+        let usart = unsafe { &*I::REGISTERS };
 
-        self.bwrite_all(s.as_bytes()).map_err(|_| fmt::Error)?;
+        if usart.cfg.read().datalen().is_bit_7() && !s.is_ascii() {
+            return Err(fmt::Error);
+        }
+
+        for byte in s.bytes() {
+            block!(self.write(W::from_u16(byte.into())))
+                .map_err(|_| fmt::Error)?;
+        }
         block!(self.flush()).map_err(|_| fmt::Error)?;
 
         Ok(())
@@ -0,0 +1,39 @@
+//! Storage for the wakers of the async APIs
+//!
+//! The futures of the various async APIs register their waker here, and the
+//! interrupt handlers wake it from there.
+
+use core::{cell::RefCell, task::Waker};
+
+use cortex_m::interrupt::{self, Mutex};
+
+/// Storage for a single waker
+///
+/// This type is an internal implementation detail.
+pub struct WakerSlot(Mutex<RefCell<Option<Waker>>>);
+
+impl WakerSlot {
+    /// Create an empty slot
+    pub(crate) const fn new() -> Self {
+        Self(Mutex::new(RefCell::new(None)))
+    }
+
+    /// Register a waker, replacing the previous one
+    pub(crate) fn register(&self, waker: &Waker) {
+        interrupt::free(|cs| {
+            let mut slot = self.0.borrow(cs).borrow_mut();
+            match slot.as_ref() {
+                Some(registered) if registered.will_wake(waker) => {}
+                _ => *slot = Some(waker.clone()),
+            }
+        })
+    }
+
+    /// Wake the registered waker, if any, and clear the slot
+    pub(crate) fn wake(&self) {
+        let waker = interrupt::free(|cs| self.0.borrow(cs).borrow_mut().take());
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}